@@ -6,14 +6,18 @@ use evenframe_core::{
         parse_format_attribute_bin, parse_mock_data_attribute, parse_relation_attribute,
         parse_table_validators,
     },
+    derive::validator_parser::parse_validators,
     schemasync::table::TableConfig,
     schemasync::{DefineConfig, EdgeConfig, PermissionsConfig},
-    types::{FieldType, StructConfig, StructField, TaggedUnion, Variant, VariantData},
+    types::{FieldType, StructConfig, StructField, StructKind, TaggedUnion, Variant, VariantData},
     validator::{StringValidator, Validator},
 };
 use std::collections::HashMap;
 use std::fs;
-use syn::{Fields, FieldsNamed, Item, ItemEnum, ItemStruct, parse_file};
+use syn::{
+    Attribute, Field, Fields, Item, ItemEnum, ItemStruct, parse_file, punctuated::Punctuated,
+    token::Comma,
+};
 use tracing::{debug, info, trace, warn};
 
 pub fn build_all_configs() -> (
@@ -175,34 +179,83 @@ pub fn build_all_configs() -> (
 fn parse_struct_config(item_struct: &ItemStruct) -> Option<StructConfig> {
     let struct_name = item_struct.ident.to_string();
     trace!("Parsing struct config for: {}", struct_name);
-    let mut fields = Vec::new();
-
-    if let Fields::Named(ref fields_named) = item_struct.fields {
-        debug!(
-            "Processing {} fields for struct {}",
-            fields_named.named.len(),
-            struct_name
-        );
-        fields = process_struct_fields(fields_named);
-    }
+    let generics = generic_param_names(&item_struct.generics);
+
+    let (fields, kind) = match item_struct.fields {
+        Fields::Named(ref fields_named) => {
+            debug!(
+                "Processing {} fields for struct {}",
+                fields_named.named.len(),
+                struct_name
+            );
+            (
+                process_struct_fields(&fields_named.named, &generics),
+                StructKind::Named,
+            )
+        }
+        Fields::Unnamed(ref fields_unnamed) => {
+            debug!(
+                "Processing {} positional fields for tuple struct {}",
+                fields_unnamed.unnamed.len(),
+                struct_name
+            );
+            let kind = if fields_unnamed.unnamed.len() == 1 {
+                StructKind::Newtype
+            } else {
+                StructKind::Tuple
+            };
+            (
+                process_struct_fields(&fields_unnamed.unnamed, &generics),
+                kind,
+            )
+        }
+        Fields::Unit => (Vec::new(), StructKind::Named),
+    };
 
-    let table_validators = parse_table_validators(&item_struct.attrs)
-        .ok()
-        .unwrap_or_default();
+    let validators = parse_struct_validators(&item_struct.attrs, &struct_name);
 
     Some(StructConfig {
         struct_name, // Keep original name, don't convert to snake_case
         fields,
-        validators: table_validators
-            .into_iter()
-            .map(|v| Validator::StringValidator(StringValidator::StringEmbedded(v)))
-            .collect(),
+        validators,
+        generics,
+        kind,
     })
 }
 
+/// Struct-level validators. `#[validators(custom = "$value > 0")]` is embedded as is,
+/// other validators are parsed like the derive does and also apply to the wrapped value
+/// of a newtype.
+fn parse_struct_validators(attrs: &[Attribute], struct_name: &str) -> Vec<Validator> {
+    let mut validators = Vec::new();
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("validators"))
+    {
+        let attr = std::slice::from_ref(attr);
+        if let Ok(custom) = parse_table_validators(attr) {
+            validators.extend(
+                custom
+                    .into_iter()
+                    .map(|v| Validator::StringValidator(StringValidator::StringEmbedded(v))),
+            );
+            continue;
+        }
+        match parse_validators(attr) {
+            Ok(typed) => validators.extend(typed),
+            Err(e) => warn!(
+                "Skipping invalid validators on struct '{}': {}",
+                struct_name, e
+            ),
+        }
+    }
+    validators
+}
+
 fn parse_enum_config(item_enum: &ItemEnum) -> Option<TaggedUnion> {
     let enum_name = item_enum.ident.to_string();
     trace!("Parsing enum config for: {}", enum_name);
+    let generics = generic_param_names(&item_enum.generics);
     let mut variants = Vec::new();
 
     for variant in &item_enum.variants {
@@ -216,13 +269,14 @@ fn parse_enum_config(item_enum: &ItemEnum) -> Option<TaggedUnion> {
                     None
                 } else if fields.unnamed.len() == 1 {
                     let field = &fields.unnamed[0];
-                    let field_type = FieldType::parse_syn_ty(&field.ty);
+                    let field_type =
+                        FieldType::parse_syn_ty(&field.ty).resolve_generic_params(&generics);
                     Some(VariantData::DataStructureRef(field_type))
                 } else {
                     let field_types: Vec<_> = fields
                         .unnamed
                         .iter()
-                        .map(|f| FieldType::parse_syn_ty(&f.ty))
+                        .map(|f| FieldType::parse_syn_ty(&f.ty).resolve_generic_params(&generics))
                         .collect();
                     Some(VariantData::DataStructureRef(FieldType::Tuple(field_types)))
                 }
@@ -233,12 +287,14 @@ fn parse_enum_config(item_enum: &ItemEnum) -> Option<TaggedUnion> {
                     fields_named.named.len(),
                     variant_name
                 );
-                let struct_fields = process_struct_fields(fields_named);
+                let struct_fields = process_struct_fields(&fields_named.named, &generics);
 
                 Some(VariantData::InlineStruct(StructConfig {
                     struct_name: variant_name.clone(),
                     fields: struct_fields,
                     validators: vec![],
+                    generics: vec![],
+                    kind: StructKind::Named,
                 }))
             }
         };
@@ -252,21 +308,35 @@ fn parse_enum_config(item_enum: &ItemEnum) -> Option<TaggedUnion> {
     Some(TaggedUnion {
         enum_name,
         variants,
+        generics,
     })
 }
 
-fn process_struct_fields(fields_named: &FieldsNamed) -> Vec<StructField> {
+/// Names of the type parameters declared on a struct or enum
+fn generic_param_names(generics: &syn::Generics) -> Vec<String> {
+    generics
+        .type_params()
+        .map(|param| param.ident.to_string())
+        .collect()
+}
+
+/// Build the fields of a struct or struct-like variant. Positional fields of
+/// tuple structs are named by their index (`0`, `1`, ...).
+fn process_struct_fields(
+    fields: &Punctuated<Field, Comma>,
+    generics: &[String],
+) -> Vec<StructField> {
     let mut struct_fields = Vec::new();
-    for field in &fields_named.named {
+    for (index, field) in fields.iter().enumerate() {
         let field_name = field
             .ident
             .as_ref()
-            .expect("Something went wrong getting the field name")
-            .to_string();
+            .map(|ident| ident.to_string())
+            .unwrap_or_else(|| index.to_string());
         let field_name = field_name.trim_start_matches("r#").to_string();
 
         // Parse field type directly to FieldType
-        let field_type = FieldType::parse_syn_ty(&field.ty);
+        let field_type = FieldType::parse_syn_ty(&field.ty).resolve_generic_params(generics);
 
         // Parse attributes using the derive module's parsers
         let edge_config = EdgeConfig::parse(field).ok().flatten();
@@ -275,8 +345,17 @@ fn process_struct_fields(fields_named: &FieldsNamed) -> Vec<StructField> {
         // Parse format
         let format = parse_format_attribute_bin(&field.attrs).ok().flatten();
 
-        // Parse validators - simplified for now
-        let validators = vec![];
+        // Parse validators the same way the derive does
+        let validators = match parse_validators(&field.attrs) {
+            Ok(validators) => validators,
+            Err(e) => {
+                warn!(
+                    "Skipping invalid validators on field '{}': {}",
+                    field_name, e
+                );
+                Vec::new()
+            }
+        };
 
         struct_fields.push(StructField {
             field_name,
@@ -314,3 +393,53 @@ pub fn merge_tables_and_objects(
     );
     struct_configs
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_newtype_keeps_struct_validators() {
+        let item_struct: ItemStruct = syn::parse_str(
+            r#"
+            #[validators(StringValidator::Email)]
+            pub struct Email(#[validators(StringValidator::MaxLength(254))] String);
+            "#,
+        )
+        .unwrap();
+        let config = parse_struct_config(&item_struct).unwrap();
+        assert_eq!(config.kind, StructKind::Newtype);
+        assert_eq!(
+            config.validators,
+            vec![Validator::StringValidator(StringValidator::Email)]
+        );
+        assert_eq!(
+            config.newtype_field().unwrap().validators,
+            vec![Validator::StringValidator(StringValidator::MaxLength(254))]
+        );
+    }
+
+    #[test]
+    fn test_struct_validators_of_both_forms() {
+        let item_struct: ItemStruct = syn::parse_str(
+            r#"
+            #[validators(custom = "$value.total > 0")]
+            #[validators(StringValidator::Email)]
+            pub struct Order {
+                pub total: f64,
+            }
+            "#,
+        )
+        .unwrap();
+        let config = parse_struct_config(&item_struct).unwrap();
+        assert_eq!(
+            config.validators,
+            vec![
+                Validator::StringValidator(StringValidator::StringEmbedded(
+                    "$value.total > 0".to_string()
+                )),
+                Validator::StringValidator(StringValidator::Email),
+            ]
+        );
+    }
+}
@@ -1,7 +1,10 @@
-pub use evenframe_core::{config, schemasync, traits, types, validator, wrappers};
+pub use evenframe_core::{
+    config, error, schemasync, surrealdb, traits, types, validator, wrappers,
+};
 pub use evenframe_derive::Evenframe;
 
 pub mod prelude {
     pub use convert_case::{Case, Casing};
+    pub use evenframe_core::traits::{EvenframeRelation, EvenframeRepository};
     pub use regex;
 }
@@ -3,10 +3,15 @@ mod workspace_scanner;
 
 use evenframe_core::evenframe_log;
 use evenframe_core::schemasync::Schemasync; // Import your new struct
+use evenframe_core::schemasync::backup::{resolve_backup, restore};
 use evenframe_core::{
     config::EvenframeConfig,
-    error::Result,
-    typesync::{arktype::generate_arktype_type_string, effect::generate_effect_schema_string},
+    error::{EvenframeError, Result},
+    typesync::{
+        arktype::generate_arktype_type_string,
+        effect::generate_effect_schema_string,
+        live::{generate_arktype_live_helpers, generate_effect_live_helpers},
+    },
 };
 use tracing::{debug, error, info};
 
@@ -34,6 +39,12 @@ async fn main() -> Result<()> {
         }
     };
 
+    let args: Vec<String> = std::env::args().collect();
+
+    // `evenframe fixtures` writes the mock data to the configured fixture files without
+    // touching the database
+    let write_fixtures = args.get(1).map(String::as_str) == Some("fixtures");
+
     let generate_arktype_types = config.typesync.should_generate_arktype_types;
     let generate_effect_schemas = config.typesync.should_generate_effect_types;
 
@@ -52,24 +63,78 @@ async fn main() -> Result<()> {
         objects.len()
     );
 
+    // `evenframe restore [BACKUP]` re-imports a backup (the latest when none is given)
+    // instead of generating anything
+    if args.get(1).map(String::as_str) == Some("restore") {
+        let path = resolve_backup(&config.schemasync, args.get(2).map(String::as_str))?;
+        info!("Restoring backup {}", path.display());
+        let managed_tables: Vec<String> = tables.keys().cloned().collect();
+        restore(&config.schemasync, &path, &managed_tables).await?;
+        info!("Backup restored successfully");
+        return Ok(());
+    }
+
+    // `evenframe scenario <NAME>` resets the database to the named scenario file
+    if args.get(1).map(String::as_str) == Some("scenario") {
+        let name = args
+            .get(2)
+            .ok_or_else(|| EvenframeError::config("Usage: evenframe scenario <NAME>"))?;
+        Schemasync::new()
+            .with_tables(&tables)
+            .with_objects(&objects)
+            .with_enums(&enums)
+            .apply_scenario(name)
+            .await?;
+        info!("Scenario '{}' applied successfully", name);
+        return Ok(());
+    }
+
+    if write_fixtures {
+        info!("Writing mock data fixtures...");
+        let written = Schemasync::new()
+            .with_tables(&tables)
+            .with_objects(&objects)
+            .with_enums(&enums)
+            .export_fixtures()?;
+        info!(
+            "Wrote {} fixture files to {}",
+            written.len(),
+            config.schemasync.fixtures.directory
+        );
+        return Ok(());
+    }
+
     if generate_arktype_types {
         info!("Generating arktype types...");
         let structs = config_builders::merge_tables_and_objects(&tables, &objects);
         debug!("Merged {} structs for arktype generation", structs.len());
 
-        let arktype_content = generate_arktype_type_string(&structs, &enums, false);
+        let arktype_content = generate_arktype_type_string(
+            &structs,
+            &enums,
+            false,
+            config.typesync.should_brand_newtypes,
+            config.typesync.big_number_mode,
+        );
         debug!(
             "Generated arktype content: {} characters",
             arktype_content.len()
         );
 
+        let (arktype_imports, live_helpers) = if config.typesync.should_generate_live_query_helpers
+        {
+            ("scope, type", generate_arktype_live_helpers(&tables))
+        } else {
+            ("scope", String::new())
+        };
+
         match std::fs::write(
             format!("{}arktype.ts", config.typesync.output_path),
             format!(
-                "import {{ scope }} from 'arktype';\n\n{}\n\n export const validator = scope({{
+                "import {{ {} }} from 'arktype';\n\n{}\n\n export const validator = scope({{
   ...bindings.export(),
-            }}).export();",
-                arktype_content,
+            }}).export();\n\n{}",
+                arktype_imports, arktype_content, live_helpers,
             ),
         ) {
             Ok(_) => info!("Arktype types written successfully to arktype.ts"),
@@ -87,7 +152,16 @@ async fn main() -> Result<()> {
         let structs = config_builders::merge_tables_and_objects(&tables, &objects);
         debug!("Merged {} structs for Effect generation", structs.len());
 
-        let effect_content = generate_effect_schema_string(&structs, &enums, false);
+        let mut effect_content = generate_effect_schema_string(
+            &structs,
+            &enums,
+            false,
+            config.typesync.should_brand_newtypes,
+            config.typesync.big_number_mode,
+        );
+        if config.typesync.should_generate_live_query_helpers {
+            effect_content.push_str(&generate_effect_live_helpers(&tables));
+        }
         debug!(
             "Generated Effect content: {} characters",
             effect_content.len()
@@ -1,5 +1,10 @@
 use super::schemasync::*;
-use crate::types::{FieldType, StructConfig, StructField, TaggedUnion, VariantData};
+use crate::mockmake::geometry::default_geometry;
+use crate::types::{
+    FieldType, GeometryKind, StructConfig, StructField, StructKind, TaggedUnion, VariantData,
+    register_generic_instances,
+};
+use crate::typesync::config::BigNumberMode;
 use convert_case::{Case, Casing};
 use rand::{rng, seq::IndexedRandom};
 use std::collections::HashMap;
@@ -9,6 +14,7 @@ pub fn field_type_to_default_value(
     field_type: &FieldType,
     structs: &HashMap<String, StructConfig>,
     enums: &HashMap<String, TaggedUnion>,
+    big_numbers: BigNumberMode,
 ) -> String {
     trace!("Generating default value for field type: {:?}", field_type);
     let result = match field_type {
@@ -40,27 +46,80 @@ pub fn field_type_to_default_value(
             trace!("Generating default for Decimal type");
             r#""0""#.to_string()
         }
+        FieldType::Uuid => {
+            trace!("Generating default for Uuid type");
+            r#""00000000-0000-0000-0000-000000000000""#.to_string()
+        }
+        FieldType::NaiveDate => {
+            trace!("Generating default for NaiveDate type");
+            r#""2024-01-01""#.to_string()
+        }
+        FieldType::NaiveTime => {
+            trace!("Generating default for NaiveTime type");
+            r#""00:00:00""#.to_string()
+        }
+        FieldType::Duration => {
+            trace!("Generating default for Duration type");
+            "{ secs: 0, nanos: 0 }".to_string()
+        }
+        FieldType::Bytes => {
+            trace!("Generating default for Bytes type");
+            "[]".to_string()
+        }
+        FieldType::JsonValue => {
+            trace!("Generating default for JsonValue type");
+            "null".to_string()
+        }
+        FieldType::IpAddr => {
+            trace!("Generating default for IpAddr type");
+            r#""0.0.0.0""#.to_string()
+        }
+        FieldType::Url => {
+            trace!("Generating default for Url type");
+            r#""http://localhost""#.to_string()
+        }
+        FieldType::Geometry(kind) => {
+            trace!("Generating default for Geometry type: {:?}", kind);
+            let geojson_type = kind.geojson_type().unwrap_or("Point");
+            let coordinates = match kind {
+                GeometryKind::Point | GeometryKind::Any => "[0, 0]",
+                GeometryKind::LineString | GeometryKind::MultiPoint => "[]",
+                GeometryKind::Polygon | GeometryKind::MultiLineString => "[[]]",
+                GeometryKind::MultiPolygon => "[[[]]]",
+            };
+            format!(
+                "{{ type: '{}', coordinates: {} }}",
+                geojson_type, coordinates
+            )
+        }
         FieldType::OrderedFloat(inner) => {
             trace!(
                 "Generating default for OrderedFloat with inner: {:?}",
                 inner
             );
-            field_type_to_default_value(inner, structs, enums)
+            field_type_to_default_value(inner, structs, enums, big_numbers)
+        }
+        FieldType::I64
+        | FieldType::I128
+        | FieldType::Isize
+        | FieldType::U64
+        | FieldType::U128
+        | FieldType::Usize => {
+            trace!("Generating default for wide integer type");
+            match big_numbers {
+                BigNumberMode::Number => "0".to_string(),
+                BigNumberMode::BigInt => "0n".to_string(),
+                BigNumberMode::String => r#""0""#.to_string(),
+            }
         }
         FieldType::F32
         | FieldType::F64
         | FieldType::I8
         | FieldType::I16
         | FieldType::I32
-        | FieldType::I64
-        | FieldType::I128
-        | FieldType::Isize
         | FieldType::U8
         | FieldType::U16
-        | FieldType::U32
-        | FieldType::U64
-        | FieldType::U128
-        | FieldType::Usize => {
+        | FieldType::U32 => {
             trace!("Generating default for numeric type");
             "0".to_string()
         }
@@ -75,7 +134,7 @@ pub fn field_type_to_default_value(
             );
             let tuple_defaults: Vec<String> = inner_types
                 .iter()
-                .map(|ty| field_type_to_default_value(ty, structs, enums))
+                .map(|ty| field_type_to_default_value(ty, structs, enums, big_numbers))
                 .collect();
             format!("[{}]", tuple_defaults.join(", "))
         }
@@ -87,7 +146,7 @@ pub fn field_type_to_default_value(
                     format!(
                         "{}: {}",
                         name.to_case(Case::Camel),
-                        field_type_to_default_value(ftype, structs, enums)
+                        field_type_to_default_value(ftype, structs, enums, big_numbers)
                     )
                 })
                 .collect::<Vec<_>>()
@@ -107,6 +166,10 @@ pub fn field_type_to_default_value(
             trace!("Generating default for Vec type with inner: {:?}", inner);
             "[]".to_string()
         }
+        FieldType::HashSet(inner) | FieldType::BTreeSet(inner) => {
+            trace!("Generating default for Set type with inner: {:?}", inner);
+            "[]".to_string()
+        }
         FieldType::HashMap(key, value) => {
             // Return an empty object as default
             trace!(
@@ -131,6 +194,25 @@ pub fn field_type_to_default_value(
             trace!("Generating default for RecordLink with inner: {:?}", inner);
             "''".to_string()
         }
+        FieldType::GenericParam(name) => {
+            trace!("Unbound generic parameter {}, returning undefined", name);
+            "undefined".to_string()
+        }
+        FieldType::Generic(..) => {
+            debug!(
+                "Generating default for generic instantiation: {}",
+                field_type
+            );
+            let mut structs = structs.clone();
+            let mut enums = enums.clone();
+            register_generic_instances(field_type, &mut structs, &mut enums);
+            field_type_to_default_value(
+                &FieldType::Other(field_type.to_string()),
+                &structs,
+                &enums,
+                big_numbers,
+            )
+        }
         FieldType::Other(name) => {
             // 1) If this is an enum, pick a random variant.
             // 2) Otherwise if it matches a known table, produce a default object for that table.
@@ -155,8 +237,12 @@ pub fn field_type_to_default_value(
                             }
                             VariantData::DataStructureRef(field_type) => field_type,
                         };
-                        let data_default =
-                            field_type_to_default_value(variant_data_field_type, structs, enums);
+                        let data_default = field_type_to_default_value(
+                            variant_data_field_type,
+                            structs,
+                            enums,
+                            big_numbers,
+                        );
                         return data_default;
                     } else {
                         // A variant without data
@@ -176,6 +262,24 @@ pub fn field_type_to_default_value(
                     name,
                     struct_config.fields.len()
                 );
+                if let Some(inner) = struct_config.newtype_field() {
+                    return field_type_to_default_value(
+                        &inner.field_type,
+                        structs,
+                        enums,
+                        big_numbers,
+                    );
+                }
+                if struct_config.kind == StructKind::Tuple {
+                    let items = struct_config
+                        .fields
+                        .iter()
+                        .map(|f| {
+                            field_type_to_default_value(&f.field_type, structs, enums, big_numbers)
+                        })
+                        .collect::<Vec<_>>();
+                    return format!("[{}]", items.join(", "));
+                }
                 // We treat this similarly to a struct:
                 let fields_str = struct_config
                     .fields
@@ -184,7 +288,12 @@ pub fn field_type_to_default_value(
                         format!(
                             "{}: {}",
                             table_field.field_name.to_case(Case::Camel),
-                            field_type_to_default_value(&table_field.field_type, structs, enums)
+                            field_type_to_default_value(
+                                &table_field.field_type,
+                                structs,
+                                enums,
+                                big_numbers
+                            )
                         )
                     })
                     .collect::<Vec<_>>()
@@ -249,6 +358,42 @@ pub fn field_type_to_surql_default(
             trace!("Generating SURQL default for Decimal");
             "0.00dec".to_string()
         }
+        FieldType::Uuid => {
+            trace!("Generating SURQL default for Uuid");
+            "u'00000000-0000-0000-0000-000000000000'".to_string()
+        }
+        FieldType::NaiveDate => {
+            trace!("Generating SURQL default for NaiveDate");
+            "d'2024-01-01'".to_string()
+        }
+        FieldType::NaiveTime => {
+            trace!("Generating SURQL default for NaiveTime");
+            "'00:00:00'".to_string()
+        }
+        FieldType::Duration => {
+            trace!("Generating SURQL default for Duration");
+            "0ns".to_string()
+        }
+        FieldType::Bytes => {
+            trace!("Generating SURQL default for Bytes");
+            "<bytes>''".to_string()
+        }
+        FieldType::JsonValue => {
+            trace!("Generating SURQL default for JsonValue");
+            "NULL".to_string()
+        }
+        FieldType::IpAddr => {
+            trace!("Generating SURQL default for IpAddr");
+            "'0.0.0.0'".to_string()
+        }
+        FieldType::Url => {
+            trace!("Generating SURQL default for Url");
+            "'http://localhost'".to_string()
+        }
+        FieldType::Geometry(kind) => {
+            trace!("Generating SURQL default for Geometry: {:?}", kind);
+            default_geometry(*kind)
+        }
         FieldType::OrderedFloat(inner) => {
             trace!(
                 "Generating SURQL default for OrderedFloat with inner: {:?}",
@@ -336,6 +481,10 @@ pub fn field_type_to_surql_default(
             trace!("Generating SURQL default for Vec with inner: {:?}", inner);
             "[]".to_string()
         }
+        FieldType::HashSet(inner) | FieldType::BTreeSet(inner) => {
+            trace!("Generating SURQL default for Set with inner: {:?}", inner);
+            "<set>[]".to_string()
+        }
         FieldType::HashMap(key, value) | FieldType::BTreeMap(key, value) => {
             trace!(
                 "Generating SURQL default for Map with key: {:?}, value: {:?}",
@@ -350,6 +499,27 @@ pub fn field_type_to_surql_default(
             );
             "NULL".to_string()
         }
+        FieldType::GenericParam(name) => {
+            trace!("Unbound generic parameter '{}', defaulting to NULL", name);
+            "NULL".to_string()
+        }
+        FieldType::Generic(..) => {
+            debug!(
+                "Processing generic instantiation '{}' for SURQL default",
+                field_type
+            );
+            let mut app_structs = app_structs.clone();
+            let mut enums = enums.clone();
+            register_generic_instances(field_type, &mut app_structs, &mut enums);
+            field_type_to_surql_default(
+                field_name,
+                table_name,
+                &FieldType::Other(field_type.to_string()),
+                &enums,
+                &app_structs,
+                persistable_structs,
+            )
+        }
         FieldType::Other(name) => {
             debug!("Processing Other type '{}' for SURQL default", name);
             // Check if it's an enum
@@ -390,6 +560,23 @@ pub fn field_type_to_surql_default(
                     name,
                     struct_config.fields.len()
                 );
+                let surql_default = |f: &StructField| {
+                    field_type_to_surql_default(
+                        &f.field_name,
+                        table_name,
+                        &f.field_type,
+                        enums,
+                        app_structs,
+                        persistable_structs,
+                    )
+                };
+                if let Some(inner) = struct_config.newtype_field() {
+                    return surql_default(inner);
+                }
+                if struct_config.kind == StructKind::Tuple {
+                    let items = struct_config.fields.iter().map(surql_default);
+                    return format!("[{}]", items.collect::<Vec<_>>().join(", "));
+                }
                 let fields_str = struct_config
                     .fields
                     .iter()
@@ -463,6 +650,34 @@ pub fn field_type_to_surreal_type(
             trace!("Converting Decimal to SurrealDB type");
             ("decimal".to_string(), false, None)
         }
+        FieldType::Uuid => {
+            trace!("Converting Uuid to SurrealDB type");
+            ("uuid".to_string(), false, None)
+        }
+        FieldType::NaiveDate => {
+            trace!("Converting NaiveDate to SurrealDB type");
+            ("datetime".to_string(), false, None)
+        }
+        FieldType::Duration => {
+            trace!("Converting Duration to SurrealDB type");
+            ("duration".to_string(), false, None)
+        }
+        FieldType::Bytes => {
+            trace!("Converting Bytes to SurrealDB type");
+            ("bytes".to_string(), false, None)
+        }
+        FieldType::JsonValue => {
+            trace!("Converting JsonValue to SurrealDB type");
+            ("any".to_string(), false, None)
+        }
+        FieldType::NaiveTime | FieldType::IpAddr | FieldType::Url => {
+            trace!("Converting {} to SurrealDB string type", field_type);
+            ("string".to_string(), false, None)
+        }
+        FieldType::Geometry(kind) => {
+            trace!("Converting Geometry to SurrealDB type: {:?}", kind);
+            (format!("geometry<{}>", kind.surreal_name()), false, None)
+        }
         FieldType::OrderedFloat(inner) => {
             trace!(
                 "Converting OrderedFloat to SurrealDB type with inner: {:?}",
@@ -542,6 +757,27 @@ pub fn field_type_to_surreal_type(
             );
             (inner_type, needs_wildcard, wildcard_type)
         }
+        FieldType::GenericParam(name) => {
+            trace!("Generic parameter '{}' maps to any", name);
+            ("any".to_string(), false, None)
+        }
+        FieldType::Generic(..) => {
+            debug!(
+                "Monomorphising generic instantiation '{}' for SurrealDB type conversion",
+                field_type
+            );
+            let mut app_structs = app_structs.clone();
+            let mut enums = enums.clone();
+            register_generic_instances(field_type, &mut app_structs, &mut enums);
+            field_type_to_surreal_type(
+                field_name,
+                table_name,
+                &FieldType::Other(field_type.to_string()),
+                &enums,
+                &app_structs,
+                persistable_structs,
+            )
+        }
         FieldType::Other(name) => {
             debug!(
                 "Processing Other type '{}' for SurrealDB type conversion",
@@ -586,6 +822,35 @@ pub fn field_type_to_surreal_type(
                     name,
                     app_struct.fields.len()
                 );
+                if let Some(inner) = app_struct.newtype_field() {
+                    // Newtypes are transparent in the database
+                    return field_type_to_surreal_type(
+                        field_name,
+                        table_name,
+                        &inner.field_type,
+                        enums,
+                        app_structs,
+                        persistable_structs,
+                    );
+                }
+                if app_struct.kind == StructKind::Tuple {
+                    let items: Vec<String> = app_struct
+                        .fields
+                        .iter()
+                        .map(|f| {
+                            field_type_to_surreal_type(
+                                field_name,
+                                table_name,
+                                &f.field_type,
+                                enums,
+                                app_structs,
+                                persistable_structs,
+                            )
+                            .0
+                        })
+                        .collect();
+                    return (format!("array<{}>", items.join(", ")), false, None);
+                }
                 let field_defs: Vec<String> = app_struct
                     .fields
                     .iter()
@@ -646,6 +911,18 @@ pub fn field_type_to_surreal_type(
             );
             (format!("array<{}>", inner_type), false, None)
         }
+        FieldType::HashSet(inner) | FieldType::BTreeSet(inner) => {
+            trace!("Converting Set to SurrealDB type with inner: {:?}", inner);
+            let (inner_type, _, _) = field_type_to_surreal_type(
+                field_name,
+                table_name,
+                inner,
+                enums,
+                app_structs,
+                persistable_structs,
+            );
+            (format!("set<{}>", inner_type), false, None)
+        }
         FieldType::Tuple(inner_types) => {
             trace!(
                 "Converting Tuple to SurrealDB type with {} types",
@@ -162,7 +162,9 @@ pub fn collect_refs(ft: &FieldType, known: &HashSet<String>, acc: &mut HashSet<S
     match ft {
         Tuple(v) => v.iter().for_each(|f| collect_refs(f, known, acc)),
         Struct(v) => v.iter().for_each(|(_, f)| collect_refs(f, known, acc)),
-        Option(i) | Vec(i) | RecordLink(i) => collect_refs(i, known, acc),
+        Option(i) | Vec(i) | HashSet(i) | BTreeSet(i) | RecordLink(i) => {
+            collect_refs(i, known, acc)
+        }
         HashMap(k, v) | BTreeMap(k, v) => {
             collect_refs(k, known, acc);
             collect_refs(v, known, acc);
@@ -170,6 +172,12 @@ pub fn collect_refs(ft: &FieldType, known: &HashSet<String>, acc: &mut HashSet<S
         Other(name) if known.contains(name) => {
             acc.insert(name.clone());
         }
+        Generic(name, args) => {
+            if known.contains(name) {
+                acc.insert(name.clone());
+            }
+            args.iter().for_each(|f| collect_refs(f, known, acc));
+        }
         _ => {}
     }
 }
@@ -404,7 +412,32 @@ pub fn collect_field_type_dependencies(
                 }
             }
         }
-        FieldType::Option(inner) | FieldType::Vec(inner) | FieldType::RecordLink(inner) => {
+        FieldType::Generic(type_name, args) => {
+            // Dependencies come from the generic definition and from its type arguments
+            collect_field_type_dependencies(
+                &FieldType::Other(type_name.clone()),
+                tables,
+                objects,
+                enums,
+                dependencies,
+                visited_types,
+            );
+            for arg in args {
+                collect_field_type_dependencies(
+                    arg,
+                    tables,
+                    objects,
+                    enums,
+                    dependencies,
+                    visited_types,
+                );
+            }
+        }
+        FieldType::Option(inner)
+        | FieldType::Vec(inner)
+        | FieldType::HashSet(inner)
+        | FieldType::BTreeSet(inner)
+        | FieldType::RecordLink(inner) => {
             collect_field_type_dependencies(
                 inner,
                 tables,
@@ -4,7 +4,10 @@ use tracing::{debug, error, info, trace};
 
 use crate::{
     format::Format,
-    mockmake::{MockGenerationConfig, coordinate::Coordination},
+    mockmake::{
+        MockGenerationConfig, cardinality::Cardinality, coordinate::Coordination, locale::Locale,
+        snapshot::Anonymization,
+    },
     schemasync::{Direction, EdgeConfig},
     types::StructField,
 };
@@ -80,6 +83,38 @@ pub fn parse_mock_data_attribute(
                                     ));
                                 }
                             }
+                            Meta::NameValue(nv) if nv.path.is_ident("seed") => {
+                                debug!("Processing 'seed' parameter");
+                                if let Expr::Lit(ExprLit {
+                                    lit: Lit::Int(lit), ..
+                                }) = &nv.value
+                                {
+                                    match lit.base10_parse::<u64>() {
+                                        Ok(value) => {
+                                            debug!("Successfully parsed seed value: {}", value);
+                                            base_config.seed = Some(value);
+                                        }
+                                        Err(_) => {
+                                            error!(
+                                                "Failed to parse 'seed' value: {}",
+                                                lit.base10_digits()
+                                            );
+                                            return Err(syn::Error::new(
+                                                lit.span(),
+                                                format!(
+                                                    "Invalid value for 'seed': '{}'. Expected an unsigned 64-bit integer.\n\nExample: #[mock_data(seed = 42)]",
+                                                    lit.base10_digits()
+                                                ),
+                                            ));
+                                        }
+                                    }
+                                } else {
+                                    return Err(syn::Error::new(
+                                        nv.value.span(),
+                                        "The 'seed' parameter must be an integer literal.\n\nExample: #[mock_data(seed = 42)]",
+                                    ));
+                                }
+                            }
                             Meta::NameValue(nv) if nv.path.is_ident("overrides") => {
                                 if let Expr::Lit(ExprLit {
                                     lit: Lit::Str(lit), ..
@@ -96,6 +131,153 @@ pub fn parse_mock_data_attribute(
                             Meta::NameValue(nv) if nv.path.is_ident("coordinate") => {
                                 // Skip here - coordinate is parsed separately by coordinate_parser
                             }
+                            Meta::NameValue(nv) if nv.path.is_ident("cardinality") => {
+                                debug!("Processing 'cardinality' parameter");
+                                let Expr::Array(ExprArray { elems, .. }) = &nv.value else {
+                                    return Err(syn::Error::new(
+                                        nv.value.span(),
+                                        "The 'cardinality' parameter must be an array of rules.\n\nExample: #[mock_data(cardinality = [Cardinality { field_name: \"order\", min: 1, max: 5 }])]",
+                                    ));
+                                };
+                                for elem in elems {
+                                    let rule = Cardinality::try_from(elem)?;
+                                    debug!("Successfully parsed cardinality rule: {:?}", rule);
+                                    base_config.cardinality.push(rule);
+                                }
+                            }
+                            Meta::List(list) if list.path.is_ident("distributions") => {
+                                debug!("Processing 'distributions' parameter");
+                                let assignments =
+                                    list.parse_args_with(
+                                        syn::punctuated::Punctuated::<
+                                            syn::MetaNameValue,
+                                            syn::Token![,],
+                                        >::parse_terminated,
+                                    )?;
+                                for assignment in assignments {
+                                    let field_name = assignment
+                                        .path
+                                        .get_ident()
+                                        .map(|ident| ident.to_string())
+                                        .ok_or_else(|| {
+                                            syn::Error::new(
+                                                assignment.path.span(),
+                                                "Expected a field name",
+                                            )
+                                        })?;
+                                    let format =
+                                        Format::try_from(&with_format_prefix(&assignment.value))
+                                            .map_err(|e| {
+                                                syn::Error::new(
+                                                    assignment.value.span(),
+                                                    format!(
+                                                        "Failed to parse distribution for '{}': {}",
+                                                        field_name, e
+                                                    ),
+                                                )
+                                            })?;
+                                    if !format.is_distribution() {
+                                        return Err(syn::Error::new(
+                                            assignment.value.span(),
+                                            "Expected a distribution: Normal, LogNormal, Exponential, Zipf, Weighted or Seasonal",
+                                        ));
+                                    }
+                                    debug!(
+                                        "Parsed distribution for field '{}': {:?}",
+                                        field_name, format
+                                    );
+                                    base_config.distributions.insert(field_name, format);
+                                }
+                            }
+                            Meta::NameValue(nv) if nv.path.is_ident("locale") => {
+                                debug!("Processing 'locale' parameter");
+                                let Expr::Lit(ExprLit {
+                                    lit: Lit::Str(lit), ..
+                                }) = &nv.value
+                                else {
+                                    return Err(syn::Error::new(
+                                        nv.value.span(),
+                                        "The 'locale' parameter must be a string literal.\n\nExample: #[mock_data(locale = \"de_DE\")]",
+                                    ));
+                                };
+                                base_config.locale = Some(Locale::from(lit.value()));
+                            }
+                            Meta::List(list) if list.path.is_ident("locales") => {
+                                debug!("Processing 'locales' parameter");
+                                let assignments =
+                                    list.parse_args_with(
+                                        syn::punctuated::Punctuated::<
+                                            syn::MetaNameValue,
+                                            syn::Token![,],
+                                        >::parse_terminated,
+                                    )?;
+                                for assignment in assignments {
+                                    let field_name = assignment
+                                        .path
+                                        .get_ident()
+                                        .map(|ident| ident.to_string())
+                                        .ok_or_else(|| {
+                                            syn::Error::new(
+                                                assignment.path.span(),
+                                                "Expected a field name",
+                                            )
+                                        })?;
+                                    let Expr::Lit(ExprLit {
+                                        lit: Lit::Str(lit), ..
+                                    }) = &assignment.value
+                                    else {
+                                        return Err(syn::Error::new(
+                                            assignment.value.span(),
+                                            "Locales must be string literals.\n\nExample: #[mock_data(locales(billing_city = \"en_GB\"))]",
+                                        ));
+                                    };
+                                    base_config
+                                        .locales
+                                        .insert(field_name, Locale::from(lit.value()));
+                                }
+                            }
+                            Meta::List(list) if list.path.is_ident("anonymize") => {
+                                debug!("Processing 'anonymize' parameter");
+                                let assignments =
+                                    list.parse_args_with(
+                                        syn::punctuated::Punctuated::<
+                                            syn::MetaNameValue,
+                                            syn::Token![,],
+                                        >::parse_terminated,
+                                    )?;
+                                for assignment in assignments {
+                                    let field_name = assignment
+                                        .path
+                                        .get_ident()
+                                        .map(|ident| ident.to_string())
+                                        .ok_or_else(|| {
+                                            syn::Error::new(
+                                                assignment.path.span(),
+                                                "Expected a field name",
+                                            )
+                                        })?;
+                                    let Expr::Path(path) = &assignment.value else {
+                                        return Err(syn::Error::new(
+                                            assignment.value.span(),
+                                            "Expected Keep, Fake, Hash or Null.\n\nExample: #[mock_data(anonymize(email = Fake, notes = Null))]",
+                                        ));
+                                    };
+                                    let rule = path
+                                        .path
+                                        .get_ident()
+                                        .ok_or_else(|| {
+                                            syn::Error::new(
+                                                path.span(),
+                                                "Expected Keep, Fake, Hash or Null",
+                                            )
+                                        })
+                                        .and_then(|ident| {
+                                            Anonymization::try_from(ident.to_string().as_str())
+                                                .map_err(|e| syn::Error::new(ident.span(), e))
+                                        })?;
+                                    base_config.anonymize.insert(field_name, rule);
+                                }
+                            }
                             Meta::NameValue(nv) => {
                                 let param_name = nv
                                     .path
@@ -105,7 +287,7 @@ pub fn parse_mock_data_attribute(
                                 return Err(syn::Error::new(
                                     nv.path.span(),
                                     format!(
-                                        "Unknown parameter '{}' in mock_data attribute.\n\nValid parameters are: n, overrides, coordinate\n\nExample: #[mock_data(n = 1000, overrides = \"config\", coordinate = [InitializeEqual([\"field1\", \"field2\"])])]",
+                                        "Unknown parameter '{}' in mock_data attribute.\n\nValid parameters are: n, seed, overrides, coordinate, cardinality, distributions, locale, locales, anonymize\n\nExample: #[mock_data(n = 1000, overrides = \"config\", coordinate = [InitializeEqual([\"field1\", \"field2\"])])]",
                                         param_name
                                     ),
                                 ));
@@ -152,10 +334,12 @@ pub fn parse_mock_data_attribute(
                     }
 
                     info!(
-                        "Successfully parsed mock_data attribute: n={}, overrides={:?}, coordination_rules_count={}",
+                        "Successfully parsed mock_data attribute: n={}, seed={:?}, overrides={:?}, coordination_rules_count={}, cardinality_rules_count={}",
                         base_config.n,
+                        base_config.seed,
                         overrides_name,
-                        coordination_rules.len()
+                        coordination_rules.len(),
+                        base_config.cardinality.len()
                     );
 
                     // Parse overrides from config if specified
@@ -425,11 +609,71 @@ pub fn parse_relation_attribute(attrs: &[Attribute]) -> Result<Option<EdgeConfig
     Ok(None)
 }
 
+/// Add the `Format::` prefix to a bare format variant like `DateTime` or
+/// `Url("domain")`, so it can be parsed with `Format::try_from`
+fn with_format_prefix(expr: &Expr) -> Expr {
+    use syn::{ExprCall, ExprPath, Path, PathSegment};
+
+    match expr {
+        // If it's just an identifier like DateTime, convert to Format::DateTime
+        Expr::Path(path_expr) if path_expr.path.segments.len() == 1 => {
+            let variant = &path_expr.path.segments[0];
+            let mut segments = syn::punctuated::Punctuated::new();
+            segments.push(PathSegment::from(syn::Ident::new("Format", variant.span())));
+            segments.push(variant.clone());
+            Expr::Path(ExprPath {
+                attrs: vec![],
+                qself: None,
+                path: Path {
+                    leading_colon: None,
+                    segments,
+                },
+            })
+        }
+        // If it's a call like Url("domain"), convert to Format::Url("domain")
+        Expr::Call(call_expr) => {
+            if let Expr::Path(path_expr) = &*call_expr.func {
+                if path_expr.path.segments.len() == 1 {
+                    let variant = &path_expr.path.segments[0];
+                    let mut segments = syn::punctuated::Punctuated::new();
+                    segments.push(PathSegment::from(syn::Ident::new("Format", variant.span())));
+                    segments.push(variant.clone());
+                    Expr::Call(ExprCall {
+                        attrs: call_expr.attrs.clone(),
+                        func: Box::new(Expr::Path(ExprPath {
+                            attrs: vec![],
+                            qself: None,
+                            path: Path {
+                                leading_colon: None,
+                                segments,
+                            },
+                        })),
+                        paren_token: call_expr.paren_token,
+                        args: call_expr.args.clone(),
+                    })
+                } else {
+                    expr.clone()
+                }
+            } else {
+                expr.clone()
+            }
+        }
+        // `custom_fn = "name"` names a registered generator, Format::CustomFn("name")
+        Expr::Assign(assign) => match &*assign.left {
+            Expr::Path(left) if left.path.is_ident("custom_fn") => {
+                let name = &assign.right;
+                syn::parse_quote!(Format::CustomFn(#name))
+            }
+            _ => expr.clone(),
+        },
+        // Otherwise keep as is
+        _ => expr.clone(),
+    }
+}
+
 pub fn parse_format_attribute(
     attrs: &[Attribute],
 ) -> Result<Option<proc_macro2::TokenStream>, syn::Error> {
-    use syn::{Expr, ExprCall, ExprPath, Path, PathSegment};
-
     info!(
         "Starting format attribute parsing for {} attributes",
         attrs.len()
@@ -444,55 +688,7 @@ pub fn parse_format_attribute(
                     format!("Failed to parse format attribute: {}\n\nExamples:\n#[format(DateTime)]\n#[format(Url(\"example.com\"))]", e)
                 ))?;
 
-            // Transform the expression to add Format:: prefix if needed
-            let format_expr = match &expr {
-                // If it's just an identifier like DateTime, convert to Format::DateTime
-                Expr::Path(path_expr) if path_expr.path.segments.len() == 1 => {
-                    let variant = &path_expr.path.segments[0];
-                    let mut segments = syn::punctuated::Punctuated::new();
-                    segments.push(PathSegment::from(syn::Ident::new("Format", variant.span())));
-                    segments.push(variant.clone());
-                    Expr::Path(ExprPath {
-                        attrs: vec![],
-                        qself: None,
-                        path: Path {
-                            leading_colon: None,
-                            segments,
-                        },
-                    })
-                }
-                // If it's a call like Url("domain"), convert to Format::Url("domain")
-                Expr::Call(call_expr) => {
-                    if let Expr::Path(path_expr) = &*call_expr.func {
-                        if path_expr.path.segments.len() == 1 {
-                            let variant = &path_expr.path.segments[0];
-                            let mut segments = syn::punctuated::Punctuated::new();
-                            segments
-                                .push(PathSegment::from(syn::Ident::new("Format", variant.span())));
-                            segments.push(variant.clone());
-                            Expr::Call(ExprCall {
-                                attrs: call_expr.attrs.clone(),
-                                func: Box::new(Expr::Path(ExprPath {
-                                    attrs: vec![],
-                                    qself: None,
-                                    path: Path {
-                                        leading_colon: None,
-                                        segments,
-                                    },
-                                })),
-                                paren_token: call_expr.paren_token,
-                                args: call_expr.args.clone(),
-                            })
-                        } else {
-                            expr.clone()
-                        }
-                    } else {
-                        expr.clone()
-                    }
-                }
-                // Otherwise keep as is
-                _ => expr.clone(),
-            };
+            let format_expr = with_format_prefix(&expr);
 
             // Use the TryFrom implementation to parse the Format
             match Format::try_from(&format_expr) {
@@ -506,7 +702,7 @@ pub fn parse_format_attribute(
                     return Err(syn::Error::new(
                         expr.span(),
                         format!(
-                            "{}\n\nValid formats:\n- Simple: DateTime, Date, Time, Currency, Percentage, Phone, Email, FirstName, LastName, CompanyName, PhoneNumber, ColorHex, JwtToken, Oklch, PostalCode\n- With parameter: Url(\"domain.com\")",
+                            "{}\n\nValid formats:\n- Simple: DateTime, Date, Time, Currency, Percentage, Phone, Email, FirstName, LastName, CompanyName, PhoneNumber, ColorHex, JwtToken, Oklch, PostalCode\n- With parameter: Url(\"domain.com\")\n- Registered generator: custom_fn = \"name\"",
                             e
                         ),
                     ));
@@ -519,8 +715,6 @@ pub fn parse_format_attribute(
 }
 
 pub fn parse_format_attribute_bin(attrs: &[Attribute]) -> Result<Option<Format>, syn::Error> {
-    use syn::{Expr, ExprCall, ExprPath, Path, PathSegment};
-
     info!(
         "Starting format attribute parsing for {} attributes",
         attrs.len()
@@ -535,55 +729,7 @@ pub fn parse_format_attribute_bin(attrs: &[Attribute]) -> Result<Option<Format>,
                     format!("Failed to parse format attribute: {}\n\nExamples:\n#[format(DateTime)]\n#[format(Url(\"example.com\"))]", e)
                 ))?;
 
-            // Transform the expression to add Format:: prefix if needed
-            let format_expr = match &expr {
-                // If it's just an identifier like DateTime, convert to Format::DateTime
-                Expr::Path(path_expr) if path_expr.path.segments.len() == 1 => {
-                    let variant = &path_expr.path.segments[0];
-                    let mut segments = syn::punctuated::Punctuated::new();
-                    segments.push(PathSegment::from(syn::Ident::new("Format", variant.span())));
-                    segments.push(variant.clone());
-                    Expr::Path(ExprPath {
-                        attrs: vec![],
-                        qself: None,
-                        path: Path {
-                            leading_colon: None,
-                            segments,
-                        },
-                    })
-                }
-                // If it's a call like Url("domain"), convert to Format::Url("domain")
-                Expr::Call(call_expr) => {
-                    if let Expr::Path(path_expr) = &*call_expr.func {
-                        if path_expr.path.segments.len() == 1 {
-                            let variant = &path_expr.path.segments[0];
-                            let mut segments = syn::punctuated::Punctuated::new();
-                            segments
-                                .push(PathSegment::from(syn::Ident::new("Format", variant.span())));
-                            segments.push(variant.clone());
-                            Expr::Call(ExprCall {
-                                attrs: call_expr.attrs.clone(),
-                                func: Box::new(Expr::Path(ExprPath {
-                                    attrs: vec![],
-                                    qself: None,
-                                    path: Path {
-                                        leading_colon: None,
-                                        segments,
-                                    },
-                                })),
-                                paren_token: call_expr.paren_token,
-                                args: call_expr.args.clone(),
-                            })
-                        } else {
-                            expr.clone()
-                        }
-                    } else {
-                        expr.clone()
-                    }
-                }
-                // Otherwise keep as is
-                _ => expr.clone(),
-            };
+            let format_expr = with_format_prefix(&expr);
 
             // Use the TryFrom implementation to parse the Format
             match Format::try_from(&format_expr) {
@@ -597,7 +743,7 @@ pub fn parse_format_attribute_bin(attrs: &[Attribute]) -> Result<Option<Format>,
                     return Err(syn::Error::new(
                         expr.span(),
                         format!(
-                            "{}\n\nValid formats:\n- Simple: DateTime, Date, Time, Currency, Percentage, Phone, Email, FirstName, LastName, CompanyName, PhoneNumber, ColorHex, JwtToken, Oklch, PostalCode\n- With parameter: Url(\"domain.com\")",
+                            "{}\n\nValid formats:\n- Simple: DateTime, Date, Time, Currency, Percentage, Phone, Email, FirstName, LastName, CompanyName, PhoneNumber, ColorHex, JwtToken, Oklch, PostalCode\n- With parameter: Url(\"domain.com\")\n- Registered generator: custom_fn = \"name\"",
                             e
                         ),
                     ));
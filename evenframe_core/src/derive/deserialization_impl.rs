@@ -20,6 +20,21 @@ fn to_pascal_case(s: &str) -> String {
         .collect()
 }
 
+/// Adds the `'de` lifetime to a type's generics and requires every type
+/// parameter to implement `Deserialize<'de>`
+fn deserialize_generics(generics: &syn::Generics) -> syn::Generics {
+    let mut de_generics = generics.clone();
+    de_generics.params.insert(0, syn::parse_quote!('de));
+    let de_where = de_generics.make_where_clause();
+    for param in generics.type_params() {
+        let param_ident = &param.ident;
+        de_where
+            .predicates
+            .push(syn::parse_quote!(#param_ident: ::serde::Deserialize<'de>));
+    }
+    de_generics
+}
+
 /// Generates a custom Deserialize implementation that includes field validation
 /// This is used when structs have validators that need to be applied during deserialization
 pub fn generate_custom_deserialize(input: &DeriveInput) -> proc_macro2::TokenStream {
@@ -168,13 +183,19 @@ pub fn generate_custom_deserialize(input: &DeriveInput) -> proc_macro2::TokenStr
     debug!("Generating deserialize imports");
     let imports = generate_deserialize_imports();
 
+    // Generic structs need every type parameter to be deserializable, and the
+    // visitor has to carry the parameters through PhantomData.
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let de_generics = deserialize_generics(&input.generics);
+    let (de_impl_generics, _, de_where_clause) = de_generics.split_for_impl();
+
     info!("Successfully generated custom deserialize implementation for struct: {}", struct_name);
     quote! {
         const _: () = {
             #imports
 
             // Custom deserialization implementation
-            impl<'de> EvenframeDeserialize<'de> for #struct_name {
+            impl #de_impl_generics EvenframeDeserialize<'de> for #struct_name #ty_generics #de_where_clause {
             fn evenframe_deserialize<D>(deserializer: D) -> Result<Self, D::Error>
             where
                 D: ::serde::Deserializer<'de>,
@@ -215,16 +236,16 @@ pub fn generate_custom_deserialize(input: &DeriveInput) -> proc_macro2::TokenStr
                     }
                 }
 
-                struct StructVisitor;
+                struct StructVisitor #impl_generics (::std::marker::PhantomData<fn() -> #struct_name #ty_generics>) #where_clause;
 
-                impl<'de> Visitor<'de> for StructVisitor {
-                    type Value = #struct_name;
+                impl #de_impl_generics Visitor<'de> for StructVisitor #ty_generics #de_where_clause {
+                    type Value = #struct_name #ty_generics;
 
                     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
                         formatter.write_str(concat!("struct ", stringify!(#struct_name)))
                     }
 
-                    fn visit_map<V>(self, mut map: V) -> Result<#struct_name, V::Error>
+                    fn visit_map<V>(self, mut map: V) -> Result<#struct_name #ty_generics, V::Error>
                     where
                         V: MapAccess<'de>,
                     {
@@ -247,14 +268,78 @@ pub fn generate_custom_deserialize(input: &DeriveInput) -> proc_macro2::TokenStr
                 }
 
                 const FIELDS: &'static [&'static str] = &[#(stringify!(#field_names)),*];
-                deserializer.deserialize_struct(stringify!(#struct_name), FIELDS, StructVisitor)
+                deserializer.deserialize_struct(stringify!(#struct_name), FIELDS, StructVisitor(::std::marker::PhantomData))
+            }
+        }
+
+        };
+
+        // Default Deserialize implementation that delegates to custom trait
+        impl #de_impl_generics ::serde::Deserialize<'de> for #struct_name #ty_generics #de_where_clause {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+            {
+                #imports
+                Self::evenframe_deserialize(deserializer)
+            }
+        }
+    }
+}
+
+/// Generates a Deserialize implementation for a newtype that deserializes the
+/// wrapped value transparently and then applies the newtype's validators.
+/// Validators may be declared on the struct itself or on its single field.
+pub fn generate_newtype_deserialize(input: &DeriveInput, field: &syn::Field) -> proc_macro2::TokenStream {
+    let struct_name = &input.ident;
+    info!("Generating newtype deserialize implementation for: {}", struct_name);
+
+    let inner_type = &field.ty;
+    let temp_var_name = "__temp_0";
+    let temp_var = quote::format_ident!("{}", temp_var_name);
+
+    let mut validation_logic_tokens = Vec::new();
+    for attrs in [&input.attrs, &field.attrs] {
+        match parse_field_validators_with_logic(attrs, temp_var_name) {
+            Ok((_, logic)) => validation_logic_tokens.extend(logic),
+            Err(err) => {
+                error!("Failed to parse validators for newtype {}: {}", struct_name, err);
+                return err.to_compile_error();
             }
         }
+    }
+    debug!(
+        "Found {} validation logic tokens for newtype: {}",
+        validation_logic_tokens.len(),
+        struct_name
+    );
+
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let de_generics = deserialize_generics(&input.generics);
+    let (de_impl_generics, _, de_where_clause) = de_generics.split_for_impl();
+
+    let imports = generate_deserialize_imports();
+
+    quote! {
+        const _: () = {
+            #imports
 
+            impl #de_impl_generics EvenframeDeserialize<'de> for #struct_name #ty_generics #de_where_clause {
+                fn evenframe_deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: ::serde::Deserializer<'de>,
+                {
+                    #[allow(unused_mut)]
+                    let mut #temp_var: #inner_type = ::serde::Deserialize::deserialize(deserializer)?;
+                    // Apply validators - any validation errors will be converted to deserialization errors
+                    #(#validation_logic_tokens)*
+                    Ok(#struct_name(#temp_var))
+                }
+            }
         };
 
         // Default Deserialize implementation that delegates to custom trait
-        impl<'de> ::serde::Deserialize<'de> for #struct_name {
+        impl #de_impl_generics ::serde::Deserialize<'de> for #struct_name #ty_generics #de_where_clause {
             fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
             where
                 D: ::serde::Deserializer<'de>,
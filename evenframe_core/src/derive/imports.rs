@@ -7,7 +7,7 @@ pub fn generate_struct_trait_imports() -> proc_macro2::TokenStream {
     quote! {
         use evenframe::{
             prelude::*,
-            traits::EvenframePersistableStruct,
+            traits::{EvenframePersistableStruct, EvenframeRelation, EvenframeRepository},
             types::{StructConfig, StructField},
             validator::{StringValidator, Validator},
         };
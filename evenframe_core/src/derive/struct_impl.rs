@@ -1,17 +1,18 @@
 use crate::{
     derive::{
         attributes::{parse_format_attribute, parse_mock_data_attribute, parse_relation_attribute},
-        deserialization_impl::generate_custom_deserialize,
+        deserialization_impl::{generate_custom_deserialize, generate_newtype_deserialize},
         imports::generate_struct_imports,
         type_parser::parse_data_type,
         validator_parser::parse_field_validators,
     },
     schemasync::{DefineConfig, EdgeConfig, PermissionsConfig},
 };
+use convert_case::{Case, Casing};
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::spanned::Spanned;
-use syn::{Data, DeriveInput, Fields};
+use syn::{Data, DeriveInput, Fields, FieldsUnnamed};
 use tracing::{debug, error, info, trace};
 
 pub fn generate_struct_impl(input: DeriveInput) -> TokenStream {
@@ -25,16 +26,26 @@ pub fn generate_struct_impl(input: DeriveInput) -> TokenStream {
     if let Data::Struct(ref data_struct) = input.data {
         debug!("Processing struct data");
         // Ensure the struct has named fields.
-        let fields_named = if let Fields::Named(ref fields_named) = data_struct.fields {
-            debug!("Found {} named fields", fields_named.named.len());
-            fields_named
-        } else {
-            error!("Struct does not have named fields");
-            return syn::Error::new(
-                ident.span(),
-                format!("Evenframe derive macro only supports structs with named fields.\n\nExample of a valid struct:\n\nstruct {} {{\n    id: String,\n    name: String,\n}}", ident),
-            )
-            .to_compile_error();
+        let fields_named = match data_struct.fields {
+            Fields::Named(ref fields_named) => {
+                debug!("Found {} named fields", fields_named.named.len());
+                fields_named
+            }
+            Fields::Unnamed(ref fields_unnamed) => {
+                debug!(
+                    "Found tuple struct with {} fields",
+                    fields_unnamed.unnamed.len()
+                );
+                return generate_tuple_struct_impl(&input, fields_unnamed);
+            }
+            Fields::Unit => {
+                error!("Struct does not have any fields");
+                return syn::Error::new(
+                    ident.span(),
+                    format!("Evenframe derive macro does not support unit structs.\n\nExample of a valid struct:\n\nstruct {} {{\n    id: String,\n    name: String,\n}}", ident),
+                )
+                .to_compile_error();
+            }
         };
 
         // Parse struct-level attributes
@@ -88,10 +99,23 @@ pub fn generate_struct_impl(input: DeriveInput) -> TokenStream {
             debug!("No 'id' field found - treating as application-level data structure");
         }
 
+        // Collect the struct's type parameters so fields referring to them
+        // (parsed as custom types) can be resolved to generic parameters.
+        let generic_params: Vec<String> = input
+            .generics
+            .type_params()
+            .map(|param| param.ident.to_string())
+            .collect();
+        if !generic_params.is_empty() {
+            debug!("Struct is generic over: {:?}", generic_params);
+        }
+
         // Single pass over all fields.
         debug!("Processing {} fields", fields_named.named.len());
         let mut table_field_tokens = Vec::new();
         let mut json_assignments = Vec::new();
+        let mut field_constants = Vec::new();
+        let mut edge_traversals = Vec::new();
 
         for (field_index, field) in fields_named.named.iter().enumerate() {
             trace!(
@@ -116,6 +140,13 @@ pub fn generate_struct_impl(input: DeriveInput) -> TokenStream {
             // Build the field type token.
             let ty = &field.ty;
             let field_type = parse_data_type(ty);
+            let field_type = if generic_params.is_empty() {
+                field_type
+            } else {
+                quote! {
+                    (#field_type).resolve_generic_params(&[ #(#generic_params.to_string()),* ])
+                }
+            };
 
             // Parse any edge attribute.
             let edge_config = match EdgeConfig::parse(field) {
@@ -212,6 +243,34 @@ pub fn generate_struct_impl(input: DeriveInput) -> TokenStream {
                 }
             });
 
+            // Field-path constant for the query builder, e.g. `User::EMAIL`
+            let const_ident = format_ident!("{}", field_name_trim.to_case(Case::Constant));
+            let field_vis = &field.vis;
+            field_constants.push(quote! {
+                #field_vis const #const_ident: ::evenframe::schemasync::surql::query::Field<Self, #ty> =
+                    ::evenframe::schemasync::surql::query::Field::new(#field_name_trim);
+            });
+
+            // Graph traversal helper for edge fields, e.g. `Order::traverse_items(&db, id)`
+            if let Some(ref details) = edge_config {
+                let traverse_ident = format_ident!("traverse_{}", field_name_trim);
+                edge_traversals.push(quote! {
+                    #field_vis fn #traverse_ident<C: ::evenframe::surrealdb::Connection>(
+                        db: &::evenframe::surrealdb::Surreal<C>,
+                        id: impl ToString,
+                    ) -> impl ::std::future::Future<Output = ::evenframe::error::Result<#ty>> + Send
+                    where
+                        #ty: ::serde::de::DeserializeOwned,
+                    {
+                        let id = id.to_string();
+                        let edge = #details;
+                        async move {
+                            ::evenframe::schemasync::surql::relation::traverse::<Self, #ty, C>(db, id, &edge).await
+                        }
+                    }
+                });
+            }
+
             // For the JSON payload, skip the "id" field and any field with an edge attribute.
             if field_name != "id" && edge_config.is_none() {
                 json_assignments.push(quote! {
@@ -253,9 +312,20 @@ pub fn generate_struct_impl(input: DeriveInput) -> TokenStream {
             quote! { None }
         };
 
+        let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+        // Relation tables get RELATE and edge listing helpers
+        let relation_impl = if relation_config.is_some() {
+            quote! {
+                impl #impl_generics EvenframeRelation for #ident #ty_generics #where_clause {}
+            }
+        } else {
+            quote! {}
+        };
+
         let evenframe_persistable_struct_impl = {
             quote! {
-                impl EvenframePersistableStruct for #ident {
+                impl #impl_generics EvenframePersistableStruct for #ident #ty_generics #where_clause {
                     fn table_config() -> Option<TableConfig> {
                         Some(TableConfig {
                             table_name: #table_name.to_case(Case::Snake),
@@ -263,6 +333,8 @@ pub fn generate_struct_impl(input: DeriveInput) -> TokenStream {
                                 struct_name: #struct_name.to_owned(),
                                 fields: vec![ #(#table_field_tokens),* ],
                                 validators: #table_validators_tokens,
+                                generics: vec![ #(#generic_params.to_string()),* ],
+                                kind: ::evenframe::types::StructKind::Named,
                             },
                             relation: #relation_tokens,
                             permissions: #permissions_config_tokens,
@@ -270,6 +342,16 @@ pub fn generate_struct_impl(input: DeriveInput) -> TokenStream {
                         })
                     }
                 }
+
+                impl #impl_generics EvenframeRepository for #ident #ty_generics #where_clause {}
+
+                #relation_impl
+
+                impl #impl_generics #ident #ty_generics #where_clause {
+                    #(#field_constants)*
+
+                    #(#edge_traversals)*
+                }
             }
         };
 
@@ -334,3 +416,40 @@ pub fn generate_struct_impl(input: DeriveInput) -> TokenStream {
         .to_compile_error()
     }
 }
+
+/// Tuple structs and newtypes are application-level data structures: they are
+/// described by the workspace scanner, so the derive only has to provide
+/// validating deserialization for newtypes.
+fn generate_tuple_struct_impl(input: &DeriveInput, fields: &FieldsUnnamed) -> TokenStream {
+    let ident = &input.ident;
+
+    let has_validators = std::iter::once(&input.attrs)
+        .chain(fields.unnamed.iter().map(|field| &field.attrs))
+        .any(|attrs| match parse_field_validators(attrs) {
+            Ok(validators) => !validators.is_empty(),
+            Err(_) => true, // If parsing fails, assume validators exist to be safe
+        });
+
+    if !has_validators {
+        info!(
+            "Successfully generated tuple struct implementation (no validation) for: {}",
+            ident
+        );
+        return quote! {};
+    }
+
+    if fields.unnamed.len() != 1 {
+        error!("Validators on tuple struct with multiple fields: {}", ident);
+        return syn::Error::new(
+            input.span(),
+            format!("Validators are only supported on newtypes (tuple structs with a single field).\n\nExample usage:\n#[derive(Evenframe)]\n#[validators(StringValidator::Email)]\npub struct {}(String);", ident),
+        )
+        .to_compile_error();
+    }
+
+    info!(
+        "Successfully generated newtype implementation with validation for: {}",
+        ident
+    );
+    generate_newtype_deserialize(input, &fields.unnamed[0])
+}
@@ -12,9 +12,11 @@ fn unsupported_type_error(ty: &Type, type_str: &str, hint: &str) -> proc_macro2:
         format!(
             "Unsupported type: '{}'. {}\n\nSupported types include:\n\
             - Primitives: bool, char, String, i8-i128, u8-u128, f32, f64\n\
-            - Special: Decimal, DateTime, EvenframeDuration, EvenframeRecordId\n\
-            - Containers: Option<T>, Vec<T>, HashMap<K,V>, BTreeMap<K,V>\n\
-            - Custom: RecordLink<T>, OrderedFloat<T>, or any custom struct/enum",
+            - Special: Decimal, DateTime, NaiveDate, NaiveTime, Duration, EvenframeDuration, EvenframeRecordId\n\
+            - Common: Uuid, Url, IpAddr, serde_json::Value, Bytes\n\
+            - Geometry: EvenframePoint, EvenframePolygon, EvenframeGeometry, ...\n\
+            - Containers: Option<T>, Vec<T>, HashSet<T>, BTreeSet<T>, HashMap<K,V>, BTreeMap<K,V>, Box<T>, Arc<T>, Rc<T>\n\
+            - Custom: RecordLink<T>, OrderedFloat<T>, or any custom struct/enum (including generic ones)",
             type_str, hint
         ),
     )
@@ -33,7 +35,7 @@ fn parse_generic_args(
         args.len()
     );
     match (type_name, args.len()) {
-        ("Option" | "Vec" | "RecordLink" | "OrderedFloat", 1) => {
+        ("Option" | "Vec" | "HashSet" | "BTreeSet" | "RecordLink" | "OrderedFloat", 1) => {
             if let Some(GenericArgument::Type(inner_ty)) = args.first() {
                 trace!("Processing inner type for {}", type_name);
                 let inner_parsed = parse_data_type(inner_ty);
@@ -41,9 +43,19 @@ fn parse_generic_args(
                     "Option" => {
                         quote! { ::evenframe::types::FieldType::Option(Box::new(#inner_parsed)) }
                     }
+                    // Vec<u8> is a byte buffer
+                    "Vec" if quote! { #inner_ty }.to_string() == "u8" => {
+                        quote! { ::evenframe::types::FieldType::Bytes }
+                    }
                     "Vec" => {
                         quote! { ::evenframe::types::FieldType::Vec(Box::new(#inner_parsed)) }
                     }
+                    "HashSet" => {
+                        quote! { ::evenframe::types::FieldType::HashSet(Box::new(#inner_parsed)) }
+                    }
+                    "BTreeSet" => {
+                        quote! { ::evenframe::types::FieldType::BTreeSet(Box::new(#inner_parsed)) }
+                    }
                     "RecordLink" => {
                         quote! { ::evenframe::types::FieldType::RecordLink(Box::new(#inner_parsed)) }
                     }
@@ -60,6 +72,18 @@ fn parse_generic_args(
                 .to_compile_error()
             }
         }
+        ("Box" | "Arc" | "Rc", 1) => {
+            // Smart pointers are transparent
+            if let Some(GenericArgument::Type(inner_ty)) = args.first() {
+                parse_data_type(inner_ty)
+            } else {
+                syn::Error::new(
+                    args.span(),
+                    format!("{} type parameter must be a type", type_name),
+                )
+                .to_compile_error()
+            }
+        }
         ("HashMap" | "BTreeMap", 2) => {
             let mut args_iter = args.iter();
             match (args_iter.next(), args_iter.next()) {
@@ -93,14 +117,36 @@ fn parse_generic_args(
         }
         (name, count) => {
             let expected = match name {
-                "Option" | "Vec" | "RecordLink" | "OrderedFloat" => 1,
+                "Option" | "Vec" | "HashSet" | "BTreeSet" | "Box" | "Arc" | "Rc" | "RecordLink"
+                | "OrderedFloat" => 1,
                 "HashMap" | "BTreeMap" => 2,
                 _ => {
-                    return unsupported_type_error(
-                        ty,
-                        &format!("{}<...>", name),
-                        "Unknown generic type",
+                    // Instantiation of a user-defined generic struct or enum
+                    let mut type_args = Vec::new();
+                    for arg in args {
+                        match arg {
+                            GenericArgument::Type(arg_ty) => {
+                                type_args.push(parse_data_type(arg_ty))
+                            }
+                            GenericArgument::Lifetime(_) => {}
+                            _ => {
+                                return unsupported_type_error(
+                                    ty,
+                                    &format!("{}<...>", name),
+                                    "Only type arguments are supported on generic types",
+                                );
+                            }
+                        }
+                    }
+                    debug!(
+                        "Treating as generic instantiation: {}<{} args>",
+                        name,
+                        type_args.len()
                     );
+                    let lit = syn::LitStr::new(name, ty.span());
+                    return quote! {
+                        ::evenframe::types::FieldType::Generic(#lit.to_string(), vec![ #(#type_args),* ])
+                    };
                 }
             };
             syn::Error::new(
@@ -144,6 +190,35 @@ fn parse_simple_type(name: &str) -> Option<proc_macro2::TokenStream> {
         "DateTime" => Some(quote! { ::evenframe::types::FieldType::DateTime }),
         "EvenframeDuration" => Some(quote! { ::evenframe::types::FieldType::EvenframeDuration }),
         "Tz" => Some(quote! { ::evenframe::types::FieldType::Timezone }),
+        "Uuid" => Some(quote! { ::evenframe::types::FieldType::Uuid }),
+        "NaiveDate" => Some(quote! { ::evenframe::types::FieldType::NaiveDate }),
+        "NaiveTime" => Some(quote! { ::evenframe::types::FieldType::NaiveTime }),
+        "Bytes" | "ByteBuf" => Some(quote! { ::evenframe::types::FieldType::Bytes }),
+        "IpAddr" | "Ipv4Addr" | "Ipv6Addr" => {
+            Some(quote! { ::evenframe::types::FieldType::IpAddr })
+        }
+        "Url" => Some(quote! { ::evenframe::types::FieldType::Url }),
+        "EvenframePoint" => Some(quote! {
+            ::evenframe::types::FieldType::Geometry(::evenframe::types::GeometryKind::Point)
+        }),
+        "EvenframeLineString" => Some(quote! {
+            ::evenframe::types::FieldType::Geometry(::evenframe::types::GeometryKind::LineString)
+        }),
+        "EvenframePolygon" => Some(quote! {
+            ::evenframe::types::FieldType::Geometry(::evenframe::types::GeometryKind::Polygon)
+        }),
+        "EvenframeMultiPoint" => Some(quote! {
+            ::evenframe::types::FieldType::Geometry(::evenframe::types::GeometryKind::MultiPoint)
+        }),
+        "EvenframeMultiLineString" => Some(quote! {
+            ::evenframe::types::FieldType::Geometry(::evenframe::types::GeometryKind::MultiLineString)
+        }),
+        "EvenframeMultiPolygon" => Some(quote! {
+            ::evenframe::types::FieldType::Geometry(::evenframe::types::GeometryKind::MultiPolygon)
+        }),
+        "EvenframeGeometry" => Some(quote! {
+            ::evenframe::types::FieldType::Geometry(::evenframe::types::GeometryKind::Any)
+        }),
         "()" => Some(quote! { ::evenframe::types::FieldType::Unit }),
         _ => {
             trace!("'{}' is not a simple type", name);
@@ -259,6 +334,19 @@ fn parse_path_type(ty: &Type, type_path: &syn::TypePath) -> proc_macro2::TokenSt
             return error;
         }
 
+        // Names that are only unambiguous together with their crate path
+        let in_path = |name: &str| type_path.path.segments.iter().any(|s| s.ident == name);
+        match ident_str.as_str() {
+            // chrono::Duration is a TimeDelta with a different representation
+            "Duration" if !in_path("chrono") => {
+                return quote! { ::evenframe::types::FieldType::Duration };
+            }
+            "Value" if in_path("serde_json") => {
+                return quote! { ::evenframe::types::FieldType::JsonValue };
+            }
+            _ => {}
+        }
+
         // Check if it's a known simple type
         if let Some(field_type) = parse_simple_type(&ident_str) {
             debug!("Found simple type: {}", ident_str);
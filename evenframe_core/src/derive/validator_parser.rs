@@ -47,11 +47,10 @@ fn suggest_validator_correction(expr_str: &str) -> String {
     String::new()
 }
 
-pub fn parse_field_validators_with_logic(
-    attrs: &[Attribute],
-    value_ident: &str,
-) -> Result<(Vec<TokenStream>, Vec<TokenStream>)> {
-    tracing::debug!(attr_count = attrs.len(), value_ident = %value_ident, "Parsing field validators with logic");
+/// Parse the `#[validators(..)]` attribute into validator values. Shared by the derive
+/// macros and the CLI, which builds its configs from the parsed source files.
+pub fn parse_validators(attrs: &[Attribute]) -> Result<Vec<Validator>> {
+    tracing::debug!(attr_count = attrs.len(), "Parsing validators");
     // Check for common attribute mistakes
     for attr in attrs {
         if attr.path().is_ident("validator") {
@@ -89,20 +88,16 @@ pub fn parse_field_validators_with_logic(
 
             match parse_result {
                 Ok(validators_list) => {
-                    let mut validator_tokens = Vec::new();
-                    let mut logic_tokens = Vec::new();
+                    let mut validators = Vec::new();
                     for validator_expr in validators_list {
-                        let (val_tokens, log_tokens) =
-                            parse_validator_enum_with_logic(&validator_expr, value_ident)?;
-                        validator_tokens.extend(val_tokens);
-                        logic_tokens.extend(log_tokens);
+                        validators.extend(parse_validator_expr(&validator_expr)?);
                     }
-                    return Ok((validator_tokens, logic_tokens));
+                    return Ok(validators);
                 }
                 Err(_err) => {
                     // Try parsing as a single expression for backwards compatibility
                     match attr.parse_args::<syn::Expr>() {
-                        Ok(expr) => return parse_validator_enum_with_logic(&expr, value_ident),
+                        Ok(expr) => return parse_validator_expr(&expr),
                         Err(parse_err) => {
                             return Err(Error::new_spanned(
                                 attr,
@@ -122,7 +117,18 @@ pub fn parse_field_validators_with_logic(
             }
         }
     }
-    Ok((vec![], vec![]))
+    Ok(Vec::new())
+}
+
+pub fn parse_field_validators_with_logic(
+    attrs: &[Attribute],
+    value_ident: &str,
+) -> Result<(Vec<TokenStream>, Vec<TokenStream>)> {
+    tracing::debug!(attr_count = attrs.len(), value_ident = %value_ident, "Parsing field validators with logic");
+    Ok(validator_tokens_with_logic(
+        parse_validators(attrs)?,
+        value_ident,
+    ))
 }
 
 pub fn parse_field_validators(attrs: &[Attribute]) -> Result<Vec<TokenStream>> {
@@ -131,14 +137,35 @@ pub fn parse_field_validators(attrs: &[Attribute]) -> Result<Vec<TokenStream>> {
     Ok(validator_tokens)
 }
 
+/// The tokens of each validator, and of the logic that checks `value_ident` against it
+fn validator_tokens_with_logic(
+    validators: Vec<Validator>,
+    value_ident: &str,
+) -> (Vec<TokenStream>, Vec<TokenStream>) {
+    validators
+        .into_iter()
+        .map(|validator| {
+            let logic = validator.get_validation_logic_tokens(value_ident);
+            (quote! {#validator}, logic)
+        })
+        .unzip()
+}
+
 // Parse a validator enum expression and return both validator tokens and validation logic
 pub fn parse_validator_enum_with_logic(
     expr: &syn::Expr,
     value_ident: &str,
 ) -> Result<(Vec<TokenStream>, Vec<TokenStream>)> {
     tracing::trace!(value_ident = %value_ident, "Parsing validator enum with logic");
-    let mut validator_tokens = Vec::new();
-    let mut logic_tokens = Vec::new();
+    Ok(validator_tokens_with_logic(
+        parse_validator_expr(expr)?,
+        value_ident,
+    ))
+}
+
+/// Parse a validator expression, or an array of them, into validator values
+pub fn parse_validator_expr(expr: &syn::Expr) -> Result<Vec<Validator>> {
+    let mut validators = Vec::new();
 
     // Handle array of validators
     if let syn::Expr::Array(array_expr) = expr {
@@ -151,11 +178,8 @@ pub fn parse_validator_enum_with_logic(
         }
 
         for (idx, elem) in array_expr.elems.iter().enumerate() {
-            match parse_validator_enum_with_logic(elem, value_ident) {
-                Ok((val_tokens, log_tokens)) => {
-                    validator_tokens.extend(val_tokens);
-                    logic_tokens.extend(log_tokens);
-                }
+            match parse_validator_expr(elem) {
+                Ok(elem_validators) => validators.extend(elem_validators),
                 Err(err) => {
                     return Err(Error::new_spanned(
                         elem,
@@ -164,23 +188,17 @@ pub fn parse_validator_enum_with_logic(
                 }
             }
         }
-        return Ok((validator_tokens, logic_tokens));
+        return Ok(validators);
     }
 
     // Handle parenthesized expressions
     if let syn::Expr::Paren(paren) = expr {
-        return parse_validator_enum_with_logic(&paren.expr, value_ident);
+        return parse_validator_expr(&paren.expr);
     }
 
     // Try to parse the expression into a Validator enum using the SynEnum derive
     match Validator::try_from(expr) {
-        Ok(validator) => {
-            // Get the validation logic tokens
-            let validation_logic = validator.get_validation_logic_tokens(value_ident);
-            logic_tokens.push(validation_logic);
-
-            validator_tokens.push(quote! {#validator});
-        }
+        Ok(validator) => validators.push(validator),
         Err(err) => {
             // Provide more specific error messages based on the expression type
             let expr_str = quote!(#expr).to_string();
@@ -207,5 +225,5 @@ pub fn parse_validator_enum_with_logic(
         }
     }
 
-    Ok((validator_tokens, logic_tokens))
+    Ok(validators)
 }
@@ -20,3 +20,6 @@ pub mod schemasync;
 // Re-export commonly used items for convenience
 pub use error::{EvenframeError, Result};
 pub use schemasync::{compare, mockmake, mockmake::coordinate, mockmake::format};
+
+// Re-exported so generated code can name SurrealDB types without a direct dependency
+pub use surrealdb;
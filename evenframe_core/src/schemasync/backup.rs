@@ -0,0 +1,291 @@
+//! Timestamped backups of the target database and point-in-time restore.
+//!
+//! Every Schemasync run exports the database before changing it: the full schema, plus
+//! the records of tables whose preservation mode keeps existing data (records of other
+//! tables are regenerated anyway), or of every table with `backup.all_records`. The
+//! records are streamed straight into the file. The export is written to
+//! `<directory>/<namespace>-<database>-<timestamp>.surql`, and the oldest files past
+//! the configured retention are deleted. `restore` re-imports one of them into the
+//! configured namespace and database.
+
+use crate::{
+    error::{EvenframeError, Result},
+    schemasync::{
+        config::SchemasyncConfig,
+        open,
+        surql::transaction::{database_definitions, restore_from_backup},
+    },
+};
+use futures::StreamExt;
+use std::{
+    fs,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+use surrealdb::{Connection, Surreal};
+use tracing::{debug, info};
+
+const BACKUP_EXTENSION: &str = "surql";
+
+/// File name prefix of the backups of the configured namespace and database
+fn backup_prefix(config: &SchemasyncConfig) -> String {
+    format!(
+        "{}-{}-",
+        config.database.namespace, config.database.database
+    )
+}
+
+/// Write a new backup and apply the retention. The backup holds `schema` (a schema-only
+/// export) followed by the records of `record_tables`, or of every table with
+/// `backup.all_records`, which are streamed from the database straight into the file.
+/// A restore only replaces the tables whose records the backup holds. Returns the new
+/// file, or `None` when backups are disabled.
+pub async fn write_backup<C: Connection>(
+    config: &SchemasyncConfig,
+    db: &Surreal<C>,
+    schema: &str,
+    record_tables: &[String],
+) -> Result<Option<PathBuf>> {
+    if !config.backup.enabled {
+        debug!("Backups disabled, not writing the export");
+        return Ok(None);
+    }
+
+    let directory = Path::new(&config.backup.directory);
+    fs::create_dir_all(directory)?;
+
+    // Lexicographic order of the timestamp is chronological order
+    let timestamp = chrono::Utc::now().format("%Y%m%dT%H%M%S%3fZ");
+    let path = directory.join(format!(
+        "{}{}.{}",
+        backup_prefix(config),
+        timestamp,
+        BACKUP_EXTENSION
+    ));
+
+    let record_tables = if config.backup.all_records {
+        database_definitions(db).await?.0
+    } else {
+        record_tables.to_vec()
+    };
+    let mut file = BufWriter::new(fs::File::create(&path)?);
+    file.write_all(schema.as_bytes())?;
+    export_records(db, &record_tables, &mut file).await?;
+    file.flush()?;
+    info!(
+        path = %path.display(),
+        record_tables = record_tables.len(),
+        "Saved database backup"
+    );
+
+    prune_backups(config)?;
+    Ok(Some(path))
+}
+
+/// Stream the records of `tables` into `out` as `INSERT` statements
+pub async fn export_records<C: Connection>(
+    db: &Surreal<C>,
+    tables: &[String],
+    out: &mut impl Write,
+) -> Result<()> {
+    if tables.is_empty() {
+        return Ok(());
+    }
+    debug!(tables = ?tables, "Exporting records");
+
+    let mut stream = db
+        .export(())
+        .with_config()
+        .users(false)
+        .accesses(false)
+        .params(false)
+        .functions(false)
+        .analyzers(false)
+        .versions(false)
+        .tables(tables.to_vec())
+        .records(true)
+        .await
+        .map_err(|e| EvenframeError::database(format!("Failed to export records: {e}")))?;
+
+    // The definitions are already part of the schema export, keep only the data
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk
+            .map_err(|e| EvenframeError::database(format!("Error reading record export: {e}")))?;
+        let statement = String::from_utf8_lossy(&chunk);
+        let trimmed = statement.trim_start();
+        if trimmed.starts_with("INSERT") || trimmed.starts_with("-- TABLE DATA") {
+            out.write_all(&chunk)?;
+        }
+    }
+    Ok(())
+}
+
+/// Backups of the configured namespace and database, oldest first
+pub fn list_backups(config: &SchemasyncConfig) -> Result<Vec<PathBuf>> {
+    let directory = Path::new(&config.backup.directory);
+    if !directory.exists() {
+        return Ok(Vec::new());
+    }
+
+    let prefix = backup_prefix(config);
+    let mut backups = Vec::new();
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        let is_backup = path.extension().is_some_and(|ext| ext == BACKUP_EXTENSION)
+            && path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&prefix));
+        if is_backup {
+            backups.push(path);
+        }
+    }
+    backups.sort();
+    Ok(backups)
+}
+
+/// Delete the oldest backups past the configured retention. Returns the deleted files.
+pub fn prune_backups(config: &SchemasyncConfig) -> Result<Vec<PathBuf>> {
+    let retention = config.backup.retention;
+    let backups = list_backups(config)?;
+    if retention == 0 || backups.len() <= retention {
+        return Ok(Vec::new());
+    }
+
+    let expired = backups[..backups.len() - retention].to_vec();
+    for path in &expired {
+        debug!(path = %path.display(), "Deleting expired backup");
+        fs::remove_file(path)?;
+    }
+    Ok(expired)
+}
+
+/// Find a backup by path or file name, or the most recent one when `backup` is `None`
+pub fn resolve_backup(config: &SchemasyncConfig, backup: Option<&str>) -> Result<PathBuf> {
+    let Some(backup) = backup else {
+        return list_backups(config)?.pop().ok_or_else(|| {
+            EvenframeError::config(format!(
+                "No backups of {}/{} found in '{}'",
+                config.database.namespace, config.database.database, config.backup.directory
+            ))
+        });
+    };
+
+    let path = PathBuf::from(backup);
+    if path.is_file() {
+        return Ok(path);
+    }
+    let in_directory = Path::new(&config.backup.directory).join(backup);
+    if in_directory.is_file() {
+        return Ok(in_directory);
+    }
+    Err(EvenframeError::InvalidPath { path })
+}
+
+/// Put the configured database back to the backup at `path`. The tables whose records
+/// the backup holds are replaced, tables outside of the backup are only removed when
+/// they are in `managed_tables`.
+pub async fn restore(
+    config: &SchemasyncConfig,
+    path: &Path,
+    managed_tables: &[String],
+) -> Result<()> {
+    info!(
+        path = %path.display(),
+        namespace = %config.database.namespace,
+        database = %config.database.database,
+        "Restoring backup"
+    );
+    let export = fs::read_to_string(path)?;
+    let db = open(&config.database).await?;
+    let managed_accesses: Vec<String> = config
+        .database
+        .accesses
+        .iter()
+        .map(|access| access.name.clone())
+        .collect();
+    restore_from_backup(&db, &export, managed_tables, &managed_accesses).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schemasync::config::BackupConfig;
+    use surrealdb::engine::local::{Db, Mem};
+
+    async fn count(db: &Surreal<Db>, table: &str) -> usize {
+        let mut response = db
+            .query(format!("RETURN count(SELECT * FROM {table})"))
+            .await
+            .unwrap();
+        let count: Option<usize> = response.take(0).unwrap();
+        count.unwrap_or_default()
+    }
+
+    #[tokio::test]
+    async fn test_restore_replaces_only_backed_up_tables() {
+        let directory =
+            std::env::temp_dir().join(format!("evenframe-backups-{}", uuid::Uuid::new_v4()));
+        let mut config = SchemasyncConfig::for_testing();
+        config.mock_gen_config.default_record_count = 10;
+        config.backup = BackupConfig {
+            enabled: true,
+            directory: directory.to_string_lossy().into_owned(),
+            retention: 0,
+            all_records: false,
+        };
+
+        let db = Surreal::new::<Mem>(()).await.unwrap();
+        db.use_ns("test").use_db("test").await.unwrap();
+        db.query(
+            "DEFINE TABLE account SCHEMALESS; CREATE account:1, account:2; \
+             DEFINE TABLE session SCHEMALESS; CREATE session:1, session:2 SET token = 'a';",
+        )
+        .await
+        .unwrap();
+
+        let mut stream = db.export(()).with_config().records(false).await.unwrap();
+        let mut schema = String::new();
+        while let Some(chunk) = stream.next().await {
+            schema.push_str(&String::from_utf8_lossy(&chunk.unwrap()));
+        }
+        // `account` preserves its records, `session` is regenerated
+        let path = write_backup(&config, &db, &schema, &["account".to_string()])
+            .await
+            .unwrap()
+            .unwrap();
+        let backup = fs::read_to_string(&path).unwrap();
+        assert!(backup.contains("-- TABLE DATA: account"));
+        assert!(!backup.contains("-- TABLE DATA: session"));
+
+        // A failed migration changed `account`, regenerated `session` and added a table,
+        // meanwhile someone created a table Evenframe doesn't manage
+        db.query(
+            "DELETE account:1; DELETE session; CREATE session:3; \
+             DEFINE TABLE user SCHEMALESS; CREATE user:1; \
+             DEFINE TABLE audit SCHEMALESS; CREATE audit:1;",
+        )
+        .await
+        .unwrap();
+
+        let managed_tables = ["account", "session", "user"].map(String::from);
+        restore_from_backup(&db, &backup, &managed_tables, &[])
+            .await
+            .unwrap();
+
+        assert_eq!(count(&db, "account").await, 2);
+        assert_eq!(count(&db, "session").await, 1);
+        assert_eq!(count(&db, "user").await, 0);
+        assert_eq!(count(&db, "audit").await, 1);
+
+        config.backup.all_records = true;
+        let path = write_backup(&config, &db, &schema, &[])
+            .await
+            .unwrap()
+            .unwrap();
+        let backup = fs::read_to_string(&path).unwrap();
+        assert!(backup.contains("-- TABLE DATA: session"));
+        assert!(backup.contains("-- TABLE DATA: audit"));
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -295,6 +295,8 @@ fn resolve_path(
         match current_type {
             FieldType::Option(inner)
             | FieldType::Vec(inner)
+            | FieldType::HashSet(inner)
+            | FieldType::BTreeSet(inner)
             | FieldType::OrderedFloat(inner)
             | FieldType::RecordLink(inner) => {
                 current_type = inner;
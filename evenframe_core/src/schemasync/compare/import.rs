@@ -9,7 +9,7 @@ use std::{
     fmt::{self, Display, Formatter},
 };
 use surrealdb::Surreal;
-use surrealdb::engine::remote::http::Client;
+use surrealdb::engine::any::Any;
 use tracing;
 
 /// Represents a complex object type definition in SurrealDB
@@ -230,11 +230,11 @@ impl SchemaDefinition {
 
 /// Imports schema definitions from a SurrealDB instance
 pub struct SchemaImporter<'a> {
-    client: &'a Surreal<Client>,
+    client: &'a Surreal<Any>,
 }
 
 impl<'a> SchemaImporter<'a> {
-    pub fn new(client: &'a Surreal<Client>) -> Self {
+    pub fn new(client: &'a Surreal<Any>) -> Self {
         Self { client }
     }
 
@@ -10,30 +10,39 @@ use crate::{
     EvenframeError, Result, compare, evenframe_log,
     schemasync::{
         TableConfig,
-        config::{PerformanceConfig, SchemasyncMockGenConfig},
+        backup::write_backup,
+        config::{FixtureFormat, PerformanceConfig, SchemasyncMockGenConfig},
+        mockmake::{
+            Mockmaker,
+            rng::{self, MockRng},
+        },
         surql::access::setup_access_definitions,
     },
     types::{FieldType, TaggedUnion, VariantData},
 };
+use chrono::{DateTime, Utc};
 pub use import::SchemaImporter;
 use import::{AccessDefinition, FieldDefinition, ObjectType, SchemaDefinition, TableDefinition};
 use quote::{ToTokens, quote};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use surrealdb::engine::local::{Db, Mem};
-use surrealdb::{Surreal, engine::remote::http::Client};
+use surrealdb::{Surreal, engine::any::Any};
 use tracing;
 
 #[derive(Debug, Clone)]
 pub struct Comparator {
-    db: Surreal<Client>,
+    db: Surreal<Any>,
     schemasync_config: crate::schemasync::config::SchemasyncConfig,
 
     // Runtime state
     remote_schema: Option<Surreal<Db>>,
     new_schema: Option<Surreal<Db>>,
     access_query: String,
+    backup_path: Option<PathBuf>,
     remote_schema_string: String,
     new_schema_string: String,
     schema_changes: Option<SchemaChanges>,
@@ -41,7 +50,7 @@ pub struct Comparator {
 
 impl Comparator {
     pub fn new(
-        db: Surreal<Client>,
+        db: Surreal<Any>,
         schemasync_config: crate::schemasync::config::SchemasyncConfig,
     ) -> Self {
         Self {
@@ -50,17 +59,20 @@ impl Comparator {
             remote_schema: None,
             new_schema: None,
             access_query: String::new(),
+            backup_path: None,
             remote_schema_string: String::new(),
             new_schema_string: String::new(),
             schema_changes: None,
         }
     }
 
-    pub async fn run(mut self, define_statements: &str) -> Result<Self> {
+    /// Compare the database against `define_statements`. The comparison only uses the
+    /// schema, the records of `record_tables` are streamed into the backup file.
+    pub async fn run(mut self, define_statements: &str, record_tables: &[String]) -> Result<Self> {
         tracing::info!("Starting Comparator pipeline");
 
         tracing::debug!("Setting up schemas");
-        self.setup_schemas(define_statements).await?;
+        let schema = self.setup_schemas(define_statements).await?;
 
         tracing::debug!("Setting up access definitions");
         self.setup_access().await?;
@@ -71,14 +83,17 @@ impl Comparator {
         tracing::debug!("Comparing schemas");
         self.compare_schemas().await?;
 
+        tracing::debug!("Writing backup");
+        self.write_backup(&schema, record_tables).await?;
+
         tracing::info!("Comparator pipeline completed successfully");
         Ok(self)
     }
 
-    /// Setup backup and create in-memory schemas
-    async fn setup_schemas(&mut self, define_statements: &str) -> Result<()> {
+    /// Setup backup and create in-memory schemas, returns the schema export of the database
+    async fn setup_schemas(&mut self, define_statements: &str) -> Result<String> {
         tracing::trace!("Creating backup and in-memory schemas");
-        let (remote_schema, new_schema) = setup_backup_and_schemas(&self.db).await?;
+        let (schema, remote_schema, new_schema) = setup_backup_and_schemas(&self.db).await?;
         self.remote_schema = Some(remote_schema);
         // Execute and check define statements
         let _ = new_schema.query(define_statements).await.map_err(|e| {
@@ -90,7 +105,7 @@ impl Comparator {
         self.new_schema = Some(new_schema);
 
         tracing::trace!("Schemas setup complete");
-        Ok(())
+        Ok(schema)
     }
 
     /// Setup access definitions
@@ -146,6 +161,23 @@ impl Comparator {
         Ok(())
     }
 
+    /// Back up the schema and the records of `record_tables` and of tables about to be
+    /// removed. Tables that don't exist yet are skipped.
+    async fn write_backup(&mut self, schema: &str, record_tables: &[String]) -> Result<()> {
+        let changes = self.schema_changes.as_ref().unwrap();
+        let mut tables: Vec<String> = record_tables
+            .iter()
+            .filter(|table| !changes.new_tables.contains(*table))
+            .chain(changes.removed_tables.iter())
+            .cloned()
+            .collect();
+        tables.sort();
+        tables.dedup();
+
+        self.backup_path = write_backup(&self.schemasync_config, &self.db, schema, &tables).await?;
+        Ok(())
+    }
+
     // Getters for Mockmaker to access the results
     pub fn get_new_schema(&self) -> Option<&Surreal<Db>> {
         self.new_schema.as_ref()
@@ -158,6 +190,11 @@ impl Comparator {
     pub fn get_schema_changes(&self) -> Option<&compare::SchemaChanges> {
         self.schema_changes.as_ref()
     }
+
+    /// Backup file written before any changes were applied, `None` when backups are disabled
+    pub fn get_backup_path(&self) -> Option<&Path> {
+        self.backup_path.as_deref()
+    }
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
@@ -190,7 +227,7 @@ impl ToTokens for PreservationMode {
 
 /// Main entry point for Schemasync Merge functionality
 pub struct Merger<'a> {
-    pub client: &'a Surreal<Client>,
+    pub client: &'a Surreal<Any>,
     pub default_mock_gen_config: SchemasyncMockGenConfig,
     pub performance: PerformanceConfig,
 }
@@ -198,7 +235,7 @@ pub struct Merger<'a> {
 impl<'a> Merger<'a> {
     /// Create a new Merger instance
     pub async fn new(
-        client: &'a Surreal<Client>,
+        client: &'a Surreal<Any>,
         default_mock_gen_config: SchemasyncMockGenConfig,
         performance: PerformanceConfig,
     ) -> Result<Self> {
@@ -250,11 +287,14 @@ impl<'a> Merger<'a> {
         Comparator::compare(old, new)
     }
 
-    /// Export mock data to file
-    pub async fn export_mock_data(&self, _file_path: &str) -> Result<()> {
-        // Implementation will use the generated statements
-        // and write them to the specified file
-        todo!("Implement export_mock_data")
+    /// Export the mock data of `mockmaker` to fixture files in `directory`
+    pub fn export_mock_data(
+        &self,
+        mockmaker: &Mockmaker,
+        directory: &str,
+        formats: &[FixtureFormat],
+    ) -> Result<Vec<PathBuf>> {
+        mockmaker.export_fixtures(Path::new(directory), formats)
     }
 
     /// Generate preserved data for a specific table
@@ -435,23 +475,33 @@ impl<'a> Merger<'a> {
         result
     }
 
-    /// Generate new records for a table
+    /// Generate new records for a table, seeded like the Mockmaker's values
     fn generate_new_records(
         &self,
-        _table_name: &str,
+        table_name: &str,
         table_config: &TableConfig,
         count: usize,
     ) -> Vec<serde_json::Value> {
         use serde_json::Value;
 
+        let seed = table_config
+            .mock_generation_config
+            .as_ref()
+            .and_then(|config| config.seed)
+            .or(self.schemasync_config.mock_gen_config.seed);
+        let now = rng::reference_time(seed);
         let mut records = Vec::new();
 
-        for _ in 0..count {
+        for index in 0..count {
             let mut record = serde_json::Map::new();
 
             // Generate values for each field
             for field in &table_config.struct_config.fields {
-                let value = Self::generate_field_value(field, table_config);
+                let mut rng = rng::mock_rng(
+                    seed,
+                    &format!("{}/preserved:{}#{}", table_name, field.field_name, index),
+                );
+                let value = Self::generate_field_value(field, table_config, &mut rng, now);
                 record.insert(field.field_name.clone(), value);
             }
 
@@ -465,20 +515,26 @@ impl<'a> Merger<'a> {
     fn generate_field_value(
         field: &crate::types::StructField,
         _table_config: &TableConfig,
+        rng: &mut MockRng,
+        now: DateTime<Utc>,
     ) -> serde_json::Value {
         use crate::types::FieldType;
         use serde_json::json;
 
         // Use format if available
         if let Some(format) = &field.format {
-            let value = format.generate_formatted_value();
+            let value = format.generate_formatted_value_with(rng, now);
 
             // Check if the format generates numeric values
             match format {
                 crate::schemasync::mockmake::format::Format::Percentage
                 | crate::schemasync::mockmake::format::Format::Latitude
                 | crate::schemasync::mockmake::format::Format::Longitude
-                | crate::schemasync::mockmake::format::Format::CurrencyAmount => {
+                | crate::schemasync::mockmake::format::Format::CurrencyAmount
+                | crate::schemasync::mockmake::format::Format::Normal(..)
+                | crate::schemasync::mockmake::format::Format::LogNormal(..)
+                | crate::schemasync::mockmake::format::Format::Exponential(_)
+                | crate::schemasync::mockmake::format::Format::Zipf(..) => {
                     // Try to parse as number
                     if let Ok(num) = value.parse::<f64>() {
                         return json!(num);
@@ -492,25 +548,25 @@ impl<'a> Merger<'a> {
 
         // Generate based on field type
         match &field.field_type {
-            FieldType::String => json!(crate::schemasync::Mockmaker::random_string(8)),
-            FieldType::Bool => json!(rand::random::<bool>()),
+            FieldType::String => json!(crate::schemasync::Mockmaker::random_string_with(rng, 8)),
+            FieldType::Bool => json!(rng.random::<bool>()),
             FieldType::U8
             | FieldType::U16
             | FieldType::U32
             | FieldType::U64
             | FieldType::U128
-            | FieldType::Usize => json!(rand::random::<u32>() % 100),
+            | FieldType::Usize => json!(rng.random::<u32>() % 100),
             FieldType::I8
             | FieldType::I16
             | FieldType::I32
             | FieldType::I64
             | FieldType::I128
-            | FieldType::Isize => json!(rand::random::<i32>() % 100),
-            FieldType::F32 | FieldType::F64 => json!(rand::random::<f64>() * 100.0),
-            FieldType::DateTime => json!(chrono::Utc::now().to_rfc3339()),
+            | FieldType::Isize => json!(rng.random::<i32>() % 100),
+            FieldType::F32 | FieldType::F64 => json!(rng.random::<f64>() * 100.0),
+            FieldType::DateTime => json!(now.to_rfc3339()),
             FieldType::EvenframeDuration => {
                 // Generate random duration in nanoseconds (0 to 1 day)
-                json!(rand::random::<i64>() % 86_400_000_000_000i64)
+                json!(rng.random::<i64>() % 86_400_000_000_000i64)
             }
             FieldType::Timezone => {
                 // Generate random IANA timezone string
@@ -524,11 +580,11 @@ impl<'a> Merger<'a> {
                     "Asia/Shanghai",
                     "Australia/Sydney",
                 ];
-                let index = (rand::random::<f64>() * timezones.len() as f64) as usize;
+                let index = (rng.random::<f64>() * timezones.len() as f64) as usize;
                 json!(timezones[index.min(timezones.len() - 1)])
             }
             FieldType::Option(inner) => {
-                if rand::random::<bool>() {
+                if rng.random::<bool>() {
                     let inner_field = crate::types::StructField {
                         field_name: field.field_name.clone(),
                         field_type: *inner.clone(),
@@ -538,7 +594,7 @@ impl<'a> Merger<'a> {
                         validators: Vec::new(),
                         always_regenerate: false,
                     };
-                    Self::generate_field_value(&inner_field, _table_config)
+                    Self::generate_field_value(&inner_field, _table_config, rng, now)
                 } else {
                     json!(null)
                 }
@@ -547,7 +603,7 @@ impl<'a> Merger<'a> {
             FieldType::Other(type_name) => {
                 // Handle common types
                 if type_name.contains("DateTime") {
-                    json!(chrono::Utc::now().to_rfc3339())
+                    json!(now.to_rfc3339())
                 } else {
                     json!(format!("{}:1", type_name.to_lowercase()))
                 }
@@ -1264,7 +1320,22 @@ pub fn collect_referenced_objects(
                 objects_to_process.push(type_name.clone());
             }
         }
-        FieldType::Option(inner) | FieldType::Vec(inner) | FieldType::RecordLink(inner) => {
+        FieldType::Generic(type_name, args) => {
+            // The generic definition itself plus anything its arguments reference
+            collect_referenced_objects(
+                &FieldType::Other(type_name.clone()),
+                objects_to_process,
+                enums,
+            );
+            for arg in args {
+                collect_referenced_objects(arg, objects_to_process, enums);
+            }
+        }
+        FieldType::Option(inner)
+        | FieldType::Vec(inner)
+        | FieldType::HashSet(inner)
+        | FieldType::BTreeSet(inner)
+        | FieldType::RecordLink(inner) => {
             collect_referenced_objects(inner, objects_to_process, enums);
         }
         FieldType::Tuple(types) => {
@@ -1286,7 +1357,7 @@ pub fn collect_referenced_objects(
 }
 
 pub async fn compare_schemas(
-    db: &Surreal<Client>,
+    db: &Surreal<Any>,
     remote_schema_string: &str,
     new_schema_string: &str,
 ) -> Result<SchemaChanges> {
@@ -1372,15 +1443,25 @@ pub async fn export_schemas(
     Ok((remote_schema_string, new_schema_string))
 }
 
-pub async fn setup_backup_and_schemas(db: &Surreal<Client>) -> Result<(Surreal<Db>, Surreal<Db>)> {
+/// Export the database schema (also written to `backup.surql`) and create the in-memory
+/// schemas used for comparison. Records are not exported, the comparison only needs the
+/// definitions. Returns the schema export alongside the two schemas.
+pub async fn setup_backup_and_schemas(
+    db: &Surreal<Any>,
+) -> Result<(String, Surreal<Db>, Surreal<Db>)> {
     use futures::StreamExt;
 
-    tracing::trace!("Creating database backup");
-    let mut backup_stream = db.export(()).await.map_err(|e| {
-        EvenframeError::database(format!(
-            "There was a problem exporting the remote database: {e}"
-        ))
-    })?;
+    tracing::trace!("Exporting database schema");
+    let mut backup_stream = db
+        .export(())
+        .with_config()
+        .records(false)
+        .await
+        .map_err(|e| {
+            EvenframeError::database(format!(
+                "There was a problem exporting the remote database: {e}"
+            ))
+        })?;
 
     let mut backup = String::new();
     while let Some(result) = backup_stream.next().await {
@@ -1430,5 +1511,5 @@ pub async fn setup_backup_and_schemas(db: &Surreal<Client>) -> Result<(Surreal<D
         })?;
 
     tracing::trace!("In-memory schemas ready");
-    Ok((remote_schema, new_schema))
+    Ok((backup, remote_schema, new_schema))
 }
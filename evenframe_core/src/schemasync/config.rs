@@ -1,6 +1,11 @@
-use crate::{coordinate::CoordinationGroup, schemasync::compare::PreservationMode};
+use crate::{
+    coordinate::CoordinationGroup,
+    mockmake::{locale::Locale, snapshot::Anonymization},
+    schemasync::compare::PreservationMode,
+};
 use bon::Builder;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use tracing::{debug, trace};
 
 /// Configuration for Schemasync operations (database synchronization)
@@ -14,15 +19,116 @@ pub struct SchemasyncConfig {
     pub mock_gen_config: SchemasyncMockGenConfig,
     /// Performance tuning configuration
     pub performance: PerformanceConfig,
+    /// How schema changes are applied to the database
+    #[serde(default)]
+    #[builder(default)]
+    pub migration: MigrationConfig,
+    /// Where the pre-migration exports are kept and how many of them
+    #[serde(default)]
+    #[builder(default)]
+    pub backup: BackupConfig,
+    /// Fixture files the generated mock data is written to
+    #[serde(default)]
+    #[builder(default)]
+    pub fixtures: FixtureConfig,
+    /// Backup the mock data is copied from, anonymized, instead of being generated
+    #[serde(default)]
+    #[builder(default)]
+    pub snapshot: SnapshotConfig,
+    /// Where the named scenarios `evenframe scenario <name>` applies are kept
+    #[serde(default)]
+    #[builder(default)]
+    pub scenarios: ScenarioConfig,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct DatabaseConfig {
+    /// Connection URL. The scheme selects the engine: `http(s)://` and `ws(s)://` for a
+    /// remote server, `mem://` or `surrealkv://<path>` for an embedded database.
+    /// A URL without a scheme is treated as an HTTP address.
     pub url: String,
     pub namespace: String,
     pub database: String,
     pub timeout: u64,
     pub accesses: Vec<AccessConfig>,
+    /// How to sign in, defaults to root credentials from the environment
+    #[serde(default)]
+    pub auth: AuthConfig,
+}
+
+/// Sign-in level used for the database connection. Credentials missing from the config
+/// are read from `SURREALDB_USER`/`SURREALDB_PASSWORD`, `SURREALDB_TOKEN` and
+/// `SURREALDB_RECORD_PARAMS` (a JSON object) respectively.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "level", rename_all = "snake_case")]
+pub enum AuthConfig {
+    Root {
+        #[serde(default)]
+        username: Option<String>,
+        #[serde(default)]
+        password: Option<String>,
+    },
+    /// A user defined on the configured namespace
+    Namespace {
+        #[serde(default)]
+        username: Option<String>,
+        #[serde(default)]
+        password: Option<String>,
+    },
+    /// A user defined on the configured database
+    Database {
+        #[serde(default)]
+        username: Option<String>,
+        #[serde(default)]
+        password: Option<String>,
+    },
+    /// Sign in through a record access method with its sign-in parameters
+    Record {
+        access: String,
+        #[serde(default)]
+        params: serde_json::Map<String, serde_json::Value>,
+    },
+    /// Authenticate with an existing token (JWT)
+    Token {
+        #[serde(default)]
+        token: Option<String>,
+    },
+    /// Do not sign in, for servers started without authentication
+    None,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        AuthConfig::Root {
+            username: None,
+            password: None,
+        }
+    }
+}
+
+impl AuthConfig {
+    pub fn level(&self) -> &'static str {
+        match self {
+            AuthConfig::Root { .. } => "root",
+            AuthConfig::Namespace { .. } => "namespace",
+            AuthConfig::Database { .. } => "database",
+            AuthConfig::Record { .. } => "record",
+            AuthConfig::Token { .. } => "token",
+            AuthConfig::None => "none",
+        }
+    }
+
+    /// Whether this level can define tables, fields and accesses on the database.
+    /// `None` when it cannot be known up front, as for tokens.
+    pub fn can_define_schema(&self) -> Option<bool> {
+        match self {
+            AuthConfig::Root { .. } | AuthConfig::Namespace { .. } | AuthConfig::Database { .. } => {
+                Some(true)
+            }
+            AuthConfig::Record { .. } => Some(false),
+            AuthConfig::Token { .. } | AuthConfig::None => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -56,9 +162,50 @@ pub struct SchemasyncMockGenConfig {
     pub coordination_groups: Vec<CoordinationGroup>,
 
     pub full_refresh_mode: bool,
+
+    /// seeds mock generation so identical schemas produce identical data, overriden by
+    /// table level configs. Unset generates different data on every run
+    #[serde(default)]
+    pub seed: Option<u64>,
+
+    /// locale of generated names, addresses and phone numbers, like "de_DE", overriden by
+    /// table level and field level configs
+    #[serde(default)]
+    #[builder(default)]
+    pub locale: Locale,
+
+    /// number of mock data batches generated and inserted at once. Each batch holds
+    /// `batch_size` records, so this bounds the records kept in memory
+    #[serde(default = "default_parallel_batches")]
+    #[builder(default = default_parallel_batches())]
+    pub parallel_batches: usize,
+}
+
+fn default_parallel_batches() -> usize {
+    1
 }
 
+/// Schemes of the engines that run inside the process rather than on a server, one per
+/// `kv-*` feature enabled on the surrealdb dependency
+const EMBEDDED_SCHEMES: [&str; 2] = ["mem", "surrealkv"];
+
 impl DatabaseConfig {
+    /// The connection URL with a scheme, defaulting to HTTP for bare addresses
+    pub fn endpoint(&self) -> String {
+        if self.url.contains("://") {
+            self.url.clone()
+        } else {
+            format!("http://{}", self.url)
+        }
+    }
+
+    /// Whether the URL targets an embedded engine, which has no users to sign in as
+    pub fn is_embedded(&self) -> bool {
+        let endpoint = self.endpoint();
+        let scheme = endpoint.split("://").next().unwrap_or_default();
+        EMBEDDED_SCHEMES.contains(&scheme)
+    }
+
     /// Creates a database configuration suitable for testing
     pub fn for_testing() -> Self {
         debug!("Creating database configuration for testing environment");
@@ -72,6 +219,7 @@ impl DatabaseConfig {
                 table_name: "user".to_owned(),
             }],
             timeout: 60,
+            auth: AuthConfig::default(),
         };
         trace!(
             "Test database config - URL: {}, namespace: {}, database: {}, timeout: {}s",
@@ -85,6 +233,27 @@ impl DatabaseConfig {
     }
 }
 
+#[cfg(test)]
+impl SchemasyncConfig {
+    /// Creates a configuration generating one mock record per table, in batches of 10,
+    /// against the test database
+    pub fn for_testing() -> Self {
+        Self::builder()
+            .database(DatabaseConfig::for_testing())
+            .should_generate_mocks(true)
+            .mock_gen_config(
+                SchemasyncMockGenConfig::builder()
+                    .default_record_count(1)
+                    .default_preservation_mode(PreservationMode::None)
+                    .default_batch_size(10)
+                    .full_refresh_mode(false)
+                    .build(),
+            )
+            .performance(PerformanceConfig::default())
+            .build()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PerformanceConfig {
     pub embedded_db_memory_limit: String,
@@ -92,6 +261,95 @@ pub struct PerformanceConfig {
     pub use_progressive_loading: bool,
 }
 
+/// Controls how removes, defines, accesses and mock data reach the database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MigrationConfig {
+    /// Run the migration as a single `BEGIN TRANSACTION ... COMMIT TRANSACTION` batch
+    /// instead of statement by statement
+    pub transactional: bool,
+    /// Include the mock data statements in the transaction
+    pub include_mock_data: bool,
+    /// Restore the database from the pre-migration backup when the migration fails.
+    /// Off by default: the restore replaces every table the backup holds.
+    pub restore_on_failure: bool,
+}
+
+/// Timestamped exports written before every Schemasync run, used by `evenframe restore`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackupConfig {
+    /// Write a backup file before applying changes
+    pub enabled: bool,
+    /// Directory holding the backup files
+    pub directory: String,
+    /// Number of backups kept per namespace and database, older ones are deleted.
+    /// `0` keeps every backup.
+    pub retention: usize,
+    /// Back up the records of every table, not only of the tables whose preservation
+    /// mode keeps existing data. Backups used as mock data snapshots need it.
+    pub all_records: bool,
+}
+
+/// Mock data written to files, per table, for consumers without a database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FixtureConfig {
+    /// Also write fixtures on every Schemasync run, which then requires a mock data
+    /// `seed`. `evenframe fixtures` writes them regardless, without connecting to the
+    /// database.
+    pub enabled: bool,
+    /// Directory holding the fixture files
+    pub directory: String,
+    /// File formats written for every table
+    pub formats: Vec<FixtureFormat>,
+}
+
+/// Anonymized production data used as mock data, see `mockmake::snapshot`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SnapshotConfig {
+    /// Copy the mock data from the snapshot instead of generating it
+    pub enabled: bool,
+    /// Path of the backup file, or its file name in the backup directory
+    pub backup: String,
+    /// Salt of hashed values. Without a secret salt, hashes of guessable values such as
+    /// email addresses can be reversed, so `Hash` rules are refused while it is empty.
+    pub salt: String,
+    /// Anonymization rules by table and field name, overriden by the `anonymize` rules
+    /// of the table's `#[mock_data]`
+    pub anonymize: HashMap<String, HashMap<String, Anonymization>>,
+}
+
+/// Hand-authored expected states of the database, see `mockmake::scenario`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScenarioConfig {
+    /// Directory holding the `<name>.toml`, `<name>.json` and `<name>.ron` scenarios
+    pub directory: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FixtureFormat {
+    /// `<table>.json`, an array of records
+    Json,
+    /// `<table>.ndjson`, one record per line
+    Ndjson,
+    /// `<table>.surql`, an import script of `INSERT` statements
+    Surql,
+}
+
+impl FixtureFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            FixtureFormat::Json => "json",
+            FixtureFormat::Ndjson => "ndjson",
+            FixtureFormat::Surql => "surql",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum MockMode {
@@ -116,6 +374,45 @@ impl Default for RegenerateFieldsConfig {
     }
 }
 
+impl Default for MigrationConfig {
+    fn default() -> Self {
+        Self {
+            transactional: true,
+            include_mock_data: false,
+            restore_on_failure: false,
+        }
+    }
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            directory: "evenframe/backups".to_string(),
+            retention: 10,
+            all_records: false,
+        }
+    }
+}
+
+impl Default for FixtureConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            directory: "evenframe/fixtures".to_string(),
+            formats: vec![FixtureFormat::Json],
+        }
+    }
+}
+
+impl Default for ScenarioConfig {
+    fn default() -> Self {
+        Self {
+            directory: "evenframe/scenarios".to_string(),
+        }
+    }
+}
+
 impl Default for PerformanceConfig {
     fn default() -> Self {
         debug!("Creating default performance configuration");
@@ -206,7 +206,7 @@ impl ToTokens for Direction {
                 tokens.extend(quote! { ::evenframe::schemasync::Direction::To })
             }
             Direction::Both => {
-                tokens.extend(quote! { ::evenframe::schemasync::Direction::To })
+                tokens.extend(quote! { ::evenframe::schemasync::Direction::Both })
             }
         }
     }
@@ -228,8 +228,8 @@ impl FromStr for Direction {
                 Ok(Direction::To)
             },
             "both" => {
-                trace!("Parsed direction: Both (mapped to To)");
-                Ok(Direction::To)
+                trace!("Parsed direction: Both");
+                Ok(Direction::Both)
             },
             _ => {
                 error!("Invalid direction string: '{}'", s);
@@ -0,0 +1,679 @@
+//! Chunked bulk inserts of generated mock data.
+//!
+//! Instead of one `UPSERT` per record sent as a single query, the records of a table are
+//! generated `batch_size` at a time and written with one `INSERT INTO <table> $batch`
+//! per chunk, the records bound as a JSON array. JSON has no record links, datetimes or
+//! durations, so the statement casts the bound fields back by their type, down into
+//! nested objects, tuples, maps and enums. At most
+//! `parallel_batches` chunks are generated and in flight at once, which bounds the
+//! memory a table takes regardless of its record count.
+
+use crate::{
+    error::{EvenframeError, Result},
+    mockmake::Mockmaker,
+    schemasync::{
+        StructConfig, TableConfig, TaggedUnion,
+        surql::{is_generated_field, needs_null_preservation},
+    },
+    types::{FieldType, StructKind, VariantData},
+};
+use convert_case::{Case, Casing};
+use futures::{StreamExt, TryStreamExt, future};
+use serde_json::Value;
+use std::{collections::HashMap, ops::Range};
+use tracing::{debug, info};
+
+impl Mockmaker {
+    /// Number of records generated and inserted per chunk for a table
+    pub fn batch_size(&self, table_config: &TableConfig) -> usize {
+        table_config
+            .mock_generation_config
+            .as_ref()
+            .map(|c| c.batch_size)
+            .unwrap_or(self.schemasync_config.mock_gen_config.default_batch_size)
+            .max(1)
+    }
+
+    /// Generate and insert the mock records of a table in chunks of its batch size
+    pub async fn bulk_insert(&self, table_name: &str, table_config: &TableConfig) -> Result<()> {
+        let total = self.record_count(table_config);
+        let batch_size = self.batch_size(table_config);
+        let parallel_batches = self
+            .schemasync_config
+            .mock_gen_config
+            .parallel_batches
+            .max(1);
+        let statement = self.bulk_insert_statement(table_name, table_config);
+        debug!(
+            table = %table_name,
+            total,
+            batch_size,
+            parallel_batches,
+            statement = %statement,
+            "Bulk inserting mock data"
+        );
+
+        let mut inserted = 0;
+        let batches = (0..total)
+            .step_by(batch_size)
+            .map(|start| start..(start + batch_size).min(total));
+        futures::stream::iter(batches)
+            .map(|range| self.insert_batch(table_name, table_config, &statement, range))
+            .buffer_unordered(parallel_batches)
+            .try_for_each(|count| {
+                inserted += count;
+                info!(
+                    table = %table_name,
+                    "Inserted {}/{} records ({}%)",
+                    inserted,
+                    total,
+                    inserted * 100 / total.max(1)
+                );
+                future::ready(Ok(()))
+            })
+            .await
+    }
+
+    /// Generate the records in `range` of a table and insert them with `statement`.
+    /// Returns the number of inserted records.
+    async fn insert_batch(
+        &self,
+        table_name: &str,
+        table_config: &TableConfig,
+        statement: &str,
+        range: Range<usize>,
+    ) -> Result<usize> {
+        let records: Vec<Value> = range
+            .clone()
+            .map(|index| {
+                self.generate_record(table_name, table_config, index)
+                    .to_json()
+            })
+            .collect();
+        let count = records.len();
+
+        let mut response = self
+            .db
+            .query(statement)
+            .bind(("records", records))
+            .await
+            .map_err(|e| {
+                EvenframeError::database(format!(
+                    "Failed to insert records {}..{} of '{}': {e}",
+                    range.start, range.end, table_name
+                ))
+            })?;
+        if let Some(e) = response.take_errors().into_values().next() {
+            return Err(EvenframeError::database(format!(
+                "Failed to insert records {}..{} of '{}': {e}",
+                range.start, range.end, table_name
+            )));
+        }
+        Ok(count)
+    }
+
+    /// The statement inserting the records bound as `$records` into `table_name`.
+    /// Existing records are updated, keeping the `NULL`s of optional fields as the
+    /// `UPSERT`s do.
+    pub fn bulk_insert_statement(&self, table_name: &str, table_config: &TableConfig) -> String {
+        let is_relation = table_config.relation.is_some();
+        let casts = JsonCasts {
+            tables: &self.tables,
+            objects: &self.objects,
+            enums: &self.enums,
+        };
+        let fields: Vec<_> = table_config
+            .struct_config
+            .fields
+            .iter()
+            .filter(|field| is_generated_field(field))
+            .collect();
+
+        let mut projections = vec!["<record> id AS id".to_string()];
+        projections.extend(fields.iter().map(|field| {
+            let expr = format!("`{}`", field.field_name);
+            let value = casts.cast(&field.field_type, &expr).unwrap_or(expr);
+            format!("{} AS `{}`", value, field.field_name)
+        }));
+
+        // The in/out fields of relations are never updated
+        let updates: Vec<_> = fields
+            .iter()
+            .filter(|field| {
+                !(is_relation && (field.field_name == "in" || field.field_name == "out"))
+            })
+            .map(|field| {
+                let name = &field.field_name;
+                if needs_null_preservation(field, self.tables.get(table_name)) {
+                    format!("`{name}` = (IF `{name}` != NULL THEN $input.`{name}` ELSE NULL END)")
+                } else {
+                    format!("`{name}` = $input.`{name}`")
+                }
+            })
+            .collect();
+
+        let mut statement = format!(
+            "LET $batch = (SELECT {} FROM $records);\nINSERT {}INTO {} $batch",
+            projections.join(", "),
+            if is_relation { "RELATION " } else { "" },
+            table_name
+        );
+        if !updates.is_empty() {
+            statement.push_str(&format!(" ON DUPLICATE KEY UPDATE {}", updates.join(", ")));
+        }
+        statement.push(';');
+        statement
+    }
+
+    /// The mock records of a table inserted chunk by chunk like `bulk_insert` does, with
+    /// the records of each chunk inlined as `$records` so the statements can run in a
+    /// transaction
+    pub fn bulk_insert_statements(&self, table_name: &str, table_config: &TableConfig) -> String {
+        let statement = self.bulk_insert_statement(table_name, table_config);
+        let total = self.record_count(table_config);
+        let batch_size = self.batch_size(table_config);
+
+        let mut output = String::new();
+        for start in (0..total).step_by(batch_size) {
+            let records: Vec<Value> = (start..(start + batch_size).min(total))
+                .map(|index| {
+                    self.generate_record(table_name, table_config, index)
+                        .to_json()
+                })
+                .collect();
+            output.push_str(&format!(
+                "LET $records = {};\n{}\n",
+                Value::Array(records),
+                statement
+            ));
+        }
+        output
+    }
+}
+
+/// Builds the casts that turn the JSON of generated values (see `surql_to_json`) back
+/// into the values SurrealDB expects. Objects, tuples, maps and enums are cast field by
+/// field, so nested record links and datetimes keep their types.
+pub(crate) struct JsonCasts<'a> {
+    pub tables: &'a HashMap<String, TableConfig>,
+    pub objects: &'a HashMap<String, StructConfig>,
+    pub enums: &'a HashMap<String, TaggedUnion>,
+}
+
+impl JsonCasts<'_> {
+    /// SurrealQL expression casting the JSON at `expr` to `field_type`, `None` when the
+    /// JSON already is the expected value
+    pub fn cast(&self, field_type: &FieldType, expr: &str) -> Option<String> {
+        self.cast_at(field_type, expr, 0, &mut Vec::new())
+    }
+
+    fn cast_at(
+        &self,
+        field_type: &FieldType,
+        expr: &str,
+        depth: usize,
+        visited: &mut Vec<String>,
+    ) -> Option<String> {
+        match field_type {
+            FieldType::EvenframeRecordId | FieldType::RecordLink(_) => {
+                Some(format!("<record> {}", expr))
+            }
+            FieldType::DateTime | FieldType::NaiveDate => Some(format!("<datetime> {}", expr)),
+            FieldType::Uuid => Some(format!("<uuid> {}", expr)),
+            FieldType::Decimal => Some(format!("<decimal> {}", expr)),
+            FieldType::EvenframeDuration | FieldType::Duration => {
+                Some(format!("<duration> {}", expr))
+            }
+            FieldType::Bytes => Some(format!("<bytes> {}", expr)),
+            FieldType::Geometry(kind) => {
+                Some(format!("<geometry<{}>> {}", kind.surreal_name(), expr))
+            }
+            FieldType::Option(inner) => {
+                let cast = self.cast_at(inner, expr, depth, visited)?;
+                Some(format!(
+                    "(IF {expr} = NONE OR {expr} = NULL THEN NONE ELSE {cast} END)"
+                ))
+            }
+            FieldType::Vec(inner) | FieldType::HashSet(inner) | FieldType::BTreeSet(inner) => {
+                let item = format!("$item_{}", depth);
+                let mapped = self
+                    .cast_at(inner, &item, depth + 1, visited)
+                    .map(|cast| {
+                        format!(
+                            "(IF type::is::array({expr}) THEN array::map({expr}, |{item}| {cast}) ELSE {expr} END)"
+                        )
+                    });
+                match (field_type, mapped) {
+                    (FieldType::Vec(_), mapped) => mapped,
+                    (_, mapped) => Some(format!("<set> {}", mapped.as_deref().unwrap_or(expr))),
+                }
+            }
+            FieldType::Tuple(types) => {
+                let types: Vec<_> = types.iter().collect();
+                self.tuple_cast(&types, expr, depth, visited)
+            }
+            FieldType::Struct(fields) => {
+                let fields: Vec<_> = fields
+                    .iter()
+                    .map(|(name, ft)| (name.as_str(), ft))
+                    .collect();
+                self.object_cast(&fields, expr, depth, visited)
+            }
+            FieldType::HashMap(_, value) | FieldType::BTreeMap(_, value) => {
+                let entry = format!("$entry_{}", depth);
+                let cast = self.cast_at(value, &format!("{entry}[1]"), depth + 1, visited)?;
+                Some(format!(
+                    "(IF type::is::object({expr}) THEN object::from_entries(array::map(object::entries({expr}), |{entry}| [{entry}[0], {cast}])) ELSE {expr} END)"
+                ))
+            }
+            FieldType::Other(_) | FieldType::Generic(..) => {
+                self.named_cast(&field_type.to_string(), expr, depth, visited)
+            }
+            _ => None,
+        }
+    }
+
+    /// Casts of tables (record links), structs and enums, by type name
+    fn named_cast(
+        &self,
+        type_name: &str,
+        expr: &str,
+        depth: usize,
+        visited: &mut Vec<String>,
+    ) -> Option<String> {
+        if self
+            .tables
+            .values()
+            .any(|table| table.table_name == type_name)
+        {
+            return Some(format!("<record> {}", expr));
+        }
+        // Recursive types are generated as null past their first level
+        if visited.iter().any(|name| name == type_name) {
+            return None;
+        }
+        visited.push(type_name.to_string());
+        let cast = if let Some(struct_config) = self
+            .objects
+            .get(type_name)
+            .or_else(|| self.objects.get(&type_name.to_case(Case::Snake)))
+        {
+            self.struct_cast(struct_config, expr, depth, visited)
+        } else if let Some(tagged_union) = self.enums.get(type_name) {
+            self.enum_cast(tagged_union, expr, depth, visited)
+        } else {
+            None
+        };
+        visited.pop();
+        cast
+    }
+
+    fn struct_cast(
+        &self,
+        struct_config: &StructConfig,
+        expr: &str,
+        depth: usize,
+        visited: &mut Vec<String>,
+    ) -> Option<String> {
+        if let Some(inner_field) = struct_config.newtype_field() {
+            return self.cast_at(&inner_field.field_type, expr, depth, visited);
+        }
+        if struct_config.kind == StructKind::Tuple {
+            let types: Vec<_> = struct_config.fields.iter().map(|f| &f.field_type).collect();
+            return self.tuple_cast(&types, expr, depth, visited);
+        }
+        let fields: Vec<_> = struct_config
+            .fields
+            .iter()
+            .map(|f| (f.field_name.as_str(), &f.field_type))
+            .collect();
+        self.object_cast(&fields, expr, depth, visited)
+    }
+
+    /// Rebuild the object at `expr` with its fields cast, when any of them needs a cast
+    fn object_cast(
+        &self,
+        fields: &[(&str, &FieldType)],
+        expr: &str,
+        depth: usize,
+        visited: &mut Vec<String>,
+    ) -> Option<String> {
+        let mut needs_cast = false;
+        let entries: Vec<_> = fields
+            .iter()
+            .map(|(name, field_type)| {
+                let field_expr = format!("{expr}.`{name}`");
+                let value = match self.cast_at(field_type, &field_expr, depth, visited) {
+                    Some(cast) => {
+                        needs_cast = true;
+                        cast
+                    }
+                    None => field_expr,
+                };
+                format!("\"{}\": {}", name, value)
+            })
+            .collect();
+        needs_cast.then(|| {
+            format!(
+                "(IF type::is::object({expr}) THEN {{ {} }} ELSE {expr} END)",
+                entries.join(", ")
+            )
+        })
+    }
+
+    /// Rebuild the array at `expr` with its items cast, when any of them needs a cast
+    fn tuple_cast(
+        &self,
+        types: &[&FieldType],
+        expr: &str,
+        depth: usize,
+        visited: &mut Vec<String>,
+    ) -> Option<String> {
+        let mut needs_cast = false;
+        let items: Vec<_> = types
+            .iter()
+            .enumerate()
+            .map(|(i, field_type)| {
+                let item_expr = format!("{expr}[{i}]");
+                match self.cast_at(field_type, &item_expr, depth, visited) {
+                    Some(cast) => {
+                        needs_cast = true;
+                        cast
+                    }
+                    None => item_expr,
+                }
+            })
+            .collect();
+        needs_cast.then(|| {
+            format!(
+                "(IF type::is::array({expr}) THEN [{}] ELSE {expr} END)",
+                items.join(", ")
+            )
+        })
+    }
+
+    /// Enum values are untagged, the variant is told apart by the shape of its JSON.
+    /// Variants of the same shape are cast like the first of them.
+    fn enum_cast(
+        &self,
+        tagged_union: &TaggedUnion,
+        expr: &str,
+        depth: usize,
+        visited: &mut Vec<String>,
+    ) -> Option<String> {
+        let unit_names: Vec<_> = tagged_union
+            .variants
+            .iter()
+            .filter(|variant| variant.data.is_none())
+            .map(|variant| format!("'{}'", variant.name))
+            .collect();
+
+        let mut branches = Vec::new();
+        for variant in &tagged_union.variants {
+            let (cast, shape) = match &variant.data {
+                None => continue,
+                Some(VariantData::InlineStruct(enum_struct)) => {
+                    let struct_config = self
+                        .objects
+                        .get(&enum_struct.struct_name)
+                        .unwrap_or(enum_struct);
+                    let names = struct_config.fields.iter().map(|f| f.field_name.as_str());
+                    (
+                        self.struct_cast(struct_config, expr, depth, visited),
+                        Some(object_shape(names, expr)),
+                    )
+                }
+                Some(VariantData::DataStructureRef(field_type)) => (
+                    self.cast_at(field_type, expr, depth, visited),
+                    self.shape(field_type, expr),
+                ),
+            };
+            let (Some(cast), Some(mut shape)) = (cast, shape) else {
+                continue;
+            };
+            if shape.starts_with("type::is::string") && !unit_names.is_empty() {
+                shape = format!("{shape} AND {expr} NOTINSIDE [{}]", unit_names.join(", "));
+            }
+            branches.push(format!("IF {shape} THEN {cast}"));
+        }
+
+        (!branches.is_empty()).then(|| format!("({} ELSE {expr} END)", branches.join(" ELSE ")))
+    }
+
+    /// Condition matching the JSON of `field_type` at `expr`, for the types that are cast
+    fn shape(&self, field_type: &FieldType, expr: &str) -> Option<String> {
+        match field_type {
+            FieldType::Option(inner) => self.shape(inner, expr),
+            FieldType::EvenframeRecordId
+            | FieldType::RecordLink(_)
+            | FieldType::DateTime
+            | FieldType::NaiveDate
+            | FieldType::Uuid
+            | FieldType::EvenframeDuration
+            | FieldType::Duration
+            | FieldType::Bytes => Some(format!("type::is::string({expr})")),
+            FieldType::Decimal => Some(format!("type::is::number({expr})")),
+            // Points are generated as `(x, y)`, other geometries as GeoJSON objects
+            FieldType::Geometry(_) => Some(format!(
+                "(type::is::array({expr}) OR {})",
+                object_shape(["coordinates", "type"].into_iter(), expr)
+            )),
+            FieldType::Vec(_)
+            | FieldType::HashSet(_)
+            | FieldType::BTreeSet(_)
+            | FieldType::Tuple(_) => Some(format!("type::is::array({expr})")),
+            FieldType::HashMap(..) | FieldType::BTreeMap(..) => {
+                Some(format!("type::is::object({expr})"))
+            }
+            FieldType::Struct(fields) => Some(object_shape(
+                fields.iter().map(|(name, _)| name.as_str()),
+                expr,
+            )),
+            FieldType::Other(_) | FieldType::Generic(..) => {
+                let type_name = field_type.to_string();
+                if self
+                    .tables
+                    .values()
+                    .any(|table| table.table_name == type_name)
+                {
+                    return Some(format!("type::is::string({expr})"));
+                }
+                let struct_config = self
+                    .objects
+                    .get(&type_name)
+                    .or_else(|| self.objects.get(&type_name.to_case(Case::Snake)))?;
+                if let Some(inner_field) = struct_config.newtype_field() {
+                    return self.shape(&inner_field.field_type, expr);
+                }
+                Some(match struct_config.kind {
+                    StructKind::Tuple => format!("type::is::array({expr})"),
+                    _ => object_shape(
+                        struct_config.fields.iter().map(|f| f.field_name.as_str()),
+                        expr,
+                    ),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Condition matching an object with exactly the fields `names`
+fn object_shape<'a>(names: impl Iterator<Item = &'a str>, expr: &str) -> String {
+    let mut names: Vec<_> = names.map(|name| format!("\"{}\"", name)).collect();
+    // Object keys are sorted
+    names.sort();
+    format!(
+        "type::is::object({expr}) AND object::keys({expr}) = [{}]",
+        names.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        schemasync::config::SchemasyncConfig,
+        types::{GeometryKind, StructField, Variant},
+    };
+
+    #[test]
+    fn test_json_cast() {
+        let (tables, objects, enums) = (HashMap::new(), HashMap::new(), HashMap::new());
+        let casts = JsonCasts {
+            tables: &tables,
+            objects: &objects,
+            enums: &enums,
+        };
+        let link = FieldType::RecordLink(Box::new(FieldType::Other("User".to_string())));
+        assert_eq!(casts.cast(&link, "`author`").unwrap(), "<record> `author`");
+        assert_eq!(
+            casts
+                .cast(&FieldType::Option(Box::new(FieldType::DateTime)), "`at`")
+                .unwrap(),
+            "(IF `at` = NONE OR `at` = NULL THEN NONE ELSE <datetime> `at` END)"
+        );
+        assert_eq!(
+            casts
+                .cast(&FieldType::Vec(Box::new(link)), "`tags`")
+                .unwrap(),
+            "(IF type::is::array(`tags`) THEN array::map(`tags`, |$item_0| <record> $item_0) ELSE `tags` END)"
+        );
+        assert_eq!(
+            casts
+                .cast(&FieldType::Geometry(GeometryKind::Polygon), "`area`")
+                .unwrap(),
+            "<geometry<polygon>> `area`"
+        );
+        assert_eq!(casts.cast(&FieldType::String, "`name`"), None);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_insert_geometry() {
+        let field = |name: &str, kind| StructField {
+            field_name: name.to_string(),
+            field_type: FieldType::Geometry(kind),
+            ..Default::default()
+        };
+        let place = TableConfig {
+            table_name: "place".to_string(),
+            struct_config: StructConfig::for_testing(
+                "Place",
+                vec![
+                    field("location", GeometryKind::Point),
+                    field("area", GeometryKind::Polygon),
+                ],
+            ),
+            relation: None,
+            permissions: None,
+            mock_generation_config: None,
+        };
+        let mut config = SchemasyncConfig::for_testing();
+        config.mock_gen_config.default_record_count = 5;
+        config.mock_gen_config.default_batch_size = 2;
+        let db = surrealdb::engine::any::connect("mem://").await.unwrap();
+        db.use_ns("test").use_db("test").await.unwrap();
+        db.query(
+            "DEFINE TABLE place SCHEMAFULL;
+            DEFINE FIELD location ON place TYPE geometry<point>;
+            DEFINE FIELD area ON place TYPE geometry<polygon>;",
+        )
+        .await
+        .unwrap()
+        .check()
+        .unwrap();
+        let mockmaker = Mockmaker::new(
+            db.clone(),
+            HashMap::from([("place".to_string(), place.clone())]),
+            HashMap::new(),
+            HashMap::new(),
+            config,
+        );
+
+        mockmaker.bulk_insert("place", &place).await.unwrap();
+
+        let geometries: Vec<bool> = db
+            .query("SELECT VALUE type::is::point(location) AND type::is::polygon(area) FROM place")
+            .await
+            .unwrap()
+            .take(0)
+            .unwrap();
+        assert_eq!(geometries, vec![true; 5]);
+    }
+
+    #[test]
+    fn test_nested_json_cast() {
+        let field = |name: &str, field_type| StructField {
+            field_name: name.to_string(),
+            field_type,
+            ..Default::default()
+        };
+        let shipment = StructConfig::for_testing(
+            "Shipment",
+            vec![
+                field("carrier", FieldType::String),
+                field("shipped_at", FieldType::DateTime),
+            ],
+        );
+        let objects = HashMap::from([("Shipment".to_string(), shipment)]);
+        let enums = HashMap::from([(
+            "Delivery".to_string(),
+            TaggedUnion {
+                enum_name: "Delivery".to_string(),
+                variants: vec![
+                    Variant {
+                        name: "Pickup".to_string(),
+                        data: None,
+                    },
+                    Variant {
+                        name: "Shipped".to_string(),
+                        data: Some(VariantData::DataStructureRef(FieldType::Other(
+                            "Shipment".to_string(),
+                        ))),
+                    },
+                ],
+                generics: Vec::new(),
+            },
+        )]);
+        let tables = HashMap::new();
+        let casts = JsonCasts {
+            tables: &tables,
+            objects: &objects,
+            enums: &enums,
+        };
+
+        let object_cast = "(IF type::is::object(`shipment`) THEN { \"carrier\": `shipment`.`carrier`, \"shipped_at\": <datetime> `shipment`.`shipped_at` } ELSE `shipment` END)";
+        assert_eq!(
+            casts
+                .cast(&FieldType::Other("Shipment".to_string()), "`shipment`")
+                .unwrap(),
+            object_cast
+        );
+        assert_eq!(
+            casts
+                .cast(&FieldType::Other("Delivery".to_string()), "`shipment`")
+                .unwrap(),
+            format!(
+                "(IF type::is::object(`shipment`) AND object::keys(`shipment`) = [\"carrier\", \"shipped_at\"] THEN {object_cast} ELSE `shipment` END)"
+            )
+        );
+        assert_eq!(
+            casts
+                .cast(
+                    &FieldType::HashMap(Box::new(FieldType::String), Box::new(FieldType::Uuid)),
+                    "`keys`"
+                )
+                .unwrap(),
+            "(IF type::is::object(`keys`) THEN object::from_entries(array::map(object::entries(`keys`), |$entry_0| [$entry_0[0], <uuid> $entry_0[1]])) ELSE `keys` END)"
+        );
+        assert_eq!(
+            casts
+                .cast(
+                    &FieldType::Tuple(vec![FieldType::String, FieldType::Decimal]),
+                    "`price`"
+                )
+                .unwrap(),
+            "(IF type::is::array(`price`) THEN [`price`[0], <decimal> `price`[1]] ELSE `price` END)"
+        );
+    }
+}
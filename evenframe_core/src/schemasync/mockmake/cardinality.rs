@@ -0,0 +1,567 @@
+//! Relationship cardinality for mock data.
+//!
+//! Without a rule, every generated reference points at a uniformly random record of the
+//! referenced table. A [`Cardinality`] rule on a reference field (or on `in`/`out` of a
+//! relation table) shapes the relationship instead:
+//!
+//! - single references: each referenced record is pointed at by `min..=max` records of
+//!   the table ("each order has 1-5 line items", "every user has exactly one profile"),
+//!   and only a `probability` share of them is referenced at all ("20% of users have no
+//!   posts")
+//! - collections of references: each record holds `min..=max` references, and none with
+//!   probability `1 - probability`
+//!
+//! The `distribution` decides which records are the popular ones.
+
+use crate::{
+    mockmake::{Mockmaker, rng::MockRng},
+    schemasync::table::TableConfig,
+    types::FieldType,
+};
+use convert_case::{Case, Casing};
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use rand::{
+    Rng,
+    distr::{Distribution, weighted::WeightedIndex},
+    seq::SliceRandom,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use syn::{Expr, ExprLit, Lit, Member, spanned::Spanned};
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Cardinality {
+    /// The reference field, `in` or `out` for relation tables
+    pub field_name: String,
+    pub min: usize,
+    pub max: usize,
+    /// Share of the referenced records that are referenced at all (single references),
+    /// or of the records holding any references (collections)
+    #[serde(default = "default_probability")]
+    pub probability: f64,
+    #[serde(default)]
+    pub distribution: ReferenceDistribution,
+}
+
+fn default_probability() -> f64 {
+    1.0
+}
+
+/// How references are spread over the referenced records
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub enum ReferenceDistribution {
+    /// Every record is equally likely to be referenced
+    #[default]
+    Uniform,
+    /// The record of popularity rank `r` is referenced with weight `1 / r^exponent`, so a
+    /// few records get most of the references
+    PowerLaw(f64),
+}
+
+impl Cardinality {
+    /// Popularity weight of each of `count` referenced records, in a random order
+    fn popularity(&self, count: usize, rng: &mut MockRng) -> Vec<f64> {
+        match self.distribution {
+            ReferenceDistribution::Uniform => vec![1.0; count],
+            ReferenceDistribution::PowerLaw(exponent) => {
+                let mut ranks: Vec<usize> = (1..=count).collect();
+                ranks.shuffle(rng);
+                ranks
+                    .into_iter()
+                    .map(|rank| 1.0 / (rank as f64).powf(exponent))
+                    .collect()
+            }
+        }
+    }
+
+    fn probability(&self) -> f64 {
+        self.probability.clamp(0.0, 1.0)
+    }
+
+    /// Plan which of `ids` each of `records` records references. With an `optional`
+    /// reference the records the rule can't place are left empty, otherwise they are
+    /// spread over the referenced records past `max`.
+    pub fn plan(
+        &self,
+        ids: &[String],
+        records: usize,
+        optional: bool,
+        rng: &mut MockRng,
+    ) -> ReferencePlan {
+        let max = self.max.max(self.min);
+        let weights = self.popularity(ids.len(), rng);
+        let referenced: Vec<usize> = (0..ids.len())
+            .filter(|_| rng.random_bool(self.probability()))
+            .collect();
+        let mut counts = vec![0; ids.len()];
+
+        // Minimums first, most popular records first when there aren't enough records
+        let mut by_popularity = referenced.clone();
+        by_popularity.sort_by(|a, b| weights[*b].total_cmp(&weights[*a]));
+        let mut assigned = 0;
+        for &index in &by_popularity {
+            let count = self.min.min(records - assigned);
+            counts[index] = count;
+            assigned += count;
+        }
+        if by_popularity.len() * self.min > records {
+            tracing::warn!(
+                field = %self.field_name,
+                records = records,
+                "Not enough records to reference every record at least {} times",
+                self.min
+            );
+        }
+
+        // Then the rest by popularity, up to the maximum. Records the minimums already
+        // filled up can't be picked.
+        let mut picker = WeightedIndex::new(referenced.iter().map(|&index| {
+            if counts[index] >= max {
+                0.0
+            } else {
+                weights[index]
+            }
+        }))
+        .ok();
+        while assigned < records {
+            let Some(dist) = picker.as_mut() else { break };
+            let slot = dist.sample(rng);
+            let index = referenced[slot];
+            counts[index] += 1;
+            assigned += 1;
+            if counts[index] >= max && dist.update_weights(&[(slot, &0.0)]).is_err() {
+                // Every referenced record is full
+                picker = None;
+            }
+        }
+
+        let mut targets: Vec<Option<String>> = counts
+            .iter()
+            .enumerate()
+            .flat_map(|(index, &count)| std::iter::repeat_n(Some(ids[index].clone()), count))
+            .collect();
+
+        if assigned < records {
+            if optional {
+                targets.resize(records, None);
+            } else {
+                tracing::warn!(
+                    field = %self.field_name,
+                    records = records,
+                    placed = assigned,
+                    "Cardinality rule can't place every record, exceeding its maximum of {}",
+                    max
+                );
+                // Past the maximum, still by popularity
+                let pool: Vec<usize> = if referenced.is_empty() {
+                    (0..ids.len()).collect()
+                } else {
+                    referenced
+                };
+                if let Ok(dist) = WeightedIndex::new(pool.iter().map(|&index| weights[index])) {
+                    while targets.len() < records {
+                        targets.push(Some(ids[pool[dist.sample(rng)]].clone()));
+                    }
+                }
+            }
+        }
+        targets.shuffle(rng);
+
+        ReferencePlan {
+            rule: self.clone(),
+            ids: ids.to_vec(),
+            popularity: WeightedIndex::new(&weights).ok(),
+            targets,
+        }
+    }
+
+    /// Plan the references of a collection of references to `ids`
+    pub fn collection_plan(&self, ids: &[String], rng: &mut MockRng) -> ReferencePlan {
+        let weights = self.popularity(ids.len(), rng);
+        ReferencePlan {
+            rule: self.clone(),
+            ids: ids.to_vec(),
+            popularity: WeightedIndex::new(&weights).ok(),
+            targets: Vec::new(),
+        }
+    }
+}
+
+impl TryFrom<&Expr> for Cardinality {
+    type Error = syn::Error;
+
+    /// Parse `Cardinality { field_name: "author", min: 0, max: 10, probability: 0.8,
+    /// distribution: PowerLaw(1.5) }`, where `probability` and `distribution` are optional
+    fn try_from(expr: &Expr) -> Result<Self, Self::Error> {
+        let Expr::Struct(expr_struct) = expr else {
+            return Err(syn::Error::new(
+                expr.span(),
+                "Expected a cardinality rule.\n\nExample: Cardinality { field_name: \"order\", min: 1, max: 5 }",
+            ));
+        };
+
+        let mut field_name = None;
+        let mut min = None;
+        let mut max = None;
+        let mut probability = default_probability();
+        let mut distribution = ReferenceDistribution::default();
+        for field in &expr_struct.fields {
+            let Member::Named(name) = &field.member else {
+                return Err(syn::Error::new(field.span(), "Expected a named field"));
+            };
+            match name.to_string().as_str() {
+                "field_name" => match &field.expr {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(lit), ..
+                    }) => field_name = Some(lit.value()),
+                    other => {
+                        return Err(syn::Error::new(
+                            other.span(),
+                            "'field_name' must be a string literal",
+                        ));
+                    }
+                },
+                "min" => min = Some(parse_usize(&field.expr)?),
+                "max" => max = Some(parse_usize(&field.expr)?),
+                "probability" => probability = parse_f64(&field.expr)?,
+                "distribution" => distribution = ReferenceDistribution::try_from(&field.expr)?,
+                other => {
+                    return Err(syn::Error::new(
+                        name.span(),
+                        format!(
+                            "Unknown cardinality parameter '{}'. Valid parameters are: field_name, min, max, probability, distribution",
+                            other
+                        ),
+                    ));
+                }
+            }
+        }
+
+        let missing = |name: &str| {
+            syn::Error::new(
+                expr.span(),
+                format!("Cardinality rule is missing '{}'", name),
+            )
+        };
+        let min = min.ok_or_else(|| missing("min"))?;
+        let max = max.ok_or_else(|| missing("max"))?;
+        if max < min {
+            return Err(syn::Error::new(
+                expr.span(),
+                format!("Cardinality 'max' ({max}) is smaller than 'min' ({min})"),
+            ));
+        }
+        if !(0.0..=1.0).contains(&probability) {
+            return Err(syn::Error::new(
+                expr.span(),
+                format!("Cardinality 'probability' must be between 0 and 1, got {probability}"),
+            ));
+        }
+
+        Ok(Self {
+            field_name: field_name.ok_or_else(|| missing("field_name"))?,
+            min,
+            max,
+            probability,
+            distribution,
+        })
+    }
+}
+
+impl TryFrom<&Expr> for ReferenceDistribution {
+    type Error = syn::Error;
+
+    fn try_from(expr: &Expr) -> Result<Self, Self::Error> {
+        let unknown = || {
+            syn::Error::new(
+                expr.span(),
+                "Expected a distribution: Uniform or PowerLaw(exponent)",
+            )
+        };
+        match expr {
+            Expr::Path(path) if path.path.is_ident("Uniform") => Ok(Self::Uniform),
+            Expr::Call(call) => match (&*call.func, call.args.len()) {
+                (Expr::Path(path), 1) if path.path.is_ident("PowerLaw") => {
+                    Ok(Self::PowerLaw(parse_f64(&call.args[0])?))
+                }
+                _ => Err(unknown()),
+            },
+            _ => Err(unknown()),
+        }
+    }
+}
+
+fn parse_usize(expr: &Expr) -> syn::Result<usize> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        }) => lit.base10_parse(),
+        other => Err(syn::Error::new(
+            other.span(),
+            "Expected a non-negative integer literal",
+        )),
+    }
+}
+
+fn parse_f64(expr: &Expr) -> syn::Result<f64> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Float(lit),
+            ..
+        }) => lit.base10_parse(),
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        }) => lit.base10_parse(),
+        other => Err(syn::Error::new(other.span(), "Expected a number literal")),
+    }
+}
+
+impl ToTokens for Cardinality {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let field_name = &self.field_name;
+        let min = self.min;
+        let max = self.max;
+        let probability = self.probability;
+        let distribution = &self.distribution;
+        tokens.extend(quote! {
+            ::evenframe::schemasync::mockmake::cardinality::Cardinality {
+                field_name: #field_name.to_string(),
+                min: #min,
+                max: #max,
+                probability: #probability,
+                distribution: #distribution,
+            }
+        });
+    }
+}
+
+impl ToTokens for ReferenceDistribution {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(match self {
+            Self::Uniform => {
+                quote! { ::evenframe::schemasync::mockmake::cardinality::ReferenceDistribution::Uniform }
+            }
+            Self::PowerLaw(exponent) => quote! {
+                ::evenframe::schemasync::mockmake::cardinality::ReferenceDistribution::PowerLaw(#exponent)
+            },
+        });
+    }
+}
+
+/// The references of one field, planned from its [`Cardinality`] rule
+#[derive(Debug, Clone)]
+pub struct ReferencePlan {
+    rule: Cardinality,
+    ids: Vec<String>,
+    popularity: Option<WeightedIndex<f64>>,
+    /// Target of each record of a single reference, by record index
+    targets: Vec<Option<String>>,
+}
+
+impl ReferencePlan {
+    /// Target of the record at `index`, `Some(None)` when it references nothing
+    pub fn target(&self, index: usize) -> Option<Option<&str>> {
+        self.targets.get(index).map(Option::as_deref)
+    }
+
+    /// Number of references in a collection
+    pub fn item_count(&self, rng: &mut MockRng) -> usize {
+        if !rng.random_bool(self.rule.probability()) {
+            return 0;
+        }
+        rng.random_range(self.rule.min..=self.rule.max.max(self.rule.min))
+    }
+
+    /// One reference of a collection, by popularity
+    pub fn pick(&self, rng: &mut MockRng) -> Option<&str> {
+        let index = match &self.popularity {
+            Some(popularity) => popularity.sample(rng),
+            None if !self.ids.is_empty() => rng.random_range(0..self.ids.len()),
+            None => return None,
+        };
+        Some(&self.ids[index])
+    }
+}
+
+/// What a field with a cardinality rule references
+enum ReferenceShape {
+    Single { optional: bool },
+    Collection,
+}
+
+impl Mockmaker {
+    /// Plan the references of every field with a cardinality rule. Needs the ids of the
+    /// referenced tables, so it runs after id generation.
+    pub(super) fn plan_references(&mut self) {
+        let mut plans = HashMap::new();
+        for (table_name, table_config) in &self.tables {
+            let Some(mock_config) = &table_config.mock_generation_config else {
+                continue;
+            };
+            let records = self.record_count(table_config);
+            for rule in &mock_config.cardinality {
+                let Some((target_table, shape)) =
+                    self.reference_target(table_config, &rule.field_name)
+                else {
+                    tracing::warn!(
+                        table = %table_name,
+                        field = %rule.field_name,
+                        "Cardinality rule on a field that doesn't reference a table, ignoring it"
+                    );
+                    continue;
+                };
+                let Some(ids) = self.id_map.get(&target_table).filter(|ids| !ids.is_empty()) else {
+                    continue;
+                };
+
+                let mut rng = self.rng_for(table_name, &format!("cardinality:{}", rule.field_name));
+                let plan = match shape {
+                    ReferenceShape::Single { optional } => {
+                        rule.plan(ids, records, optional, &mut rng)
+                    }
+                    ReferenceShape::Collection => rule.collection_plan(ids, &mut rng),
+                };
+                tracing::debug!(
+                    table = %table_name,
+                    field = %rule.field_name,
+                    target_table = %target_table,
+                    "Planned references from cardinality rule"
+                );
+                plans.insert((table_name.clone(), rule.field_name.clone()), plan);
+            }
+        }
+        self.reference_plans = plans;
+    }
+
+    /// The table `field_name` references and how
+    fn reference_target(
+        &self,
+        table_config: &TableConfig,
+        field_name: &str,
+    ) -> Option<(String, ReferenceShape)> {
+        if let Some(relation) = &table_config.relation {
+            match field_name {
+                "in" => {
+                    return Some((
+                        relation.from.clone(),
+                        ReferenceShape::Single { optional: false },
+                    ));
+                }
+                "out" => {
+                    return Some((
+                        relation.to.clone(),
+                        ReferenceShape::Single { optional: false },
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        let field = table_config
+            .struct_config
+            .fields
+            .iter()
+            .find(|field| field.field_name == field_name)?;
+        let (field_type, shape) = match &field.field_type {
+            FieldType::Option(inner) => (inner.as_ref(), ReferenceShape::Single { optional: true }),
+            FieldType::Vec(inner) | FieldType::HashSet(inner) | FieldType::BTreeSet(inner) => {
+                (inner.as_ref(), ReferenceShape::Collection)
+            }
+            field_type => (field_type, ReferenceShape::Single { optional: false }),
+        };
+        let type_name = match field_type {
+            FieldType::RecordLink(inner) => match inner.as_ref() {
+                FieldType::Other(type_name) => type_name,
+                _ => return None,
+            },
+            FieldType::Other(type_name) => type_name,
+            _ => return None,
+        };
+        self.tables
+            .get(&type_name.to_case(Case::Snake))
+            .or_else(|| {
+                self.tables
+                    .values()
+                    .find(|table| &table.table_name == type_name)
+            })
+            .map(|table| (table.table_name.clone(), shape))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mockmake::rng::mock_rng;
+
+    fn ids(count: usize) -> Vec<String> {
+        (1..=count).map(|i| format!("user:{i}")).collect()
+    }
+
+    fn rule(min: usize, max: usize, probability: f64) -> Cardinality {
+        Cardinality {
+            field_name: "author".to_string(),
+            min,
+            max,
+            probability,
+            distribution: ReferenceDistribution::Uniform,
+        }
+    }
+
+    fn counts(plan: &ReferencePlan) -> HashMap<&str, usize> {
+        let mut counts = HashMap::new();
+        for target in plan.targets.iter().flatten() {
+            *counts.entry(target.as_str()).or_default() += 1;
+        }
+        counts
+    }
+
+    #[test]
+    fn test_plan_respects_bounds() {
+        let mut rng = mock_rng(Some(7), "test");
+        let plan = rule(1, 5, 1.0).plan(&ids(10), 30, false, &mut rng);
+        assert_eq!(plan.targets.len(), 30);
+        let counts = counts(&plan);
+        assert_eq!(counts.len(), 10);
+        assert!(counts.values().all(|&count| (1..=5).contains(&count)));
+
+        let plan = rule(1, 1, 1.0).plan(&ids(10), 10, false, &mut rng);
+        assert!(counts(&plan).values().all(|&count| count == 1));
+    }
+
+    #[test]
+    fn test_optional_references_stay_empty_past_the_rule() {
+        let mut rng = mock_rng(Some(7), "test");
+        let plan = rule(0, 2, 0.5).plan(&ids(10), 50, true, &mut rng);
+        assert_eq!(plan.targets.len(), 50);
+        assert!(plan.targets.iter().any(Option::is_none));
+        assert!(counts(&plan).values().all(|&count| count <= 2));
+    }
+
+    #[test]
+    fn test_minimums_reaching_the_maximum_are_full() {
+        let mut rng = mock_rng(Some(7), "test");
+        // More records than the 3 * 2 the rule can place
+        let plan = rule(2, 2, 1.0).plan(&ids(3), 10, true, &mut rng);
+        assert_eq!(plan.targets.len(), 10);
+        assert_eq!(plan.targets.iter().filter(|t| t.is_none()).count(), 4);
+        assert!(counts(&plan).values().all(|&count| count == 2));
+
+        let plan = rule(1, 2, 1.0).plan(&ids(3), 10, true, &mut rng);
+        assert_eq!(plan.targets.iter().filter(|t| t.is_none()).count(), 4);
+        assert!(counts(&plan).values().all(|&count| count == 2));
+    }
+
+    #[test]
+    fn test_parse_rule() {
+        let expr: Expr = syn::parse_quote! {
+            Cardinality { field_name: "author", min: 0, max: 10, probability: 0.8, distribution: PowerLaw(1.5) }
+        };
+        let rule = Cardinality::try_from(&expr).unwrap();
+        assert_eq!(rule.max, 10);
+        assert_eq!(rule.distribution, ReferenceDistribution::PowerLaw(1.5));
+
+        let expr: Expr = syn::parse_quote! { Cardinality { field_name: "author", min: 3, max: 1 } };
+        assert!(Cardinality::try_from(&expr).is_err());
+    }
+}
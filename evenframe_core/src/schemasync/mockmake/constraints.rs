@@ -0,0 +1,660 @@
+//! Value constraints derived from field validators.
+//!
+//! Without a `#[format]`, mock values only follow the field type, so validators like
+//! `MinLength(20)`, `Email`, `Between(1, 5)` or `MaxItems(3)` would be violated. The
+//! validators of a field are collected into bounds here, and the field value generator
+//! draws its values within them.
+
+use crate::{
+    error::{EvenframeError, Result},
+    format::Format,
+    mockmake::{Mockmaker, rng::MockRng},
+    validator::{
+        ArrayValidator, BigDecimalValidator, BigIntValidator, DateValidator, NumberValidator,
+        StringValidator, Validator,
+    },
+};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use rand::Rng;
+use std::ops::Range;
+use tracing::trace;
+
+const ALPHA_CHARS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const ALPHANUM_CHARS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+const DIGIT_CHARS: &str = "0123456789";
+const HEX_CHARS: &str = "0123456789abcdef";
+const BASE64_CHARS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Length of generated strings when only their content is constrained
+const DEFAULT_STRING_LENGTH: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LetterCase {
+    Lower,
+    Upper,
+    Capitalized,
+}
+
+/// A numeric bound and whether the bound itself is allowed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bound {
+    pub value: f64,
+    pub inclusive: bool,
+}
+
+/// Everything the validators of a field require from its value
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValueConstraints {
+    /// The only allowed string
+    pub literal: Option<String>,
+    /// Strings are generated from this format, e.g. for `Email` or `Uuid`
+    pub format: Option<Format>,
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+    pub prefix: Option<String>,
+    pub suffix: Option<String>,
+    pub includes: Vec<String>,
+    /// Characters the rest of the string is drawn from
+    pub charset: Option<&'static str>,
+    /// Generated string lengths are a multiple of this (base64 padding)
+    pub length_step: Option<usize>,
+    pub case: Option<LetterCase>,
+
+    pub minimum: Option<Bound>,
+    pub maximum: Option<Bound>,
+    pub integer: bool,
+    /// The value is stored in an unsigned type, so it may not be negative
+    pub unsigned: bool,
+    pub multiple_of: Option<f64>,
+
+    pub min_items: Option<usize>,
+    pub max_items: Option<usize>,
+
+    pub earliest: Option<DateTime<Utc>>,
+    pub latest: Option<DateTime<Utc>>,
+}
+
+impl ValueConstraints {
+    pub fn from_validators(validators: &[Validator]) -> Self {
+        let mut constraints = Self::default();
+        for validator in validators {
+            match validator {
+                Validator::StringValidator(sv) => constraints.add_string_validator(sv),
+                Validator::NumberValidator(nv) => constraints.add_number_validator(nv),
+                Validator::ArrayValidator(av) => match av {
+                    ArrayValidator::MinItems(count) => {
+                        constraints.min_items = constraints.min_items.max(Some(*count))
+                    }
+                    ArrayValidator::MaxItems(count) => {
+                        constraints.max_items = min(constraints.max_items, *count)
+                    }
+                    ArrayValidator::ItemsCount(count) => {
+                        constraints.min_items = Some(*count);
+                        constraints.max_items = Some(*count);
+                    }
+                },
+                Validator::DateValidator(dv) => constraints.add_date_validator(dv),
+                Validator::BigIntValidator(biv) => constraints.add_big_int_validator(biv),
+                Validator::BigDecimalValidator(bdv) => constraints.add_big_decimal_validator(bdv),
+                Validator::DurationValidator(dv) => {
+                    trace!("Duration validator {:?} not used for mock generation", dv);
+                }
+            }
+        }
+        constraints
+    }
+
+    fn add_string_validator(&mut self, validator: &StringValidator) {
+        match validator {
+            StringValidator::Email => self.format = Some(Format::Email),
+            StringValidator::Url | StringValidator::UrlParse => {
+                self.format = Some(Format::Url("example.com".to_string()))
+            }
+            StringValidator::Uuid
+            | StringValidator::UuidV1
+            | StringValidator::UuidV2
+            | StringValidator::UuidV3
+            | StringValidator::UuidV4
+            | StringValidator::UuidV5
+            | StringValidator::UuidV6
+            | StringValidator::UuidV7
+            | StringValidator::UuidV8 => self.format = Some(Format::Uuid),
+            StringValidator::Ip | StringValidator::IpV4 => self.format = Some(Format::IpAddress),
+            StringValidator::IpV6 => {
+                self.format = Some(Format::Custom(
+                    r"^[0-9a-f]{4}(:[0-9a-f]{4}){7}$".to_string(),
+                ))
+            }
+            StringValidator::Semver => self.format = Some(Format::Version),
+            StringValidator::CreditCard => self.format = Some(Format::CreditCardNumber),
+            StringValidator::Date
+            | StringValidator::DateIso
+            | StringValidator::DateParse
+            | StringValidator::DateIsoParse => self.format = Some(Format::Date),
+            StringValidator::RegexLiteral(format) => self.format = Some(format.clone()),
+            StringValidator::Literal(value) => self.literal = Some(value.clone()),
+            StringValidator::Json | StringValidator::JsonParse => {
+                self.literal = Some("{}".to_string())
+            }
+
+            StringValidator::Alpha => self.charset = Some(ALPHA_CHARS),
+            StringValidator::Alphanumeric => self.charset = Some(ALPHANUM_CHARS),
+            StringValidator::Digits
+            | StringValidator::Numeric
+            | StringValidator::NumericParse
+            | StringValidator::Integer
+            | StringValidator::IntegerParse
+            | StringValidator::DateEpoch
+            | StringValidator::DateEpochParse => self.charset = Some(DIGIT_CHARS),
+            StringValidator::Hex => self.charset = Some(HEX_CHARS),
+            StringValidator::Base64 | StringValidator::Base64Url => {
+                self.charset = Some(BASE64_CHARS);
+                self.length_step = Some(4);
+            }
+
+            StringValidator::MinLength(len) => self.min_length = self.min_length.max(Some(*len)),
+            StringValidator::MaxLength(len) => self.max_length = min(self.max_length, *len),
+            StringValidator::NonEmpty => self.min_length = self.min_length.max(Some(1)),
+            StringValidator::Length(len) => match len.trim().parse::<usize>() {
+                Ok(len) => {
+                    self.min_length = Some(len);
+                    self.max_length = Some(len);
+                }
+                Err(_) => trace!("Length '{}' is not a number, ignoring", len),
+            },
+            StringValidator::StartsWith(prefix) => self.prefix = Some(prefix.clone()),
+            StringValidator::EndsWith(suffix) => self.suffix = Some(suffix.clone()),
+            StringValidator::Includes(substring) => self.includes.push(substring.clone()),
+
+            StringValidator::Lower
+            | StringValidator::LowerPreformatted
+            | StringValidator::Lowercased
+            | StringValidator::Uncapitalized => self.case = Some(LetterCase::Lower),
+            StringValidator::Upper
+            | StringValidator::UpperPreformatted
+            | StringValidator::Uppercased => self.case = Some(LetterCase::Upper),
+            StringValidator::Capitalize
+            | StringValidator::CapitalizePreformatted
+            | StringValidator::Capitalized => self.case = Some(LetterCase::Capitalized),
+
+            // Generated strings have no surrounding whitespace and are normalized already
+            _ => trace!("String validator {:?} needs no mock constraint", validator),
+        }
+    }
+
+    fn add_number_validator(&mut self, validator: &NumberValidator) {
+        match validator {
+            NumberValidator::GreaterThan(value) => self.raise_minimum(value.0, false),
+            NumberValidator::GreaterThanOrEqualTo(value) => self.raise_minimum(value.0, true),
+            NumberValidator::LessThan(value) => self.lower_maximum(value.0, false),
+            NumberValidator::LessThanOrEqualTo(value) => self.lower_maximum(value.0, true),
+            NumberValidator::Between(start, end) => {
+                self.raise_minimum(start.0, true);
+                self.lower_maximum(end.0, true);
+            }
+            NumberValidator::Int => self.integer = true,
+            NumberValidator::Positive => self.raise_minimum(0.0, false),
+            NumberValidator::NonNegative => self.raise_minimum(0.0, true),
+            NumberValidator::Negative => self.lower_maximum(0.0, false),
+            NumberValidator::NonPositive => self.lower_maximum(0.0, true),
+            NumberValidator::MultipleOf(value) => self.multiple_of = Some(value.0),
+            NumberValidator::Uint8 => {
+                self.integer = true;
+                self.raise_minimum(0.0, true);
+                self.lower_maximum(255.0, true);
+            }
+            NumberValidator::NonNaN | NumberValidator::Finite => {}
+        }
+    }
+
+    fn add_big_int_validator(&mut self, validator: &BigIntValidator) {
+        self.integer = true;
+        match validator {
+            BigIntValidator::GreaterThanBigInt(value) => self.raise_minimum_str(value, false),
+            BigIntValidator::GreaterThanOrEqualToBigInt(value) => {
+                self.raise_minimum_str(value, true)
+            }
+            BigIntValidator::LessThanBigInt(value) => self.lower_maximum_str(value, false),
+            BigIntValidator::LessThanOrEqualToBigInt(value) => self.lower_maximum_str(value, true),
+            BigIntValidator::BetweenBigInt(start, end) => {
+                self.raise_minimum_str(start, true);
+                self.lower_maximum_str(end, true);
+            }
+            BigIntValidator::PositiveBigInt => self.raise_minimum(0.0, false),
+            BigIntValidator::NonNegativeBigInt => self.raise_minimum(0.0, true),
+            BigIntValidator::NegativeBigInt => self.lower_maximum(0.0, false),
+            BigIntValidator::NonPositiveBigInt => self.lower_maximum(0.0, true),
+        }
+    }
+
+    fn add_big_decimal_validator(&mut self, validator: &BigDecimalValidator) {
+        match validator {
+            BigDecimalValidator::GreaterThanBigDecimal(value) => {
+                self.raise_minimum_str(value, false)
+            }
+            BigDecimalValidator::GreaterThanOrEqualToBigDecimal(value) => {
+                self.raise_minimum_str(value, true)
+            }
+            BigDecimalValidator::LessThanBigDecimal(value) => self.lower_maximum_str(value, false),
+            BigDecimalValidator::LessThanOrEqualToBigDecimal(value) => {
+                self.lower_maximum_str(value, true)
+            }
+            BigDecimalValidator::BetweenBigDecimal(start, end) => {
+                self.raise_minimum_str(start, true);
+                self.lower_maximum_str(end, true);
+            }
+            BigDecimalValidator::PositiveBigDecimal => self.raise_minimum(0.0, false),
+            BigDecimalValidator::NonNegativeBigDecimal => self.raise_minimum(0.0, true),
+            BigDecimalValidator::NegativeBigDecimal => self.lower_maximum(0.0, false),
+            BigDecimalValidator::NonPositiveBigDecimal => self.lower_maximum(0.0, true),
+        }
+    }
+
+    fn add_date_validator(&mut self, validator: &DateValidator) {
+        // Exclusive bounds are moved by a second
+        let second = Duration::seconds(1);
+        match validator {
+            DateValidator::ValidDate => {}
+            DateValidator::GreaterThanDate(date) => self.raise_earliest(date, second),
+            DateValidator::GreaterThanOrEqualToDate(date) => {
+                self.raise_earliest(date, Duration::zero())
+            }
+            DateValidator::LessThanDate(date) => self.lower_latest(date, second),
+            DateValidator::LessThanOrEqualToDate(date) => self.lower_latest(date, Duration::zero()),
+            DateValidator::BetweenDate(start, end) => {
+                self.raise_earliest(start, Duration::zero());
+                self.lower_latest(end, Duration::zero());
+            }
+        }
+    }
+
+    fn raise_minimum(&mut self, value: f64, inclusive: bool) {
+        let tighter = match self.minimum {
+            Some(current) => value > current.value || (value == current.value && !inclusive),
+            None => true,
+        };
+        if tighter {
+            self.minimum = Some(Bound { value, inclusive });
+        }
+    }
+
+    fn lower_maximum(&mut self, value: f64, inclusive: bool) {
+        let tighter = match self.maximum {
+            Some(current) => value < current.value || (value == current.value && !inclusive),
+            None => true,
+        };
+        if tighter {
+            self.maximum = Some(Bound { value, inclusive });
+        }
+    }
+
+    fn raise_minimum_str(&mut self, value: &str, inclusive: bool) {
+        match value.trim().trim_end_matches('n').parse::<f64>() {
+            Ok(value) => self.raise_minimum(value, inclusive),
+            Err(_) => trace!("Bound '{}' is not a number, ignoring", value),
+        }
+    }
+
+    fn lower_maximum_str(&mut self, value: &str, inclusive: bool) {
+        match value.trim().trim_end_matches('n').parse::<f64>() {
+            Ok(value) => self.lower_maximum(value, inclusive),
+            Err(_) => trace!("Bound '{}' is not a number, ignoring", value),
+        }
+    }
+
+    fn raise_earliest(&mut self, date: &str, offset: Duration) {
+        if let Some(date) = parse_date(date).map(|date| date + offset) {
+            self.earliest = self.earliest.max(Some(date));
+        }
+    }
+
+    fn lower_latest(&mut self, date: &str, offset: Duration) {
+        if let Some(date) = parse_date(date).map(|date| date - offset) {
+            self.latest = Some(self.latest.map_or(date, |latest| latest.min(date)));
+        }
+    }
+
+    pub fn constrains_strings(&self) -> bool {
+        self.literal.is_some()
+            || self.format.is_some()
+            || self.min_length.is_some()
+            || self.max_length.is_some()
+            || self.prefix.is_some()
+            || self.suffix.is_some()
+            || !self.includes.is_empty()
+            || self.charset.is_some()
+            || self.case.is_some()
+    }
+
+    pub fn constrains_numbers(&self) -> bool {
+        self.minimum.is_some()
+            || self.maximum.is_some()
+            || self.integer
+            || self.multiple_of.is_some()
+    }
+
+    pub fn constrains_items(&self) -> bool {
+        self.min_items.is_some() || self.max_items.is_some()
+    }
+
+    pub fn constrains_dates(&self) -> bool {
+        self.earliest.is_some() || self.latest.is_some()
+    }
+
+    /// Characters every generated string needs for its prefix, suffix and substrings
+    fn fixed_length(&self) -> usize {
+        [self.prefix.as_deref(), self.suffix.as_deref()]
+            .into_iter()
+            .flatten()
+            .chain(self.includes.iter().map(String::as_str))
+            .map(|s| s.chars().count())
+            .sum()
+    }
+
+    /// Why no string can satisfy the constraints, e.g. a `MaxLength` shorter than the
+    /// required prefix and suffix
+    pub fn string_conflict(&self) -> Option<String> {
+        if self.literal.is_some() || self.format.is_some() {
+            return None;
+        }
+        let max_length = self.max_length?;
+        let fixed_len = self.fixed_length();
+        (max_length < fixed_len).then(|| {
+            format!(
+                "its maximum length of {max_length} is shorter than the {fixed_len} characters of its required prefix, suffix and substrings"
+            )
+        })
+    }
+
+    /// A string satisfying the constraints. Strings are never cut below their fixed
+    /// parts, see [`Self::string_conflict`].
+    pub fn string(&self, rng: &mut MockRng, now: DateTime<Utc>) -> String {
+        if let Some(literal) = &self.literal {
+            return literal.clone();
+        }
+        if let Some(format) = &self.format {
+            return format.generate_formatted_value_with(rng, now);
+        }
+
+        let prefix = self.prefix.as_deref().unwrap_or_default();
+        let suffix = self.suffix.as_deref().unwrap_or_default();
+        let fixed_len = self.fixed_length();
+
+        let min_len = self.min_length.unwrap_or(0).max(fixed_len);
+        let max_len = self
+            .max_length
+            .unwrap_or(min_len.max(DEFAULT_STRING_LENGTH) + DEFAULT_STRING_LENGTH)
+            .max(min_len);
+        let mut len = if self.min_length.is_none() && self.max_length.is_none() {
+            min_len.max(DEFAULT_STRING_LENGTH)
+        } else {
+            rng.random_range(min_len..=max_len)
+        };
+        if let Some(step) = self.length_step.filter(|step| *step > 1) {
+            len = len.div_ceil(step) * step;
+            if len > max_len && len >= step {
+                len -= step;
+            }
+        }
+
+        let charset: Vec<char> = self.charset.unwrap_or(ALPHANUM_CHARS).chars().collect();
+        let mut filler: String = (0..len.saturating_sub(fixed_len))
+            .map(|_| charset[rng.random_range(0..charset.len())])
+            .collect();
+        match self.case {
+            Some(LetterCase::Lower) => filler = filler.to_lowercase(),
+            Some(LetterCase::Upper) => filler = filler.to_uppercase(),
+            Some(LetterCase::Capitalized) => {
+                filler = filler.to_lowercase();
+                if prefix.is_empty() {
+                    let mut chars = filler.chars();
+                    if let Some(first) = chars.next() {
+                        filler = first.to_uppercase().chain(chars).collect();
+                    }
+                }
+            }
+            None => {}
+        }
+
+        format!("{}{}{}{}", prefix, self.includes.concat(), filler, suffix)
+    }
+
+    /// A number satisfying the constraints, drawn from `default` when unbounded
+    pub fn number<R: Rng + ?Sized>(&self, rng: &mut R, integer: bool, default: Range<f64>) -> f64 {
+        let integer = integer || self.integer;
+        let span = default.end - default.start;
+        let mut lo = self.minimum.map(|bound| bound.value);
+        let mut hi = self.maximum.map(|bound| bound.value);
+        match (lo, hi) {
+            (None, None) => {
+                lo = Some(default.start);
+                hi = Some(default.end);
+            }
+            (Some(min), None) => hi = Some(min + span),
+            (None, Some(max)) => lo = Some(max - span),
+            _ => {}
+        }
+        let (mut lo, mut hi) = (lo.unwrap_or_default(), hi.unwrap_or_default());
+        if self.unsigned {
+            lo = lo.max(0.0);
+        }
+
+        if integer {
+            lo = lo.ceil();
+            hi = hi.floor();
+            if self
+                .minimum
+                .is_some_and(|bound| !bound.inclusive && bound.value == lo)
+            {
+                lo += 1.0;
+            }
+            if self
+                .maximum
+                .is_some_and(|bound| !bound.inclusive && bound.value == hi)
+            {
+                hi -= 1.0;
+            }
+        }
+
+        if let Some(step) = self.multiple_of.filter(|step| *step > 0.0) {
+            let mut first = (lo / step).ceil();
+            let mut last = (hi / step).floor();
+            if self
+                .minimum
+                .is_some_and(|bound| !bound.inclusive && first * step == bound.value)
+            {
+                first += 1.0;
+            }
+            if self
+                .maximum
+                .is_some_and(|bound| !bound.inclusive && last * step == bound.value)
+            {
+                last -= 1.0;
+            }
+            if first > last {
+                return first * step;
+            }
+            return rng.random_range(first as i64..=last as i64) as f64 * step;
+        }
+
+        if lo >= hi {
+            return lo;
+        }
+        if integer {
+            return rng.random_range(lo as i64..=hi as i64) as f64;
+        }
+        let value = rng.random_range(lo..hi);
+        // Exclusive lower bounds are the only value of the half open range to avoid
+        if self.minimum.is_some_and(|bound| !bound.inclusive) && value == lo {
+            (lo + hi) / 2.0
+        } else {
+            value
+        }
+    }
+
+    /// Whether `value` satisfies the numeric constraints
+    pub fn admits(&self, value: f64) -> bool {
+        let above = self
+            .minimum
+            .is_none_or(|bound| value > bound.value || (bound.inclusive && value == bound.value));
+        let below = self
+            .maximum
+            .is_none_or(|bound| value < bound.value || (bound.inclusive && value == bound.value));
+        let integral = !self.integer || value.fract() == 0.0;
+        let multiple = self
+            .multiple_of
+            .filter(|step| *step > 0.0)
+            .is_none_or(|step| ((value / step).round() * step - value).abs() < 1e-9);
+        above && below && integral && multiple
+    }
+
+    /// Number of array items, drawn from `default` when unbounded
+    pub fn item_count(&self, rng: &mut MockRng, default: Range<usize>) -> usize {
+        let lo = match (self.min_items, self.max_items) {
+            (Some(min_items), _) => min_items,
+            (None, Some(max_items)) => default.start.min(max_items),
+            (None, None) => default.start,
+        };
+        let hi = self
+            .max_items
+            .unwrap_or_else(|| default.end.saturating_sub(1))
+            .max(lo);
+        rng.random_range(lo..=hi)
+    }
+
+    /// A datetime within the date bounds, `None` when there are none
+    pub fn datetime(&self, rng: &mut MockRng) -> Option<DateTime<Utc>> {
+        let year = Duration::days(365);
+        let (earliest, latest) = match (self.earliest, self.latest) {
+            (None, None) => return None,
+            (Some(earliest), None) => (earliest, earliest + year),
+            (None, Some(latest)) => (latest - year, latest),
+            (Some(earliest), Some(latest)) => (earliest, latest.max(earliest)),
+        };
+        let seconds = (latest - earliest).num_seconds();
+        Some(earliest + Duration::seconds(rng.random_range(0..=seconds)))
+    }
+}
+
+fn min(current: Option<usize>, value: usize) -> Option<usize> {
+    Some(current.map_or(value, |current| current.min(value)))
+}
+
+/// Parse an RFC 3339 datetime or a `YYYY-MM-DD` date
+fn parse_date(date: &str) -> Option<DateTime<Utc>> {
+    let date = date.trim();
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(date) {
+        return Some(datetime.with_timezone(&Utc));
+    }
+    match NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+        Ok(date) => Some(date.and_hms_opt(0, 0, 0)?.and_utc()),
+        Err(_) => {
+            trace!("Date bound '{}' could not be parsed, ignoring", date);
+            None
+        }
+    }
+}
+
+impl Mockmaker {
+    /// Fail when the validators of a string field can't all be satisfied
+    pub(super) fn check_value_constraints(&self) -> Result<()> {
+        let tables = self
+            .tables
+            .iter()
+            .map(|(name, table)| (name, &table.struct_config.fields));
+        let objects = self
+            .objects
+            .iter()
+            .map(|(name, object)| (name, &object.fields));
+        for (owner, fields) in tables.chain(objects) {
+            for field in fields.iter().filter(|field| !field.validators.is_empty()) {
+                let constraints = ValueConstraints::from_validators(&field.validators);
+                if let Some(conflict) = constraints.string_conflict() {
+                    return Err(EvenframeError::validation(format!(
+                        "Cannot generate mock values for '{owner}.{}': {conflict}",
+                        field.field_name
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mockmake::rng::mock_rng;
+    use ordered_float::OrderedFloat;
+
+    #[test]
+    fn test_generated_values_satisfy_validators() {
+        let mut rng = mock_rng(Some(7), "constraints");
+        let now = Utc::now();
+
+        let constraints = ValueConstraints::from_validators(&[
+            StringValidator::MinLength(20).into(),
+            StringValidator::StartsWith("ord_".to_string()).into(),
+            StringValidator::Lowercased.into(),
+        ]);
+        for _ in 0..50 {
+            let value = constraints.string(&mut rng, now);
+            assert!(value.chars().count() >= 20, "{value} is too short");
+            assert!(value.starts_with("ord_"));
+            assert_eq!(value, value.to_lowercase());
+        }
+
+        let constraints = ValueConstraints::from_validators(&[StringValidator::Email.into()]);
+        assert!(constraints.string(&mut rng, now).contains('@'));
+
+        let constraints = ValueConstraints::from_validators(&[NumberValidator::Between(
+            OrderedFloat(1.0),
+            OrderedFloat(5.0),
+        )
+        .into()]);
+        for _ in 0..50 {
+            let value = constraints.number(&mut rng, true, 0.0..100.0);
+            assert!((1.0..=5.0).contains(&value), "{value} is out of range");
+        }
+
+        let constraints = ValueConstraints::from_validators(&[
+            NumberValidator::Positive.into(),
+            NumberValidator::MultipleOf(OrderedFloat(0.25)).into(),
+        ]);
+        for _ in 0..50 {
+            let value = constraints.number(&mut rng, false, 0.0..100.0);
+            assert!(value > 0.0 && (value / 0.25).fract() == 0.0, "{value}");
+        }
+
+        let mut constraints =
+            ValueConstraints::from_validators(&[
+                NumberValidator::LessThan(OrderedFloat(20.0)).into()
+            ]);
+        constraints.unsigned = true;
+        for _ in 0..50 {
+            let value = constraints.number(&mut rng, true, 0.0..100.0);
+            assert!((0.0..20.0).contains(&value), "{value} is out of range");
+        }
+
+        let constraints = ValueConstraints::from_validators(&[ArrayValidator::MaxItems(3).into()]);
+        for _ in 0..50 {
+            assert!(constraints.item_count(&mut rng, 2..10) <= 3);
+        }
+    }
+
+    #[test]
+    fn test_string_conflict() {
+        let constraints = ValueConstraints::from_validators(&[
+            StringValidator::StartsWith("order_".to_string()).into(),
+            StringValidator::EndsWith("_v1".to_string()).into(),
+            StringValidator::MaxLength(6).into(),
+        ]);
+        assert!(constraints.string_conflict().is_some());
+
+        let constraints = ValueConstraints::from_validators(&[
+            StringValidator::StartsWith("order_".to_string()).into(),
+            StringValidator::MaxLength(12).into(),
+        ]);
+        assert_eq!(constraints.string_conflict(), None);
+    }
+}
@@ -1,10 +1,13 @@
 use crate::error::EvenframeError;
 use crate::format::Format;
-use crate::mockmake::{Mockmaker, field_value::FieldValueGenerator};
+use crate::mockmake::{
+    Mockmaker, custom, field_value::FieldValueGenerator, geometry::random_geometry, locale::Locale,
+    rng::MockRng,
+};
 use crate::types::{FieldType, StructField};
 use bon::Builder;
 use chrono::{DateTime, Duration, NaiveDate, Utc};
-use rand::Rng;
+use rand::{Rng, seq::IndexedRandom};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use try_from_expr::TryFromExpr;
@@ -116,6 +119,25 @@ impl Mockmaker {
             // Process coordinated values for this group
             for index in 0..n {
                 for coordination_pair in &coordination_group.coordination_pairs {
+                    // Seeded per pair and record, like the field value generators
+                    let table_name = coordination_pair
+                        .coordinated_fields
+                        .first()
+                        .map(|coord_id| coord_id.table_name.clone())
+                        .unwrap_or_default();
+                    let scope = format!(
+                        "coordinate:{}#{}",
+                        coordination_pair
+                            .coordinated_fields
+                            .iter()
+                            .map(|coord_id| coord_id.field_name.as_str())
+                            .collect::<Vec<_>>()
+                            .join(","),
+                        index
+                    );
+                    let mut rng = self.rng_for(&table_name, &scope);
+                    let now = self.reference_time(&table_name);
+
                     match &coordination_pair.coordination {
                         Coordination::InitializeEqual(_) => {
                             // Get the table config for the first coordinated field
@@ -152,8 +174,13 @@ impl Mockmaker {
                             let field_refs: Vec<&StructField> = fields.iter().collect();
 
                             // Generate sequential values using the dedicated function
-                            let values =
-                                Self::generate_sequential_values(&field_refs, index, increment);
+                            let values = Self::generate_sequential_values(
+                                &field_refs,
+                                index,
+                                increment,
+                                &mut rng,
+                                now,
+                            );
 
                             // Store the generated values
                             for coordination_id in &coordination_pair.coordinated_fields {
@@ -182,7 +209,8 @@ impl Mockmaker {
                             let field_refs: Vec<&StructField> = fields.iter().collect();
 
                             // Generate sum values using the dedicated function
-                            let values = Self::generate_sum_values(&field_refs, index, *total);
+                            let values =
+                                Self::generate_sum_values(&field_refs, index, *total, &mut rng);
 
                             // Store the generated values
                             for coordination_id in &coordination_pair.coordinated_fields {
@@ -270,12 +298,22 @@ impl Mockmaker {
                                 .map(|coord_id| coord_id.get_field(self))
                                 .collect();
                             let field_refs: Vec<&StructField> = fields.iter().collect();
+                            // The locale of the first field decides the locale of the dataset
+                            let locale = coordination_pair
+                                .coordinated_fields
+                                .first()
+                                .map(|coord_id| {
+                                    self.locale_for(&coord_id.table_name, &coord_id.field_name)
+                                })
+                                .unwrap_or(&self.schemasync_config.mock_gen_config.locale);
 
                             // Generate coherent values using the dedicated function
                             let values = Self::generate_coherent_values(
                                 &field_refs,
                                 coherent_dataset,
                                 index,
+                                locale,
+                                &mut rng,
                             );
 
                             // Store the generated values
@@ -307,6 +345,27 @@ impl Mockmaker {
                                 }
                             }
                         }
+                        Coordination::InitializeCustom {
+                            field_names,
+                            coordinator,
+                        } => {
+                            let coordinator =
+                                custom::coordinator(coordinator).unwrap_or_else(|| {
+                                    panic!("No custom coordinator registered as '{}'", coordinator)
+                                });
+                            let field_refs: Vec<&str> =
+                                field_names.iter().map(String::as_str).collect();
+                            let values = coordinator.generate(&field_refs, index, &mut rng);
+
+                            for coordination_id in &coordination_pair.coordinated_fields {
+                                if let Some(value) = values.get(&coordination_id.field_name) {
+                                    self.coordinated_values.insert(
+                                        coordination_id.clone(),
+                                        format!("'{}'", value.replace('\'', "\\'")),
+                                    );
+                                }
+                            }
+                        }
                     }
                 }
             }
@@ -318,6 +377,8 @@ impl Mockmaker {
         fields: &[&StructField],
         _index: usize,
         increment: &CoordinateIncrement,
+        rng: &mut MockRng,
+        now: DateTime<Utc>,
     ) -> HashMap<String, String> {
         tracing::trace!(field_count = fields.len(), "Generating sequential values");
         let mut values = HashMap::new();
@@ -328,7 +389,7 @@ impl Mockmaker {
         match &first_field.format {
             Some(Format::DateTime) => {
                 // Generate base datetime
-                let base: DateTime<Utc> = Utc::now();
+                let base: DateTime<Utc> = now;
                 values.insert(first_field.field_name.clone(), base.to_rfc3339());
 
                 // Generate subsequent values
@@ -369,7 +430,7 @@ impl Mockmaker {
             }
             Some(Format::DateWithinDays(_)) => {
                 // Generate base datetime for DateWithinDays format
-                let base: DateTime<Utc> = Utc::now();
+                let base: DateTime<Utc> = now;
                 values.insert(first_field.field_name.clone(), base.to_rfc3339());
 
                 // Generate subsequent values
@@ -391,7 +452,6 @@ impl Mockmaker {
             }
             _ => {
                 // Numeric sequential
-                let mut rng = rand::rng();
                 let base: f64 = rng.random_range(0.0..100.0);
 
                 for (i, field) in fields.iter().enumerate() {
@@ -412,6 +472,7 @@ impl Mockmaker {
         fields: &[&StructField],
         _index: usize,
         total: f64,
+        rng: &mut MockRng,
     ) -> HashMap<String, String> {
         tracing::trace!(
             field_count = fields.len(),
@@ -419,7 +480,6 @@ impl Mockmaker {
             "Generating sum values"
         );
         let mut values = HashMap::new();
-        let mut rng = rand::rng();
 
         if fields.is_empty() {
             return values;
@@ -570,27 +630,15 @@ impl Mockmaker {
 
     /// Generate coherent values from predefined datasets
     fn generate_coherent_values(
-        _fields: &[&StructField],
+        fields: &[&StructField],
         dataset: &crate::coordinate::CoherentDataset,
         index: usize,
+        locale: &Locale,
+        rng: &mut MockRng,
     ) -> HashMap<String, String> {
         tracing::trace!(index = index, "Generating coherent values");
         use crate::coordinate::*;
 
-        /// Coherent address data
-        const COHERENT_ADDRESSES: &[(&str, &str, &str, &str)] = &[
-            ("New York", "NY", "10001", "USA"),
-            ("Los Angeles", "CA", "90001", "USA"),
-            ("Chicago", "IL", "60601", "USA"),
-            ("Houston", "TX", "77001", "USA"),
-            ("Phoenix", "AZ", "85001", "USA"),
-            ("Philadelphia", "PA", "19101", "USA"),
-            ("San Antonio", "TX", "78201", "USA"),
-            ("San Diego", "CA", "92101", "USA"),
-            ("Dallas", "TX", "75201", "USA"),
-            ("San Jose", "CA", "95101", "USA"),
-        ];
-
         /// Coherent geo location data
         const CITY_COORDINATES: &[(&str, f64, f64, &str)] = &[
             ("New York", 40.7128, -74.0060, "USA"),
@@ -612,8 +660,9 @@ impl Mockmaker {
                 zip,
                 country,
             } => {
+                let addresses = locale.addresses();
                 let (city_val, state_val, zip_val, country_val) =
-                    COHERENT_ADDRESSES[index % COHERENT_ADDRESSES.len()];
+                    addresses[index % addresses.len()];
                 let mut values = HashMap::new();
                 values.insert(city.clone(), city_val.to_string());
                 values.insert(state.clone(), state_val.to_string());
@@ -626,9 +675,18 @@ impl Mockmaker {
                 last_name,
                 full_name,
             } => {
-                // Use the extended person names from coordinate.rs
-                let names = crate::coordinate::EXTENDED_PERSON_NAMES;
-                let (first, last, _gender) = names[index % names.len()];
+                // Use the names of the locale, else the extended person names from coordinate.rs
+                let (first, last) = match locale.dataset() {
+                    Some(dataset) => (
+                        *dataset.first_names.choose(rng).expect("No first names"),
+                        *dataset.last_names.choose(rng).expect("No last names"),
+                    ),
+                    None => {
+                        let names = crate::coordinate::EXTENDED_PERSON_NAMES;
+                        let (first, last, _gender) = names[index % names.len()];
+                        (first, last)
+                    }
+                };
                 let mut values = HashMap::new();
                 values.insert(first_name.clone(), first.to_string());
                 values.insert(last_name.clone(), last.to_string());
@@ -648,6 +706,20 @@ impl Mockmaker {
                 values.insert(longitude.clone(), lng.to_string());
                 values.insert(city.clone(), city_val.to_string());
                 values.insert(country.clone(), country_val.to_string());
+
+                // Geometry fields in the same coordination are placed around the city
+                for field in fields {
+                    let field_type = match &field.field_type {
+                        FieldType::Option(inner) => inner.as_ref(),
+                        other => other,
+                    };
+                    if let FieldType::Geometry(kind) = field_type {
+                        values.insert(
+                            field.field_name.clone(),
+                            random_geometry(*kind, [lng, lat], rng),
+                        );
+                    }
+                }
                 values
             }
             CoherentDataset::DateRange {
@@ -705,6 +777,12 @@ pub enum Coordination {
 
     /// Ensure fields are from same dataset (e.g., matching city/state/zip)
     InitializeCoherent(CoherentDataset),
+
+    /// Fields generated together by the `CustomCoordinator` registered as `coordinator`
+    InitializeCustom {
+        field_names: Vec<String>,
+        coordinator: String,
+    },
 }
 
 impl Coordination {
@@ -1042,6 +1120,15 @@ impl Coordination {
                     }
                 }
             }
+
+            Coordination::InitializeCustom { coordinator, .. } => {
+                if custom::coordinator(coordinator).is_none() {
+                    return Err(EvenframeError::Validation(format!(
+                        "InitializeCustom: No custom coordinator registered as '{}'",
+                        coordinator
+                    )));
+                }
+            }
         }
 
         Ok(())
@@ -1221,6 +1308,8 @@ pub enum CoherentDataset {
         last_name: String,
         full_name: String,
     },
+    /// `latitude` or `longitude` may also name a geometry field, which then
+    /// receives a point or shape around the chosen city
     GeoLocation {
         latitude: String,
         longitude: String,
@@ -1233,12 +1322,15 @@ pub enum CoherentDataset {
     },
 }
 
-/// Trait for custom coordinators
+/// Trait for custom coordinators, registered with `custom::register_coordinator`.
+/// Returns the value of each field of the record at `index` by field name. Values are
+/// plain strings like those of a `MockValueGenerator`, they are quoted for SurrealQL.
 pub trait CustomCoordinator: Send + Sync {
-    fn generate(&self, fields: &[&str], index: usize) -> HashMap<String, String>;
+    fn generate(&self, fields: &[&str], index: usize, rng: &mut MockRng)
+    -> HashMap<String, String>;
 }
 
-// Extended address dataset with more US cities
+// Extended address dataset with more US cities, the addresses of the en_US locale
 pub const EXTENDED_ADDRESSES: &[(&str, &str, &str, &str)] = &[
     // Original addresses
     ("New York", "NY", "10001", "USA"),
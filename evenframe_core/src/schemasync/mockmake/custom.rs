@@ -0,0 +1,347 @@
+//! Application-defined generators for mock values.
+//!
+//! Domain-specific values (SKUs, account numbers, internal codes) rarely fit a regex.
+//! Applications register a `MockValueGenerator` by name with `register_generator` before
+//! running Schemasync, and fields use it with `#[format(custom_fn = "sku_generator")]`.
+//! Values spanning several fields come from a `CustomCoordinator` registered with
+//! `register_coordinator`, referenced by the `InitializeCustom` coordination rule. Both
+//! return plain strings, which are quoted for SurrealQL. Names that aren't registered
+//! fail the run before any mock data is generated.
+
+use crate::{
+    coordinate::{Coordination, CustomCoordinator},
+    error::{EvenframeError, Result},
+    mockmake::{Mockmaker, format::Format, locale::Locale, rng::MockRng},
+    types::{StructField, VariantData},
+};
+use chrono::{DateTime, Utc};
+use std::{
+    collections::HashMap,
+    sync::{Arc, LazyLock, RwLock},
+};
+
+/// Where a custom value is generated
+#[derive(Debug, Clone, Copy)]
+pub struct GeneratorContext<'a> {
+    pub table_name: &'a str,
+    /// Path of the field in the table, like `address.street`
+    pub field_path: &'a str,
+    /// Index of the generated record
+    pub index: usize,
+    pub locale: &'a Locale,
+    /// The time relative dates are counted from
+    pub now: DateTime<Utc>,
+}
+
+/// Generates the values of `#[format(custom_fn = "..")]` fields. Values are plain
+/// strings like those of the other formats, they are quoted for SurrealQL.
+pub trait MockValueGenerator: Send + Sync {
+    fn generate(&self, context: &GeneratorContext<'_>, rng: &mut MockRng) -> String;
+}
+
+impl<F> MockValueGenerator for F
+where
+    F: Fn(&GeneratorContext<'_>, &mut MockRng) -> String + Send + Sync,
+{
+    fn generate(&self, context: &GeneratorContext<'_>, rng: &mut MockRng) -> String {
+        self(context, rng)
+    }
+}
+
+impl<F> CustomCoordinator for F
+where
+    F: Fn(&[&str], usize, &mut MockRng) -> HashMap<String, String> + Send + Sync,
+{
+    fn generate(
+        &self,
+        fields: &[&str],
+        index: usize,
+        rng: &mut MockRng,
+    ) -> HashMap<String, String> {
+        self(fields, index, rng)
+    }
+}
+
+static GENERATORS: LazyLock<RwLock<HashMap<String, Arc<dyn MockValueGenerator>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+static COORDINATORS: LazyLock<RwLock<HashMap<String, Arc<dyn CustomCoordinator>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Register `generator` as the `custom_fn` named `name`, replacing any generator
+/// registered under that name
+pub fn register_generator(name: impl Into<String>, generator: impl MockValueGenerator + 'static) {
+    let name = name.into();
+    tracing::debug!(name = %name, "Registering mock value generator");
+    GENERATORS
+        .write()
+        .expect("Generator registry lock poisoned")
+        .insert(name, Arc::new(generator));
+}
+
+/// Register `coordinator` for the `InitializeCustom` rules naming `name`, replacing any
+/// coordinator registered under that name
+pub fn register_coordinator(
+    name: impl Into<String>,
+    coordinator: impl CustomCoordinator + 'static,
+) {
+    let name = name.into();
+    tracing::debug!(name = %name, "Registering custom coordinator");
+    COORDINATORS
+        .write()
+        .expect("Coordinator registry lock poisoned")
+        .insert(name, Arc::new(coordinator));
+}
+
+/// The generator registered as `name`
+pub fn generator(name: &str) -> Option<Arc<dyn MockValueGenerator>> {
+    GENERATORS
+        .read()
+        .expect("Generator registry lock poisoned")
+        .get(name)
+        .cloned()
+}
+
+/// The coordinator registered as `name`
+pub fn coordinator(name: &str) -> Option<Arc<dyn CustomCoordinator>> {
+    COORDINATORS
+        .read()
+        .expect("Coordinator registry lock poisoned")
+        .get(name)
+        .cloned()
+}
+
+impl Format {
+    /// The value of a `CustomFn` format from its registered generator, `None` for other
+    /// formats
+    pub fn generate_custom_value(
+        &self,
+        context: &GeneratorContext<'_>,
+        rng: &mut MockRng,
+    ) -> Option<String> {
+        let Format::CustomFn(name) = self else {
+            return None;
+        };
+        let generator = generator(name)
+            .unwrap_or_else(|| panic!("No mock value generator registered as '{}'", name));
+        Some(generator.generate(context, rng))
+    }
+}
+
+impl Mockmaker {
+    /// Fail when a field or coordination rule names a generator or coordinator that
+    /// isn't registered. Formats live on the fields of tables, objects and struct-like
+    /// enum variants, inline `FieldType::Struct`s don't carry any.
+    pub(super) fn check_custom_generators(&self) -> Result<()> {
+        let tables = self
+            .tables
+            .iter()
+            .map(|(name, table)| (name.clone(), &table.struct_config.fields));
+        let objects = self
+            .objects
+            .iter()
+            .map(|(name, object)| (name.clone(), &object.fields));
+        let variants = self.enums.iter().flat_map(|(name, tagged_union)| {
+            tagged_union
+                .variants
+                .iter()
+                .filter_map(move |variant| match &variant.data {
+                    Some(VariantData::InlineStruct(config)) => {
+                        Some((format!("{name}::{}", variant.name), &config.fields))
+                    }
+                    _ => None,
+                })
+        });
+        for (owner, fields) in tables.chain(objects).chain(variants) {
+            for field in fields {
+                check_field(&owner, field)?;
+            }
+        }
+
+        for (table_name, table) in &self.tables {
+            let Some(config) = &table.mock_generation_config else {
+                continue;
+            };
+            for rule in &config.coordination_rules {
+                if let Coordination::InitializeCustom {
+                    coordinator: name, ..
+                } = rule
+                    && coordinator(name).is_none()
+                {
+                    return Err(EvenframeError::config(format!(
+                        "No custom coordinator registered as '{name}', used by table '{table_name}'. Register it with `register_coordinator` before running Schemasync"
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn check_field(owner: &str, field: &StructField) -> Result<()> {
+    match &field.format {
+        Some(Format::CustomFn(name)) if generator(name).is_none() => {
+            Err(unregistered_generator(name, owner, &field.field_name))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn unregistered_generator(name: &str, owner: &str, field_name: &str) -> EvenframeError {
+    EvenframeError::config(format!(
+        "No mock value generator registered as '{name}', used by '{owner}.{field_name}'. Register it with `register_generator` before running Schemasync"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        coordinate::CoordinationId,
+        mockmake::{
+            MockGenerationConfig,
+            rng::{mock_rng, reference_time},
+        },
+        schemasync::{StructConfig, TableConfig, TaggedUnion, config::SchemasyncConfig},
+        types::{FieldType, Variant},
+    };
+    use rand::Rng;
+
+    async fn test_mockmaker(
+        tables: HashMap<String, TableConfig>,
+        enums: HashMap<String, TaggedUnion>,
+    ) -> Mockmaker {
+        let config = SchemasyncConfig::for_testing();
+        let db = surrealdb::engine::any::connect("mem://").await.unwrap();
+        Mockmaker::new(db, tables, HashMap::new(), enums, config)
+    }
+
+    fn string_field(name: &str, format: Option<Format>) -> StructField {
+        StructField {
+            field_name: name.to_string(),
+            field_type: FieldType::String,
+            format,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_registered_generator() {
+        register_generator(
+            "test_sku",
+            |context: &GeneratorContext<'_>, rng: &mut MockRng| {
+                format!(
+                    "{}-{:04}-{}",
+                    context.table_name,
+                    context.index,
+                    rng.random_range(0..10)
+                )
+            },
+        );
+        let context = GeneratorContext {
+            table_name: "product",
+            field_path: "sku",
+            index: 7,
+            locale: &Locale::EnUs,
+            now: reference_time(Some(1)),
+        };
+        let format = Format::CustomFn("test_sku".to_string());
+        let value = format
+            .generate_custom_value(&context, &mut mock_rng(Some(1), "test"))
+            .unwrap();
+        assert!(value.starts_with("product-0007-"));
+        assert_eq!(
+            Format::Email.generate_custom_value(&context, &mut mock_rng(None, "")),
+            None
+        );
+        assert!(generator("test_unknown").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_initialize_custom() {
+        register_coordinator(
+            "test_region",
+            |fields: &[&str], index: usize, _rng: &mut MockRng| {
+                assert_eq!(fields, ["code", "airport"]);
+                HashMap::from([
+                    ("code".to_string(), format!("US-{index}")),
+                    ("airport".to_string(), "O'Hare".to_string()),
+                ])
+            },
+        );
+        let rule = |coordinator: &str| Coordination::InitializeCustom {
+            field_names: vec!["code".to_string(), "airport".to_string()],
+            coordinator: coordinator.to_string(),
+        };
+        let table = |coordinator: &str| TableConfig {
+            table_name: "office".to_string(),
+            struct_config: StructConfig::for_testing(
+                "Office",
+                vec![string_field("code", None), string_field("airport", None)],
+            ),
+            relation: None,
+            permissions: None,
+            mock_generation_config: Some(MockGenerationConfig {
+                n: 1,
+                coordination_rules: vec![rule(coordinator)],
+                ..Default::default()
+            }),
+        };
+
+        let mut mockmaker = test_mockmaker(
+            HashMap::from([("office".to_string(), table("test_region"))]),
+            HashMap::new(),
+        )
+        .await;
+        mockmaker.check_custom_generators().unwrap();
+        mockmaker.generate_coordinated_values();
+        let value = |field_name: &str| {
+            mockmaker.coordinated_values[&CoordinationId {
+                table_name: "office".to_string(),
+                field_name: field_name.to_string(),
+            }]
+                .clone()
+        };
+        assert_eq!(value("code"), "'US-0'");
+        assert_eq!(value("airport"), "'O\\'Hare'");
+
+        let mockmaker = test_mockmaker(
+            HashMap::from([("office".to_string(), table("test_unknown"))]),
+            HashMap::new(),
+        )
+        .await;
+        assert!(mockmaker.check_custom_generators().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_check_variant_fields() {
+        let payment = TaggedUnion {
+            enum_name: "Payment".to_string(),
+            variants: vec![Variant {
+                name: "Card".to_string(),
+                data: Some(VariantData::InlineStruct(StructConfig::for_testing(
+                    "Card",
+                    vec![string_field(
+                        "token",
+                        Some(Format::CustomFn("test_unknown_token".to_string())),
+                    )],
+                ))),
+            }],
+            generics: Vec::new(),
+        };
+        let mut mockmaker = test_mockmaker(
+            HashMap::new(),
+            HashMap::from([("Payment".to_string(), payment)]),
+        )
+        .await;
+        let error = mockmaker.check_custom_generators().unwrap_err();
+        assert!(error.to_string().contains("Payment::Card.token"));
+        // The offline paths fail before generating any value
+        assert!(mockmaker.generate_offline_ids().is_err());
+        assert!(
+            mockmaker
+                .export_fixtures(&std::env::temp_dir(), &[])
+                .is_err()
+        );
+    }
+}
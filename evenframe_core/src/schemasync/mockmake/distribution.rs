@@ -0,0 +1,251 @@
+//! Distributions for generated numbers, choices and datetimes.
+//!
+//! Uniform values make charts of mock data look fake. The distribution formats
+//! (`Normal`, `LogNormal`, `Exponential`, `Zipf`, `Weighted` and `Seasonal`) shape them
+//! instead. They are set on a field with `#[format(..)]`, or by field name with
+//! `#[mock_data(distributions(price = LogNormal(3.0, 0.8)))]`, and the generated value
+//! follows the field's type: integer fields get rounded numbers, enum fields get the
+//! `Weighted` variant of that name.
+
+use crate::{
+    mockmake::{constraints::ValueConstraints, format::Format},
+    types::FieldType,
+};
+use chrono::{DateTime, Datelike, Duration, NaiveTime, Utc};
+use rand::{
+    Rng,
+    distr::{Distribution, weighted::WeightedIndex},
+};
+use rand_distr::{Exp, LogNormal, Normal, Zipf};
+
+impl Format {
+    /// Whether this format is a distribution rather than a string format
+    pub fn is_distribution(&self) -> bool {
+        matches!(
+            self,
+            Format::Normal(..)
+                | Format::LogNormal(..)
+                | Format::Exponential(_)
+                | Format::Zipf(..)
+                | Format::Weighted(..)
+                | Format::Seasonal(..)
+        )
+    }
+
+    /// A number from a numeric distribution, `None` for other formats or invalid
+    /// parameters
+    pub fn sample_number<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<f64> {
+        match self {
+            Format::Normal(mean, std_dev) => Normal::new(mean.0, std_dev.0)
+                .ok()
+                .map(|dist| dist.sample(rng)),
+            Format::LogNormal(mu, sigma) => LogNormal::new(mu.0, sigma.0)
+                .ok()
+                .map(|dist| dist.sample(rng)),
+            Format::Exponential(rate) => Exp::new(rate.0).ok().map(|dist| dist.sample(rng)),
+            Format::Zipf(n, exponent) => Zipf::new(*n as f64, exponent.0)
+                .ok()
+                .map(|dist| dist.sample(rng)),
+            _ => None,
+        }
+    }
+
+    /// One of the `Weighted` values
+    pub fn choose_weighted<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<&str> {
+        let Format::Weighted(values, weights) = self else {
+            return None;
+        };
+        let index = weighted_index(values.len(), weights, rng)?;
+        Some(&values[index])
+    }
+
+    /// A datetime from a `Seasonal` distribution, at most its number of days before `now`
+    pub fn sample_datetime<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        now: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let Format::Seasonal(days, hours, weekdays, months) = self else {
+            return None;
+        };
+        let today = now.date_naive();
+        let day_weights = (0..(*days).max(1)).map(|offset| {
+            let date = today - Duration::days(offset);
+            weight(weekdays, date.weekday().num_days_from_monday() as usize)
+                * weight(months, date.month0() as usize)
+        });
+        let offset = WeightedIndex::new(day_weights).ok()?.sample(rng);
+        let hour = weighted_index(24, hours, rng)?;
+
+        let time = NaiveTime::from_hms_opt(
+            hour as u32,
+            rng.random_range(0..60),
+            rng.random_range(0..60),
+        )?;
+        Some(
+            (today - Duration::days(offset as i64))
+                .and_time(time)
+                .and_utc(),
+        )
+    }
+
+    /// The value of a distribution format, as `generate_formatted_value_with` returns it
+    pub(super) fn generate_distribution_value<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        now: DateTime<Utc>,
+    ) -> Option<String> {
+        match self {
+            Format::Normal(..) | Format::LogNormal(..) | Format::Exponential(_) => {
+                self.sample_number(rng).map(|value| format!("{:.2}", value))
+            }
+            Format::Zipf(..) => self
+                .sample_number(rng)
+                .map(|value| format!("{}", value as u64)),
+            Format::Weighted(..) => self.choose_weighted(rng).map(str::to_string),
+            Format::Seasonal(..) => self
+                .sample_datetime(rng, now)
+                .map(|datetime| datetime.format("%Y-%m-%dT%H:%M:%SZ").to_string()),
+            _ => None,
+        }
+    }
+
+    /// A value of `field_type` from this distribution, as a SurrealQL literal. Numbers
+    /// are drawn until they satisfy `constraints`. `None` for types the distribution
+    /// doesn't generate: options and collections get distributed items, enums a
+    /// `Weighted` variant.
+    pub fn generate_value_for<R: Rng + ?Sized>(
+        &self,
+        field_type: &FieldType,
+        constraints: &ValueConstraints,
+        rng: &mut R,
+        now: DateTime<Utc>,
+    ) -> Option<String> {
+        match field_type {
+            FieldType::String => {
+                let value = self.generate_distribution_value(rng, now)?;
+                Some(format!("'{}'", value.replace('\'', "\\'")))
+            }
+            FieldType::Decimal => Some(format!(
+                "{:.3}dec",
+                self.bounded_number(constraints, false, rng)?
+            )),
+            FieldType::F32 | FieldType::F64 | FieldType::OrderedFloat(_) => Some(format!(
+                "{:.2}f",
+                self.bounded_number(constraints, false, rng)?
+            )),
+            FieldType::I8
+            | FieldType::I16
+            | FieldType::I32
+            | FieldType::I64
+            | FieldType::I128
+            | FieldType::Isize => Some(format!(
+                "{}",
+                self.bounded_number(constraints, true, rng)? as i128
+            )),
+            FieldType::U8
+            | FieldType::U16
+            | FieldType::U32
+            | FieldType::U64
+            | FieldType::U128
+            | FieldType::Usize => Some(format!(
+                "{}",
+                self.bounded_number(constraints, true, rng)?.max(0.0) as u128
+            )),
+            FieldType::DateTime => self
+                .sample_datetime(rng, now)
+                .map(|datetime| format!("d'{}'", datetime.to_rfc3339())),
+            FieldType::NaiveDate => self
+                .sample_datetime(rng, now)
+                .map(|datetime| format!("d'{}'", datetime.format("%Y-%m-%d"))),
+            _ => None,
+        }
+    }
+
+    /// A number from this distribution within the bounds of `constraints`. Draws falling
+    /// outside are repeated a few times before falling back to a uniform value inside.
+    fn bounded_number<R: Rng + ?Sized>(
+        &self,
+        constraints: &ValueConstraints,
+        integer: bool,
+        rng: &mut R,
+    ) -> Option<f64> {
+        let integer = integer || constraints.integer;
+        for _ in 0..16 {
+            let mut value = self.sample_number(rng)?;
+            if let Some(step) = constraints.multiple_of.filter(|step| *step > 0.0) {
+                value = (value / step).round() * step;
+            }
+            if integer {
+                value = value.round();
+            }
+            if constraints.admits(value) {
+                return Some(value);
+            }
+        }
+        Some(constraints.number(rng, integer, 0.0..100.0))
+    }
+}
+
+/// Weight at `index`, 1 when not given
+fn weight(weights: &[u32], index: usize) -> f64 {
+    weights.get(index).map_or(1.0, |weight| *weight as f64)
+}
+
+/// An index below `count` drawn by `weights`, `None` when every weight is zero
+fn weighted_index<R: Rng + ?Sized>(count: usize, weights: &[u32], rng: &mut R) -> Option<usize> {
+    let dist = WeightedIndex::new((0..count).map(|index| weight(weights, index))).ok()?;
+    Some(dist.sample(rng))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mockmake::rng::{mock_rng, reference_time};
+    use chrono::{Timelike, Weekday};
+    use ordered_float::OrderedFloat;
+
+    #[test]
+    fn test_numeric_distributions() {
+        let mut rng = mock_rng(Some(3), "test");
+        let normal = Format::Normal(OrderedFloat(50.0), OrderedFloat(5.0));
+        let values: Vec<f64> = (0..1000)
+            .map(|_| normal.sample_number(&mut rng).unwrap())
+            .collect();
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        assert!((mean - 50.0).abs() < 1.0, "mean was {mean}");
+
+        let zipf = Format::Zipf(10, OrderedFloat(1.5));
+        let ranks: Vec<f64> = (0..1000)
+            .map(|_| zipf.sample_number(&mut rng).unwrap())
+            .collect();
+        assert!(ranks.iter().all(|rank| (1.0..=10.0).contains(rank)));
+        let ones = ranks.iter().filter(|rank| **rank == 1.0).count();
+        assert!(ones > 300, "rank 1 was drawn {ones} times");
+
+        assert_eq!(Format::Email.sample_number(&mut rng), None);
+    }
+
+    #[test]
+    fn test_weighted_and_seasonal() {
+        let mut rng = mock_rng(Some(3), "test");
+        let weighted = Format::Weighted(
+            vec!["Active".to_string(), "Suspended".to_string()],
+            vec![1, 0],
+        );
+        for _ in 0..100 {
+            assert_eq!(weighted.choose_weighted(&mut rng), Some("Active"));
+        }
+
+        // Business hours on weekdays only
+        let hours = (0..24).map(|hour| (9..17).contains(&hour) as u32).collect();
+        let seasonal = Format::Seasonal(90, hours, vec![1, 1, 1, 1, 1, 0, 0], vec![]);
+        let now = reference_time(Some(3));
+        for _ in 0..100 {
+            let datetime = seasonal.sample_datetime(&mut rng, now).unwrap();
+            assert!(now - datetime < Duration::days(91));
+            assert!((9..17).contains(&datetime.time().hour()));
+            assert!(!matches!(datetime.weekday(), Weekday::Sat | Weekday::Sun));
+        }
+    }
+}
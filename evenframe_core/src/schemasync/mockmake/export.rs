@@ -0,0 +1,437 @@
+//! Mock data fixture files.
+//!
+//! The records Mockmaker generates for the database can also be written to files, one
+//! per table and format, so frontends and Storybook consume the same data. Every format
+//! is rendered from the same generated records. `manifest.json` lists the tables in
+//! dependency order (referenced tables first), the order the `.surql` scripts have to
+//! be imported in.
+
+use crate::{
+    dependency::sort_tables_by_dependencies,
+    error::Result,
+    mockmake::{Mockmaker, field_value::FieldValueGenerator},
+    schemasync::{config::FixtureFormat, surql::is_generated_field, table::TableConfig},
+};
+use convert_case::{Case, Casing};
+use serde_json::{Map, Number, Value, json};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{BufWriter, Write},
+    iter::Peekable,
+    path::{Path, PathBuf},
+    str::Chars,
+};
+use tracing::{debug, info};
+
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// A generated record. Field values are SurrealQL literals, as in the mock data
+/// statements.
+#[derive(Debug, Clone)]
+pub struct MockRecord {
+    pub id: String,
+    pub fields: Vec<(String, String)>,
+}
+
+impl MockRecord {
+    /// The record as a JSON object. Record links, datetimes, UUIDs and durations become
+    /// strings.
+    pub fn to_json(&self) -> Value {
+        let mut object = Map::new();
+        object.insert("id".to_string(), Value::String(self.id.clone()));
+        for (field_name, literal) in &self.fields {
+            object.insert(field_name.clone(), surql_to_json(literal));
+        }
+        Value::Object(object)
+    }
+
+    /// `INSERT` statement creating the record in `table_name`
+    pub fn to_surql(&self, table_name: &str, is_relation: bool) -> String {
+        let mut assignments = vec![format!("id: r'{}'", self.id)];
+        assignments.extend(
+            self.fields
+                .iter()
+                .map(|(field_name, literal)| format!("{}: {}", field_name, literal)),
+        );
+        let relation = if is_relation { "RELATION " } else { "" };
+        format!(
+            "INSERT {}INTO {} {{ {} }};\n",
+            relation,
+            table_name,
+            assignments.join(", ")
+        )
+    }
+}
+
+impl Mockmaker {
+    /// Number of records generated for a table
+    pub fn record_count(&self, table_config: &TableConfig) -> usize {
+        table_config
+            .mock_generation_config
+            .as_ref()
+            .map(|c| c.n)
+            .unwrap_or(self.schemasync_config.mock_gen_config.default_record_count)
+    }
+
+    /// Ids of every table without querying the database: `<table>:1` to `<table>:<n>`.
+    /// Fails when the mock data can't be generated, see `check_mock_generation`.
+    pub fn generate_offline_ids(&mut self) -> Result<()> {
+        self.check_mock_generation()?;
+        let mut id_map = HashMap::new();
+        let mut record_diffs = HashMap::new();
+        for (table_name, table_config) in &self.tables {
+            let n = self.record_count(table_config);
+            let ids = (1..=n).map(|i| format!("{table_name}:{i}")).collect();
+            id_map.insert(table_name.clone(), ids);
+            record_diffs.insert(table_name.clone(), n as i32);
+        }
+        debug!(table_count = id_map.len(), "Offline ID generation complete");
+        self.id_map = id_map;
+        self.record_diffs = record_diffs;
+        self.plan_references();
+        Ok(())
+    }
+
+    /// Generate the mock records of a table, with the same fields and values the
+    /// mock data statements write
+    pub fn generate_records(
+        &self,
+        table_name: &str,
+        table_config: &TableConfig,
+    ) -> Vec<MockRecord> {
+        (0..self.record_count(table_config))
+            .map(|i| self.generate_record(table_name, table_config, i))
+            .collect()
+    }
+
+    /// Generate the mock record at `index` of a table
+    pub fn generate_record(
+        &self,
+        table_name: &str,
+        table_config: &TableConfig,
+        index: usize,
+    ) -> MockRecord {
+        let id = self
+            .id_map
+            .get(table_name)
+            .and_then(|ids| ids.get(index))
+            .cloned()
+            .unwrap_or_else(|| format!("{}:{}", table_name.to_case(Case::Snake), index + 1));
+        let fields = table_config
+            .struct_config
+            .fields
+            .iter()
+            .filter(|field| is_generated_field(field))
+            .map(|field| {
+                let value = FieldValueGenerator::builder()
+                    .field(field)
+                    .id_index(&index)
+                    .mockmaker(self)
+                    .table_config(table_config)
+                    .build()
+                    .run();
+                (field.field_name.clone(), value)
+            })
+            .collect();
+        MockRecord { id, fields }
+    }
+
+    /// Write the mock data of every table to `directory`, one file per table and format,
+    /// plus the manifest. Returns the written files.
+    ///
+    /// The records are generated again from the current ids rather than read back from
+    /// the database, so they only match the inserted data when `seed` is set, which
+    /// Schemasync runs writing fixtures require. Records preserved in the database are
+    /// exported with regenerated values too.
+    pub fn export_fixtures(
+        &self,
+        directory: &Path,
+        formats: &[FixtureFormat],
+    ) -> Result<Vec<PathBuf>> {
+        info!(
+            directory = %directory.display(),
+            formats = ?formats,
+            "Exporting mock data fixtures"
+        );
+        self.check_mock_generation()?;
+        fs::create_dir_all(directory)?;
+
+        let sorted_table_names =
+            sort_tables_by_dependencies(&self.tables, &self.objects, &self.enums);
+        let mut written = Vec::new();
+        let mut manifest = Vec::new();
+
+        for table_name in &sorted_table_names {
+            let Some(table_config) = self.tables.get(table_name) else {
+                continue;
+            };
+            let records = self.generate_records(table_name, table_config);
+            let is_relation = table_config.relation.is_some();
+
+            let mut files = Vec::new();
+            for format in formats {
+                let file_name = format!("{}.{}", table_name, format.extension());
+                let path = directory.join(&file_name);
+                let mut out = BufWriter::new(fs::File::create(&path)?);
+                match format {
+                    FixtureFormat::Json => {
+                        let records: Vec<Value> = records.iter().map(MockRecord::to_json).collect();
+                        serde_json::to_writer_pretty(&mut out, &records)?;
+                        out.write_all(b"\n")?;
+                    }
+                    FixtureFormat::Ndjson => {
+                        for record in &records {
+                            serde_json::to_writer(&mut out, &record.to_json())?;
+                            out.write_all(b"\n")?;
+                        }
+                    }
+                    FixtureFormat::Surql => {
+                        for record in &records {
+                            out.write_all(record.to_surql(table_name, is_relation).as_bytes())?;
+                        }
+                    }
+                }
+                out.flush()?;
+                files.push(file_name);
+                written.push(path);
+            }
+
+            debug!(
+                table = %table_name,
+                record_count = records.len(),
+                "Wrote fixtures for table"
+            );
+            manifest.push(json!({
+                "table": table_name,
+                "relation": is_relation,
+                "records": records.len(),
+                "files": files,
+            }));
+        }
+
+        let manifest_path = directory.join(MANIFEST_FILE);
+        fs::write(
+            &manifest_path,
+            serde_json::to_string_pretty(&json!({ "tables": manifest }))?,
+        )?;
+        written.push(manifest_path);
+
+        info!(
+            table_count = sorted_table_names.len(),
+            file_count = written.len(),
+            "Mock data fixtures exported"
+        );
+        Ok(written)
+    }
+}
+
+/// Convert a generated SurrealQL value literal to JSON. Literals that are not understood
+/// are kept as strings.
+pub fn surql_to_json(literal: &str) -> Value {
+    let mut chars = literal.trim().chars().peekable();
+    parse_value(&mut chars)
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+}
+
+fn parse_value(chars: &mut Peekable<Chars>) -> Value {
+    skip_whitespace(chars);
+    if is_typed_string(chars) {
+        // d'...' datetimes, u'...' UUIDs and r'...' record links
+        chars.next();
+        return Value::String(parse_string(chars));
+    }
+    match chars.peek().copied() {
+        Some('\'') | Some('"') => Value::String(parse_string(chars)),
+        Some('[') | Some('(') => Value::Array(parse_list(chars)),
+        Some('{') => parse_object(chars),
+        Some('<') => {
+            // Casts like `<set>` and `<bytes>` don't change the JSON shape
+            while chars.next().is_some_and(|c| c != '>') {}
+            parse_value(chars)
+        }
+        Some(_) => parse_bare(chars),
+        None => Value::Null,
+    }
+}
+
+fn is_typed_string(chars: &Peekable<Chars>) -> bool {
+    let mut lookahead = chars.clone();
+    matches!(lookahead.next(), Some('d' | 'u' | 'r'))
+        && matches!(lookahead.next(), Some('\'' | '"'))
+}
+
+fn parse_string(chars: &mut Peekable<Chars>) -> String {
+    let quote = chars.next().unwrap_or('\'');
+    let mut value = String::new();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    value.push(escaped);
+                }
+            }
+            c if c == quote => break,
+            c => value.push(c),
+        }
+    }
+    value
+}
+
+fn parse_list(chars: &mut Peekable<Chars>) -> Vec<Value> {
+    let close = if chars.next() == Some('(') { ')' } else { ']' };
+    let mut items = Vec::new();
+    loop {
+        skip_whitespace(chars);
+        match chars.peek() {
+            None => break,
+            Some(&c) if c == close => {
+                chars.next();
+                break;
+            }
+            Some(',') => {
+                chars.next();
+            }
+            Some(_) => items.push(parse_value(chars)),
+        }
+    }
+    items
+}
+
+fn parse_object(chars: &mut Peekable<Chars>) -> Value {
+    chars.next();
+    let mut object = Map::new();
+    loop {
+        skip_whitespace(chars);
+        match chars.peek() {
+            None => break,
+            Some('}') => {
+                chars.next();
+                break;
+            }
+            Some(',') => {
+                chars.next();
+            }
+            Some('\'') | Some('"') => {
+                let key = parse_string(chars);
+                object.insert(key, parse_object_value(chars));
+            }
+            Some(_) => {
+                let mut key = String::new();
+                while let Some(c) = chars.next_if(|c| *c != ':' && !c.is_whitespace()) {
+                    key.push(c);
+                }
+                object.insert(key, parse_object_value(chars));
+            }
+        }
+    }
+    Value::Object(object)
+}
+
+fn parse_object_value(chars: &mut Peekable<Chars>) -> Value {
+    skip_whitespace(chars);
+    chars.next_if_eq(&':');
+    parse_value(chars)
+}
+
+fn parse_bare(chars: &mut Peekable<Chars>) -> Value {
+    let mut token = String::new();
+    let mut depth = 0usize;
+    while let Some(&c) = chars.peek() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth > 0 => depth -= 1,
+            ',' | ']' | '}' | ')' if depth == 0 => break,
+            _ => {}
+        }
+        token.push(c);
+        chars.next();
+    }
+    let token = token.trim();
+
+    if let Some(nanos) = token
+        .strip_prefix("duration::from::nanos(")
+        .and_then(|rest| rest.strip_suffix(')'))
+        .and_then(|nanos| nanos.trim().parse::<u128>().ok())
+    {
+        return Value::String(format_duration(nanos));
+    }
+
+    match token {
+        "NONE" | "NULL" | "null" => return Value::Null,
+        "true" => return Value::Bool(true),
+        "false" => return Value::Bool(false),
+        _ => {}
+    }
+    if let Ok(integer) = token.parse::<i64>() {
+        return Value::Number(integer.into());
+    }
+    let number = token
+        .strip_suffix("dec")
+        .or_else(|| token.strip_suffix('f'))
+        .unwrap_or(token);
+    if let Some(number) = number.parse::<f64>().ok().and_then(Number::from_f64) {
+        return Value::Number(number);
+    }
+    Value::String(token.to_string())
+}
+
+/// Format nanoseconds the way SurrealDB displays durations, e.g. `1h30m`
+fn format_duration(nanos: u128) -> String {
+    const UNITS: [(&str, u128); 9] = [
+        ("y", 365 * 86_400_000_000_000),
+        ("w", 7 * 86_400_000_000_000),
+        ("d", 86_400_000_000_000),
+        ("h", 3_600_000_000_000),
+        ("m", 60_000_000_000),
+        ("s", 1_000_000_000),
+        ("ms", 1_000_000),
+        ("µs", 1_000),
+        ("ns", 1),
+    ];
+    if nanos == 0 {
+        return "0ns".to_string();
+    }
+    let mut remaining = nanos;
+    let mut formatted = String::new();
+    for (unit, size) in UNITS {
+        let count = remaining / size;
+        if count > 0 {
+            formatted.push_str(&format!("{count}{unit}"));
+            remaining %= size;
+        }
+    }
+    formatted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_surql_to_json() {
+        assert_eq!(surql_to_json("'abc'"), json!("abc"));
+        assert_eq!(surql_to_json("42"), json!(42));
+        assert_eq!(surql_to_json("12.50f"), json!(12.5));
+        assert_eq!(surql_to_json("3.125dec"), json!(3.125));
+        assert_eq!(surql_to_json("NONE"), Value::Null);
+        assert_eq!(surql_to_json("r'user:1'"), json!("user:1"));
+        assert_eq!(
+            surql_to_json("d'2025-01-01T00:00:00+00:00'"),
+            json!("2025-01-01T00:00:00+00:00")
+        );
+        assert_eq!(
+            surql_to_json("duration::from::nanos(5400000000000)"),
+            json!("1h30m")
+        );
+        assert_eq!(
+            surql_to_json(
+                "{ name: 'a', tags: <set>['x', 'y'], point: (1.5, -2), bytes: <bytes>[1, 2] }"
+            ),
+            json!({ "name": "a", "tags": ["x", "y"], "point": [1.5, -2], "bytes": [1, 2] })
+        );
+    }
+}
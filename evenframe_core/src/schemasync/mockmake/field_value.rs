@@ -1,30 +1,41 @@
 use crate::{
     coordinate::CoordinationId,
     format::Format,
-    mockmake::Mockmaker,
+    mockmake::{
+        Mockmaker,
+        cardinality::ReferencePlan,
+        constraints::ValueConstraints,
+        custom::GeneratorContext,
+        geometry::{random_center, random_geometry},
+        rng::{MockRng, random_uuid},
+    },
     schemasync::TableConfig,
-    types::{FieldType, StructField, VariantData},
+    types::{FieldType, StructField, StructKind, Variant, VariantData},
 };
 use bon::Builder;
+use chrono::{DateTime, Utc};
 use chrono_tz::TZ_VARIANTS;
 use convert_case::{Case, Casing};
-use rand::{Rng, rngs::ThreadRng, seq::IndexedRandom};
-use std::collections::HashSet;
+use rand::{Rng, seq::IndexedRandom};
+use std::{borrow::Cow, collections::HashSet};
 use tracing;
 
 // The context struct is now simple again, with a direct reference.
 #[derive(Clone)]
 struct Frame<'a> {
-    field: &'a StructField,
+    field: Cow<'a, StructField>,
     table_config: &'a TableConfig,
     field_type: &'a FieldType,
     field_path: String,             // Track the full path for nested fields
     visited_types: HashSet<String>, // Track visited types to avoid infinite recursion
+    constrained: bool,              // Whether the validators of `field` apply to this value
+    collection_item: bool,          // Whether this value is an item of a Vec or set
 }
 
 enum WorkItem<'a> {
     Generate(Frame<'a>),
     AssembleVec { count: usize },
+    AssembleSet { count: usize },
     AssembleTuple { count: usize },
     AssembleStruct { field_names: Vec<String> },
     AssembleMap { count: usize },
@@ -44,14 +55,22 @@ impl<'a> FieldValueGenerator<'a> {
     pub fn run(&self) -> String {
         let mut work_stack: Vec<WorkItem<'a>> = Vec::new();
         let mut value_stack: Vec<String> = Vec::new();
-        let mut rng = rand::rng();
+        // One stream per record and field, so values don't depend on generation order
+        let table_name = &self.table_config.table_name;
+        let mut rng = self.mockmaker.rng_for(
+            table_name,
+            &format!("{}#{}", self.field.field_name, self.id_index),
+        );
+        let now = self.mockmaker.reference_time(table_name);
 
         let initial_context = Frame {
-            field: self.field,
+            field: Cow::Borrowed(self.field),
             table_config: self.table_config,
             field_type: &self.field.field_type,
             field_path: self.field.field_name.clone(),
             visited_types: HashSet::new(),
+            constrained: true,
+            collection_item: false,
         };
         work_stack.push(WorkItem::Generate(initial_context));
 
@@ -65,22 +84,63 @@ impl<'a> FieldValueGenerator<'a> {
                             .build(),
                     ) {
                         value_stack.push(coordinated_value.to_string());
-                    } else if let Some(format) = &ctx.field.format {
-                        value_stack.push(self.handle_format(format));
+                    } else if let Some(format) = &ctx.field.format
+                        && !format.is_distribution()
+                    {
+                        value_stack.push(self.handle_format(
+                            format,
+                            &ctx.field_path,
+                            &mut rng,
+                            now,
+                        ));
+                    } else if let Some(value) = self.distributed_value(&ctx, &mut rng, now) {
+                        value_stack.push(value);
                     } else {
+                        // Validators apply to the field value, and to the items of an Option
+                        // or collection
+                        let mut constraints = if ctx.constrained {
+                            ValueConstraints::from_validators(&ctx.field.validators)
+                        } else {
+                            ValueConstraints::default()
+                        };
+                        constraints.unsigned = matches!(
+                            ctx.field_type,
+                            FieldType::U8
+                                | FieldType::U16
+                                | FieldType::U32
+                                | FieldType::U64
+                                | FieldType::U128
+                                | FieldType::Usize
+                        );
                         match ctx.field_type {
-                            FieldType::String => {
-                                value_stack.push(format!("'{}'", Mockmaker::random_string(8)))
+                            FieldType::String if constraints.constrains_strings() => {
+                                let value = constraints.string(&mut rng, now);
+                                value_stack.push(format!("'{}'", value.replace('\'', "\\'")))
                             }
+                            FieldType::String => value_stack
+                                .push(format!("'{}'", Mockmaker::random_string_with(&mut rng, 8))),
                             FieldType::Char => value_stack
                                 .push(format!("'{}'", rng.random_range(32u8..=126u8) as char)),
                             FieldType::Bool => {
                                 value_stack.push(format!("{}", rng.random_bool(0.5)))
                             }
                             FieldType::Unit => value_stack.push("NONE".to_string()),
+                            FieldType::Decimal if constraints.constrains_numbers() => value_stack
+                                .push(format!(
+                                    "{}dec",
+                                    constraints.number(&mut rng, false, 0.0..100.0)
+                                )),
                             FieldType::Decimal => {
                                 value_stack.push(format!("{:.3}dec", rng.random_range(0.0..100.0)))
                             }
+                            FieldType::F32 | FieldType::F64 | FieldType::OrderedFloat(_)
+                                if constraints.constrains_numbers() =>
+                            {
+                                value_stack.push(format!(
+                                    "{}f",
+                                    constraints.number(&mut rng, false, 0.0..100.0)
+                                ))
+                            }
                             FieldType::F32 | FieldType::F64 | FieldType::OrderedFloat(_) => {
                                 value_stack.push(format!("{:.2}f", rng.random_range(0.0..100.0)))
                             }
@@ -89,6 +149,25 @@ impl<'a> FieldValueGenerator<'a> {
                             | FieldType::I32
                             | FieldType::I64
                             | FieldType::I128
+                            | FieldType::Isize
+                            | FieldType::U8
+                            | FieldType::U16
+                            | FieldType::U32
+                            | FieldType::U64
+                            | FieldType::U128
+                            | FieldType::Usize
+                                if constraints.constrains_numbers() =>
+                            {
+                                value_stack.push(format!(
+                                    "{}",
+                                    constraints.number(&mut rng, true, 0.0..100.0) as i128
+                                ))
+                            }
+                            FieldType::I8
+                            | FieldType::I16
+                            | FieldType::I32
+                            | FieldType::I64
+                            | FieldType::I128
                             | FieldType::Isize => {
                                 value_stack.push(format!("{}", rng.random_range(0..100)))
                             }
@@ -101,7 +180,8 @@ impl<'a> FieldValueGenerator<'a> {
                                 value_stack.push(format!("{}", rng.random_range(0..100)))
                             }
                             FieldType::DateTime => {
-                                value_stack.push(format!("d'{}'", chrono::Utc::now().to_rfc3339()))
+                                let datetime = constraints.datetime(&mut rng).unwrap_or(now);
+                                value_stack.push(format!("d'{}'", datetime.to_rfc3339()))
                             }
                             FieldType::EvenframeDuration => value_stack.push(format!(
                                 "duration::from::nanos({})",
@@ -111,6 +191,55 @@ impl<'a> FieldValueGenerator<'a> {
                                 let tz = &TZ_VARIANTS[rng.random_range(0..TZ_VARIANTS.len())];
                                 value_stack.push(format!("'{}'", tz.name()));
                             }
+                            FieldType::Uuid => {
+                                value_stack.push(format!("u'{}'", random_uuid(&mut rng)))
+                            }
+                            FieldType::NaiveDate => {
+                                let date = match constraints.datetime(&mut rng) {
+                                    Some(datetime) => datetime.date_naive(),
+                                    None => {
+                                        now.date_naive()
+                                            - chrono::Days::new(rng.random_range(0..3650))
+                                    }
+                                };
+                                value_stack.push(format!("d'{}'", date.format("%Y-%m-%d")));
+                            }
+                            FieldType::NaiveTime => value_stack.push(format!(
+                                "'{:02}:{:02}:{:02}'",
+                                rng.random_range(0..24),
+                                rng.random_range(0..60),
+                                rng.random_range(0..60)
+                            )),
+                            FieldType::Duration => value_stack.push(format!(
+                                "duration::from::nanos({})",
+                                rng.random_range(0..86_400_000_000_000i64)
+                            )),
+                            FieldType::Bytes => {
+                                let bytes: Vec<String> = (0..rng.random_range(4..32))
+                                    .map(|_| rng.random::<u8>().to_string())
+                                    .collect();
+                                value_stack.push(format!("<bytes>[{}]", bytes.join(", ")));
+                            }
+                            FieldType::JsonValue => value_stack.push(format!(
+                                "{{ value: '{}' }}",
+                                Mockmaker::random_string_with(&mut rng, 8)
+                            )),
+                            FieldType::IpAddr => value_stack.push(self.handle_format(
+                                &Format::IpAddress,
+                                &ctx.field_path,
+                                &mut rng,
+                                now,
+                            )),
+                            FieldType::Url => value_stack.push(self.handle_format(
+                                &Format::Url("example.com".to_string()),
+                                &ctx.field_path,
+                                &mut rng,
+                                now,
+                            )),
+                            FieldType::Geometry(kind) => {
+                                let center = random_center(&mut rng);
+                                value_stack.push(random_geometry(*kind, center, &mut rng));
+                            }
                             FieldType::EvenframeRecordId => {
                                 value_stack.push(self.handle_record_id(
                                     &ctx.field.field_name,
@@ -120,7 +249,14 @@ impl<'a> FieldValueGenerator<'a> {
                                 ))
                             }
                             FieldType::Option(inner_type) => {
-                                if rng.random_bool(0.5) {
+                                // A planned reference is only empty where the plan says so
+                                let is_null = match self.reference_plan(&ctx.field_path) {
+                                    Some(plan) if !ctx.collection_item => {
+                                        plan.target(*self.id_index) == Some(None)
+                                    }
+                                    _ => rng.random_bool(0.5),
+                                };
+                                if is_null {
                                     value_stack.push("null".to_string());
                                 } else {
                                     work_stack.push(WorkItem::Generate(Frame {
@@ -130,11 +266,29 @@ impl<'a> FieldValueGenerator<'a> {
                                 }
                             }
                             FieldType::Vec(inner_type) => {
-                                let count = rng.random_range(2..10);
+                                let count = match self.reference_plan(&ctx.field_path) {
+                                    Some(plan) => plan.item_count(&mut rng),
+                                    None => constraints.item_count(&mut rng, 2..10),
+                                };
                                 work_stack.push(WorkItem::AssembleVec { count });
                                 for _ in 0..count {
                                     work_stack.push(WorkItem::Generate(Frame {
                                         field_type: inner_type,
+                                        collection_item: true,
+                                        ..ctx.clone()
+                                    }));
+                                }
+                            }
+                            FieldType::HashSet(inner_type) | FieldType::BTreeSet(inner_type) => {
+                                let count = match self.reference_plan(&ctx.field_path) {
+                                    Some(plan) => plan.item_count(&mut rng),
+                                    None => constraints.item_count(&mut rng, 2..10),
+                                };
+                                work_stack.push(WorkItem::AssembleSet { count });
+                                for _ in 0..count {
+                                    work_stack.push(WorkItem::Generate(Frame {
+                                        field_type: inner_type,
+                                        collection_item: true,
                                         ..ctx.clone()
                                     }));
                                 }
@@ -144,6 +298,7 @@ impl<'a> FieldValueGenerator<'a> {
                                 for inner_type in types.iter().rev() {
                                     work_stack.push(WorkItem::Generate(Frame {
                                         field_type: inner_type,
+                                        constrained: false,
                                         ..ctx.clone()
                                     }));
                                 }
@@ -161,6 +316,7 @@ impl<'a> FieldValueGenerator<'a> {
                                             ctx.field_path.clone(),
                                             nested_field_name
                                         ),
+                                        constrained: false,
                                         ..ctx.clone()
                                     }));
                                 }
@@ -172,10 +328,12 @@ impl<'a> FieldValueGenerator<'a> {
                                 for _ in 0..count {
                                     work_stack.push(WorkItem::Generate(Frame {
                                         field_type: value_ft,
+                                        constrained: false,
                                         ..ctx.clone()
                                     }));
                                     work_stack.push(WorkItem::Generate(Frame {
                                         field_type: key_ft,
+                                        constrained: false,
                                         ..ctx.clone()
                                     }));
                                 }
@@ -196,8 +354,12 @@ impl<'a> FieldValueGenerator<'a> {
                                                 if !possible_ids.is_empty() {
                                                     let id = format!(
                                                         "r'{}'",
-                                                        possible_ids[rng
-                                                            .random_range(0..possible_ids.len())]
+                                                        self.pick_reference(
+                                                            &ctx.field_path,
+                                                            ctx.collection_item,
+                                                            possible_ids,
+                                                            &mut rng,
+                                                        )
                                                     );
                                                     value_stack.push(id);
                                                 } else {
@@ -227,7 +389,17 @@ impl<'a> FieldValueGenerator<'a> {
                                     }
                                 }
                             }
-                            FieldType::Other(type_name) => {
+                            FieldType::GenericParam(param) => {
+                                tracing::warn!(
+                                    param = %param,
+                                    field_path = %ctx.field_path,
+                                    "Unbound generic parameter, generating null"
+                                );
+                                value_stack.push("null".to_string());
+                            }
+                            FieldType::Other(_) | FieldType::Generic(..) => {
+                                // Generic instantiations are registered under their display name
+                                let type_name = &ctx.field_type.to_string();
                                 // Check if we've already visited this type to avoid infinite recursion
                                 if ctx.visited_types.contains(type_name) {
                                     tracing::debug!(
@@ -251,7 +423,12 @@ impl<'a> FieldValueGenerator<'a> {
                                     {
                                         format!(
                                             "r'{}'",
-                                            possible_ids[rng.random_range(0..possible_ids.len())]
+                                            self.pick_reference(
+                                                &ctx.field_path,
+                                                ctx.collection_item,
+                                                possible_ids,
+                                                &mut rng,
+                                            )
                                         )
                                     } else {
                                         panic!(
@@ -266,20 +443,54 @@ impl<'a> FieldValueGenerator<'a> {
                                     .get(type_name)
                                     .or_else(|| self.mockmaker.objects.get(&snake_case_name))
                                 {
-                                    let field_names: Vec<String> = struct_config
-                                        .fields
-                                        .iter()
-                                        .map(|f| f.field_name.clone())
-                                        .collect();
-                                    work_stack.push(WorkItem::AssembleStruct { field_names });
-
                                     // Add current type to visited types for nested fields
                                     let mut new_visited = ctx.visited_types.clone();
                                     new_visited.insert(type_name.clone());
 
+                                    if let Some(inner_field) = struct_config.newtype_field() {
+                                        // Newtypes are transparent: generate the wrapped value in
+                                        // place, under the outer field's format and validators as
+                                        // well as the newtype's own
+                                        let mut field = inner_field.clone();
+                                        field.validators = struct_config
+                                            .validators
+                                            .iter()
+                                            .chain(&inner_field.validators)
+                                            .cloned()
+                                            .collect();
+                                        if ctx.constrained {
+                                            field.format =
+                                                ctx.field.format.clone().or(field.format);
+                                            field
+                                                .validators
+                                                .extend_from_slice(&ctx.field.validators);
+                                        }
+                                        work_stack.push(WorkItem::Generate(Frame {
+                                            field: Cow::Owned(field),
+                                            field_type: &inner_field.field_type,
+                                            visited_types: new_visited,
+                                            constrained: true,
+                                            ..ctx.clone()
+                                        }));
+                                        continue;
+                                    }
+
+                                    if struct_config.kind == StructKind::Tuple {
+                                        work_stack.push(WorkItem::AssembleVec {
+                                            count: struct_config.fields.len(),
+                                        });
+                                    } else {
+                                        let field_names: Vec<String> = struct_config
+                                            .fields
+                                            .iter()
+                                            .map(|f| f.field_name.clone())
+                                            .collect();
+                                        work_stack.push(WorkItem::AssembleStruct { field_names });
+                                    }
+
                                     for struct_field in struct_config.fields.iter().rev() {
                                         let new_ctx = Frame {
-                                            field: struct_field,
+                                            field: Cow::Borrowed(struct_field),
                                             field_type: &struct_field.field_type,
                                             field_path: format!(
                                                 "{}.{}",
@@ -288,16 +499,16 @@ impl<'a> FieldValueGenerator<'a> {
                                             ),
                                             table_config: ctx.table_config,
                                             visited_types: new_visited.clone(),
+                                            constrained: true,
+                                            collection_item: false,
                                         };
                                         work_stack.push(WorkItem::Generate(new_ctx));
                                     }
                                 } else if let Some(tagged_union) =
                                     self.mockmaker.enums.get(type_name)
                                 {
-                                    let variant = tagged_union
-                                        .variants
-                                        .choose(&mut rng)
-                                        .expect("Failed to select a random enum variant");
+                                    let variant =
+                                        self.choose_variant(&ctx, &tagged_union.variants, &mut rng);
                                     if let Some(ref variant_data) = variant.data {
                                         // This logic is now restructured.
                                         match variant_data {
@@ -320,7 +531,7 @@ impl<'a> FieldValueGenerator<'a> {
                                                     struct_config.fields.iter().rev()
                                                 {
                                                     let new_ctx = Frame {
-                                                        field: struct_field,
+                                                        field: Cow::Borrowed(struct_field),
                                                         field_type: &struct_field.field_type,
                                                         field_path: format!(
                                                             "{}.{}",
@@ -329,6 +540,8 @@ impl<'a> FieldValueGenerator<'a> {
                                                         ),
                                                         table_config: ctx.table_config,
                                                         visited_types: new_visited.clone(),
+                                                        constrained: true,
+                                                        collection_item: false,
                                                     };
                                                     work_stack.push(WorkItem::Generate(new_ctx));
                                                 }
@@ -337,6 +550,7 @@ impl<'a> FieldValueGenerator<'a> {
                                                 work_stack.push(WorkItem::AssembleEnum);
                                                 work_stack.push(WorkItem::Generate(Frame {
                                                     field_type,
+                                                    constrained: false,
                                                     ..ctx.clone()
                                                 }));
                                             }
@@ -358,6 +572,11 @@ impl<'a> FieldValueGenerator<'a> {
                     let items: Vec<_> = value_stack.drain(value_stack.len() - count..).collect();
                     value_stack.push(format!("[{}]", items.join(", ")));
                 }
+                WorkItem::AssembleSet { count } => {
+                    // Duplicates are dropped by the cast
+                    let items: Vec<_> = value_stack.drain(value_stack.len() - count..).collect();
+                    value_stack.push(format!("<set>[{}]", items.join(", ")));
+                }
                 WorkItem::AssembleTuple { count } => {
                     let items: Vec<_> = value_stack.drain(value_stack.len() - count..).collect();
                     value_stack.push(format!("({})", items.join(", ")));
@@ -396,33 +615,149 @@ impl<'a> FieldValueGenerator<'a> {
         value_stack.pop().unwrap()
     }
 
-    pub fn handle_format(&self, format: &Format) -> String {
-        let generated = format.generate_formatted_value();
+    /// The value of `format` for the field at `field_path`, in the field's locale
+    pub fn handle_format(
+        &self,
+        format: &Format,
+        field_path: &str,
+        rng: &mut MockRng,
+        now: DateTime<Utc>,
+    ) -> String {
+        let locale = self
+            .mockmaker
+            .locale_for(&self.table_config.table_name, field_path);
+        let context = GeneratorContext {
+            table_name: &self.table_config.table_name,
+            field_path,
+            index: *self.id_index,
+            locale,
+            now,
+        };
+        let generated = format
+            .generate_custom_value(&context, rng)
+            .unwrap_or_else(|| format.generate_localized_value(locale, rng, now));
         match format {
             Format::Percentage
             | Format::Latitude
             | Format::Longitude
             | Format::CurrencyAmount
-            | Format::AppointmentDurationNs => generated,
-            Format::DateTime | Format::AppointmentDateTime | Format::DateWithinDays(_) => {
+            | Format::AppointmentDurationNs
+            | Format::Normal(..)
+            | Format::LogNormal(..)
+            | Format::Exponential(_)
+            | Format::Zipf(..) => generated,
+            Format::DateTime
+            | Format::AppointmentDateTime
+            | Format::DateWithinDays(_)
+            | Format::Seasonal(..) => {
                 format!("d'{}'", generated)
             }
+            // Registered generators may produce any string
+            Format::CustomFn(_) => format!("'{}'", generated.replace('\'', "\\'")),
             _ => format!("'{}'", generated),
         }
     }
 
+    /// The distribution of the value `ctx` generates: the field's distribution format, or
+    /// the one the table's mock data config sets for the field
+    fn distribution<'f>(&'f self, ctx: &'f Frame<'a>) -> Option<&'f Format> {
+        if let Some(format) = &ctx.field.format {
+            return format.is_distribution().then_some(format);
+        }
+        self.table_config
+            .mock_generation_config
+            .as_ref()?
+            .distributions
+            .get(&ctx.field_path)
+    }
+
+    /// A value drawn from the field's distribution, `None` when it has none or the type
+    /// isn't generated from it directly
+    fn distributed_value(
+        &self,
+        ctx: &Frame<'a>,
+        rng: &mut MockRng,
+        now: DateTime<Utc>,
+    ) -> Option<String> {
+        let distribution = self.distribution(ctx)?;
+        let constraints = if ctx.constrained {
+            ValueConstraints::from_validators(&ctx.field.validators)
+        } else {
+            ValueConstraints::default()
+        };
+        distribution.generate_value_for(ctx.field_type, &constraints, rng, now)
+    }
+
+    /// An enum variant, picked by the field's `Weighted` distribution when it has one
+    fn choose_variant<'v>(
+        &self,
+        ctx: &Frame<'a>,
+        variants: &'v [Variant],
+        rng: &mut MockRng,
+    ) -> &'v Variant {
+        if let Some(name) = self
+            .distribution(ctx)
+            .and_then(|distribution| distribution.choose_weighted(rng))
+        {
+            match variants.iter().find(|variant| variant.name == name) {
+                Some(variant) => return variant,
+                None => tracing::warn!(
+                    field_path = %ctx.field_path,
+                    variant = %name,
+                    "Weighted variant does not exist, picking a random one"
+                ),
+            }
+        }
+        variants
+            .choose(rng)
+            .expect("Failed to select a random enum variant")
+    }
+
+    /// The cardinality plan of the reference at `field_path`. Rules apply to the table's
+    /// own fields, not to fields nested in objects.
+    fn reference_plan(&self, field_path: &str) -> Option<&ReferencePlan> {
+        if field_path != self.field.field_name {
+            return None;
+        }
+        self.mockmaker
+            .reference_plans
+            .get(&(self.table_config.table_name.clone(), field_path.to_string()))
+    }
+
+    /// The record a reference points at: as planned by the field's cardinality rule,
+    /// uniformly at random without one
+    fn pick_reference(
+        &self,
+        field_path: &str,
+        collection_item: bool,
+        ids: &[String],
+        rng: &mut MockRng,
+    ) -> String {
+        let planned = self.reference_plan(field_path).and_then(|plan| {
+            if collection_item {
+                plan.pick(rng)
+            } else {
+                plan.target(*self.id_index).flatten()
+            }
+        });
+        match planned {
+            Some(id) => id.to_string(),
+            None => ids[rng.random_range(0..ids.len())].clone(),
+        }
+    }
+
     fn handle_record_id(
         &self,
         field_name: &str,
         table_name: &str,
         table_config: &TableConfig,
-        rng: &mut ThreadRng,
+        rng: &mut MockRng,
     ) -> String {
         if table_config.relation.is_some() && field_name == "in" {
             let from_table = &table_config.relation.as_ref().unwrap().from;
             if let Some(ids) = self.mockmaker.id_map.get(from_table) {
                 if !ids.is_empty() {
-                    format!("r'{}'", ids[rng.random_range(0..ids.len())].clone())
+                    format!("r'{}'", self.pick_reference(field_name, false, ids, rng))
                 } else {
                     format!("r'{}:1'", from_table.to_lowercase())
                 }
@@ -439,7 +774,7 @@ impl<'a> FieldValueGenerator<'a> {
             let to_table = &table_config.relation.as_ref().unwrap().to;
             if let Some(ids) = self.mockmaker.id_map.get(to_table) {
                 if !ids.is_empty() {
-                    format!("r'{}'", ids[rng.random_range(0..ids.len())].clone())
+                    format!("r'{}'", self.pick_reference(field_name, false, ids, rng))
                 } else {
                     panic!(
                         "{}",
@@ -469,3 +804,61 @@ impl<'a> FieldValueGenerator<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        schemasync::{StructConfig, config::SchemasyncConfig},
+        validator::{StringValidator, Validator},
+    };
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn test_newtype_keeps_outer_and_own_validators() {
+        let code = StructConfig {
+            struct_name: "Code".to_string(),
+            fields: vec![StructField {
+                field_type: FieldType::String,
+                ..StructField::unit("0".to_string())
+            }],
+            validators: vec![Validator::StringValidator(StringValidator::Digits)],
+            generics: Vec::new(),
+            kind: StructKind::Newtype,
+        };
+        let field = StructField {
+            field_type: FieldType::Other("Code".to_string()),
+            validators: vec![Validator::StringValidator(StringValidator::MinLength(20))],
+            ..StructField::unit("code".to_string())
+        };
+        let table_config = TableConfig {
+            table_name: "voucher".to_string(),
+            struct_config: StructConfig::for_testing("Voucher", vec![field.clone()]),
+            relation: None,
+            permissions: None,
+            mock_generation_config: None,
+        };
+        let config = SchemasyncConfig::for_testing();
+        let db = surrealdb::engine::any::connect("mem://").await.unwrap();
+        let mockmaker = Mockmaker::new(
+            db,
+            HashMap::from([("voucher".to_string(), table_config.clone())]),
+            HashMap::from([("Code".to_string(), code)]),
+            HashMap::new(),
+            config,
+        );
+
+        for id_index in 0..10 {
+            let value = FieldValueGenerator::builder()
+                .mockmaker(&mockmaker)
+                .table_config(&table_config)
+                .field(&field)
+                .id_index(&id_index)
+                .build()
+                .run();
+            let code = value.trim_matches('\'');
+            assert!(code.len() >= 20, "{value}");
+            assert!(code.chars().all(|c| c.is_ascii_digit()), "{value}");
+        }
+    }
+}
@@ -1,13 +1,20 @@
 use crate::{
     format::Format,
-    mockmake::Mockmaker,
+    mockmake::{
+        Mockmaker,
+        constraints::ValueConstraints,
+        custom::GeneratorContext,
+        geometry::{random_center, random_geometry},
+        rng::{MockRng, random_uuid},
+    },
     schemasync::TableConfig,
-    types::{FieldType, StructConfig, StructField, TaggedUnion, VariantData},
+    types::{FieldType, StructConfig, StructField, StructKind, TaggedUnion, VariantData},
 };
 use bon::Builder;
+use chrono::{DateTime, Utc};
 use chrono_tz::TZ_VARIANTS;
 use convert_case::{Case, Casing};
-use rand::{Rng, rngs::ThreadRng, seq::IndexedRandom};
+use rand::{Rng, seq::IndexedRandom};
 use std::collections::HashMap;
 
 #[derive(Debug, Builder)]
@@ -21,21 +28,57 @@ pub struct FieldValueGenerator<'a> {
 
 impl<'a> FieldValueGenerator<'a> {
     pub fn run(&self) -> String {
+        // One stream per record and field, like the iterative generator
+        let table_name = &self.table_config.table_name;
+        let mut rng = self.mockmaker.rng_for(
+            table_name,
+            &format!("{}#{}", self.field.field_name, self.id_index),
+        );
+        let now = self.mockmaker.reference_time(table_name);
+        if let Some(format) = &self.field.format
+            && !format.is_distribution()
+        {
+            return self.handle_format(format, &mut rng, now);
+        }
+        self.generate_field_value(&self.field.field_type, &mut rng, now)
+    }
+
+    /// The field's distribution format, or the one the table's mock data config sets for
+    /// the field
+    fn distribution(&self) -> Option<&'a Format> {
         if let Some(format) = &self.field.format {
-            return self.handle_format(format);
+            return format.is_distribution().then_some(format);
         }
-        self.generate_field_value(&self.field.field_type)
+        self.table_config
+            .mock_generation_config
+            .as_ref()?
+            .distributions
+            .get(&self.field.field_name)
     }
 
-    pub fn generate_field_value(&self, field_type: &FieldType) -> String {
+    pub fn generate_field_value(
+        &self,
+        field_type: &FieldType,
+        rng: &mut MockRng,
+        now: DateTime<Utc>,
+    ) -> String {
         tracing::trace!(
             field_name = %self.field.field_name,
             field_type = ?self.field.field_type,
             "Generating field value"
         );
-        let mut rng = rand::rng();
+        if let Some(value) = self.distribution().and_then(|distribution| {
+            distribution.generate_value_for(
+                field_type,
+                &ValueConstraints::from_validators(&self.field.validators),
+                rng,
+                now,
+            )
+        }) {
+            return value;
+        }
         match field_type {
-            FieldType::String => format!("'{}'", Mockmaker::random_string(8)),
+            FieldType::String => format!("'{}'", Mockmaker::random_string_with(rng, 8)),
 
             FieldType::Char => {
                 let c = rng.random_range(32u8..=126u8) as char;
@@ -64,7 +107,7 @@ impl<'a> FieldValueGenerator<'a> {
             | FieldType::U128
             | FieldType::Usize => format!("{}", rng.random_range(0..100)),
 
-            FieldType::DateTime => format!("d'{}'", chrono::Utc::now().to_rfc3339()),
+            FieldType::DateTime => format!("d'{}'", now.to_rfc3339()),
 
             FieldType::EvenframeDuration => {
                 // Generate random duration in nanoseconds (0 to 1 day in nanos)
@@ -76,29 +119,77 @@ impl<'a> FieldValueGenerator<'a> {
                 let tz = &TZ_VARIANTS[rng.random_range(0..TZ_VARIANTS.len())];
                 format!("'{}'", tz.name())
             }
-            FieldType::EvenframeRecordId => self.handle_record_id(
-                &self.field.field_name,
-                &self.table_config.table_name,
-                &mut rng,
+            FieldType::Uuid => format!("u'{}'", random_uuid(rng)),
+            FieldType::NaiveDate => {
+                let date = now.date_naive() - chrono::Days::new(rng.random_range(0..3650));
+                format!("d'{}'", date.format("%Y-%m-%d"))
+            }
+            FieldType::NaiveTime => format!(
+                "'{:02}:{:02}:{:02}'",
+                rng.random_range(0..24),
+                rng.random_range(0..60),
+                rng.random_range(0..60)
             ),
+            FieldType::Duration => {
+                let nanos = rng.random_range(0..86_400_000_000_000i64);
+                format!("duration::from::nanos({})", nanos)
+            }
+            FieldType::Bytes => {
+                let bytes: Vec<String> = (0..rng.random_range(4..32))
+                    .map(|_| rng.random::<u8>().to_string())
+                    .collect();
+                format!("<bytes>[{}]", bytes.join(", "))
+            }
+            FieldType::JsonValue => {
+                format!("{{ value: '{}' }}", Mockmaker::random_string_with(rng, 8))
+            }
+            FieldType::IpAddr => self.handle_format(&Format::IpAddress, rng, now),
+            FieldType::Url => self.handle_format(&Format::Url("example.com".to_string()), rng, now),
+            FieldType::Geometry(kind) => {
+                let center = random_center(rng);
+                random_geometry(*kind, center, rng)
+            }
+            FieldType::EvenframeRecordId => {
+                self.handle_record_id(&self.field.field_name, &self.table_config.table_name, rng)
+            }
             // For an Option, randomly decide whether to generate a value or use NULL.
-            FieldType::Option(inner_type) => self.handle_option(inner_type, &mut rng),
+            FieldType::Option(inner_type) => self.handle_option(inner_type, rng, now),
             // For a vector, generate a dummy array with a couple of elements.
-            FieldType::Vec(inner_type) => self.handle_vec(inner_type),
+            FieldType::Vec(inner_type) => self.handle_vec(inner_type, rng, now),
+            // Sets are generated like vectors and deduplicated by the cast
+            FieldType::HashSet(inner_type) | FieldType::BTreeSet(inner_type) => {
+                format!("<set>{}", self.handle_vec(inner_type, rng, now))
+            }
             // For a tuple, recursively generate values for each component.
-            FieldType::Tuple(types) => self.handle_tuple(types),
+            FieldType::Tuple(types) => self.handle_tuple(types, rng, now),
             // For a struct (named fields), create a JSON-like object.
-            FieldType::Struct(fields) => self.handle_struct(fields),
-            FieldType::HashMap(key, value) => self.handle_hash_map(key, value),
-            FieldType::BTreeMap(key, value) => self.handle_b_tree_map(key, value),
-            FieldType::RecordLink(inner_type) => self.generate_field_value(inner_type),
+            FieldType::Struct(fields) => self.handle_struct(fields, rng, now),
+            FieldType::HashMap(key, value) | FieldType::BTreeMap(key, value) => {
+                self.handle_map(key, value, rng, now)
+            }
+            FieldType::RecordLink(inner_type) => self.generate_field_value(inner_type, rng, now),
             // For other types, try to see if the type is actually a reference to another db table/app struct, a app-only struct, or an enum.
-            FieldType::Other(type_name) => self.handle_other(type_name, &mut rng),
+            FieldType::Other(type_name) => self.handle_other(type_name, rng, now),
+            // Generic instantiations are registered under their display name by the Mockmaker
+            FieldType::Generic(..) => self.handle_other(&field_type.to_string(), rng, now),
+            FieldType::GenericParam(_) => "null".to_string(),
         }
     }
 
-    pub fn handle_format(&self, format: &Format) -> String {
-        let generated = format.generate_formatted_value();
+    pub fn handle_format(&self, format: &Format, rng: &mut MockRng, now: DateTime<Utc>) -> String {
+        let locale = self
+            .mockmaker
+            .locale_for(&self.table_config.table_name, &self.field.field_name);
+        let context = GeneratorContext {
+            table_name: &self.table_config.table_name,
+            field_path: &self.field.field_name,
+            index: *self.id_index,
+            locale,
+            now,
+        };
+        let generated = format
+            .generate_custom_value(&context, rng)
+            .unwrap_or_else(|| format.generate_localized_value(locale, rng, now));
 
         // Check if format generates numeric or boolean values that shouldn't be quoted
         match format {
@@ -107,12 +198,22 @@ impl<'a> FieldValueGenerator<'a> {
             | Format::Latitude
             | Format::Longitude
             | Format::CurrencyAmount
-            | Format::AppointmentDurationNs => generated,
+            | Format::AppointmentDurationNs
+            | Format::Normal(..)
+            | Format::LogNormal(..)
+            | Format::Exponential(_)
+            | Format::Zipf(..) => generated,
 
-            Format::DateTime | Format::AppointmentDateTime | Format::DateWithinDays(_) => {
+            Format::DateTime
+            | Format::AppointmentDateTime
+            | Format::DateWithinDays(_)
+            | Format::Seasonal(..) => {
                 format!("d'{}'", generated)
             }
 
+            // Registered generators may produce any string
+            Format::CustomFn(_) => format!("'{}'", generated.replace('\'', "\\'")),
+
             // Most formats generate strings, quote them
             _ => format!("'{}'", generated),
         }
@@ -122,7 +223,7 @@ impl<'a> FieldValueGenerator<'a> {
         &self,
         field_name: &String,
         table_name: &String,
-        rng: &mut ThreadRng,
+        rng: &mut MockRng,
     ) -> String {
         {
             if self.table_config.relation.is_none() && field_name == "id" {
@@ -195,32 +296,42 @@ impl<'a> FieldValueGenerator<'a> {
         }
     }
 
-    fn handle_option(&self, inner_type: &FieldType, rng: &mut ThreadRng) -> String {
+    fn handle_option(
+        &self,
+        inner_type: &FieldType,
+        rng: &mut MockRng,
+        now: DateTime<Utc>,
+    ) -> String {
         if rng.random_bool(0.5) {
             "null".to_string()
         } else {
-            self.generate_field_value(inner_type)
+            self.generate_field_value(inner_type, rng, now)
         }
     }
 
-    fn handle_vec(&self, inner_type: &FieldType) -> String {
-        let count = rand::rng().random_range(2..10);
+    fn handle_vec(&self, inner_type: &FieldType, rng: &mut MockRng, now: DateTime<Utc>) -> String {
+        let count = rng.random_range(2..10);
 
         let items: Vec<String> = (0..count)
-            .map(|_| self.generate_field_value(inner_type))
+            .map(|_| self.generate_field_value(inner_type, rng, now))
             .collect();
         format!("[{}]", items.join(", "))
     }
 
-    fn handle_tuple(&self, types: &[FieldType]) -> String {
+    fn handle_tuple(&self, types: &[FieldType], rng: &mut MockRng, now: DateTime<Utc>) -> String {
         let values: Vec<String> = types
             .iter()
-            .map(|inner_type| self.generate_field_value(inner_type))
+            .map(|inner_type| self.generate_field_value(inner_type, rng, now))
             .collect();
         format!("({})", values.join(", "))
     }
 
-    fn handle_struct(&self, fields: &[(String, FieldType)]) -> String {
+    fn handle_struct(
+        &self,
+        fields: &[(String, FieldType)],
+        rng: &mut MockRng,
+        now: DateTime<Utc>,
+    ) -> String {
         // Build nested coordination context
         let mut nested_coordinated_values = HashMap::new();
         let field_prefix = format!("{}.", self.field.field_name);
@@ -245,7 +356,7 @@ impl<'a> FieldValueGenerator<'a> {
                         _ => coord_value.clone(),
                     }
                 } else {
-                    self.generate_field_value(ftype)
+                    self.generate_field_value(ftype, rng, now)
                 };
                 format!("{}: {}", fname, value)
             })
@@ -253,31 +364,25 @@ impl<'a> FieldValueGenerator<'a> {
         format!("{{ {} }}", field_values.join(", "))
     }
 
-    fn handle_hash_map(&self, key_ft: &FieldType, value_ft: &FieldType) -> String {
-        let count = rand::rng().random_range(0..3);
-        let entries: Vec<String> = (0..count)
-            .map(|_| {
-                let key_string = self.generate_field_value(key_ft);
-                let value_string = self.generate_field_value(value_ft);
-                format!("{}: {}", key_string, value_string)
-            })
-            .collect();
-        format!("{{ {} }}", entries.join(", "))
-    }
-
-    fn handle_b_tree_map(&self, key_ft: &FieldType, value_ft: &FieldType) -> String {
-        let count = rand::rng().random_range(0..3);
+    fn handle_map(
+        &self,
+        key_ft: &FieldType,
+        value_ft: &FieldType,
+        rng: &mut MockRng,
+        now: DateTime<Utc>,
+    ) -> String {
+        let count = rng.random_range(0..3);
         let entries: Vec<String> = (0..count)
             .map(|_| {
-                let key_string = self.generate_field_value(key_ft);
-                let value_string = self.generate_field_value(value_ft);
+                let key_string = self.generate_field_value(key_ft, rng, now);
+                let value_string = self.generate_field_value(value_ft, rng, now);
                 format!("{}: {}", key_string, value_string)
             })
             .collect();
         format!("{{ {} }}", entries.join(", "))
     }
 
-    fn handle_other(&self, type_name: &String, rng: &mut ThreadRng) -> String {
+    fn handle_other(&self, type_name: &String, rng: &mut MockRng, now: DateTime<Utc>) -> String {
         let snake_case_name = type_name.to_case(Case::Snake);
         // First try to find by matching table-struct name
         if let Some((table_name, _)) = self
@@ -295,7 +400,7 @@ impl<'a> FieldValueGenerator<'a> {
         {
             self.handle_object(struct_config)
         } else if let Some(tagged_union) = self.mockmaker.enums.get(type_name) {
-            self.handle_enum(tagged_union, rng)
+            self.handle_enum(tagged_union, rng, now)
         } else {
             panic!(
                 "{}",
@@ -307,7 +412,7 @@ impl<'a> FieldValueGenerator<'a> {
         }
     }
 
-    fn handle_table(&self, table_name: &String, rng: &mut ThreadRng) -> String {
+    fn handle_table(&self, table_name: &String, rng: &mut MockRng) -> String {
         if let Some(possible_ids) = self.mockmaker.id_map.get(table_name) {
             let idx = rng.random_range(0..possible_ids.len());
             format!("r'{}'", possible_ids[idx])
@@ -323,11 +428,29 @@ impl<'a> FieldValueGenerator<'a> {
         }
     }
 
-    fn handle_enum(&self, tagged_union: &TaggedUnion, rng: &mut ThreadRng) -> String {
-        let variant = tagged_union
-            .variants
-            .choose(rng)
-            .expect("Something went wrong selecting a random enum variant, returned None");
+    fn handle_enum(
+        &self,
+        tagged_union: &TaggedUnion,
+        rng: &mut MockRng,
+        now: DateTime<Utc>,
+    ) -> String {
+        // A `Weighted` distribution picks the variant by name
+        let weighted = self
+            .distribution()
+            .and_then(|distribution| distribution.choose_weighted(rng))
+            .and_then(|name| {
+                tagged_union
+                    .variants
+                    .iter()
+                    .find(|variant| variant.name == name)
+            });
+        let variant = match weighted {
+            Some(variant) => variant,
+            None => tagged_union
+                .variants
+                .choose(rng)
+                .expect("Something went wrong selecting a random enum variant, returned None"),
+        };
         if let Some(ref variant_data) = variant.data {
             // Generate dummy value for the enum variant's data, if available.
             let variant_data_field_type = match variant_data {
@@ -336,14 +459,14 @@ impl<'a> FieldValueGenerator<'a> {
                 }
                 VariantData::DataStructureRef(field_type) => field_type,
             };
-            self.generate_field_value(variant_data_field_type)
+            self.generate_field_value(variant_data_field_type, rng, now)
         } else {
             format!("'{}'", variant.name)
         }
     }
 
     fn handle_object(&self, struct_config: &StructConfig) -> String {
-        let mut assignments = Vec::new();
+        let mut values = Vec::new();
         for struct_field in &struct_config.fields {
             let val = Self::builder()
                 .coordinated_values(self.coordinated_values)
@@ -354,9 +477,31 @@ impl<'a> FieldValueGenerator<'a> {
                 .build()
                 .run();
 
-            assignments.push(format!("{}: {val}", struct_field.field_name));
+            values.push((&struct_field.field_name, val));
+        }
+        match struct_config.kind {
+            // Newtypes are transparent
+            StructKind::Newtype => values
+                .pop()
+                .map(|(_, val)| val)
+                .unwrap_or_else(|| "null".to_string()),
+            // Tuple structs are stored as arrays
+            StructKind::Tuple => format!(
+                "[{}]",
+                values
+                    .into_iter()
+                    .map(|(_, val)| val)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            StructKind::Named => {
+                let assignments: Vec<String> = values
+                    .into_iter()
+                    .map(|(name, val)| format!("{}: {val}", name))
+                    .collect();
+                // Surreal accepts JSON-like objects with unquoted keys:
+                format!("{{ {} }}", assignments.join(", "))
+            }
         }
-        // Surreal accepts JSON-like objects with unquoted keys:
-        format!("{{ {} }}", assignments.join(", "))
     }
 }
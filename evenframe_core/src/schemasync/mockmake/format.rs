@@ -1,14 +1,14 @@
-use super::regex_val_gen::RegexValGen;
-use chrono::{Datelike, Duration, Utc};
+use super::{custom::GeneratorContext, locale::Locale, regex_val_gen::RegexValGen, rng::MockRng};
+use chrono::{DateTime, Datelike, Duration, Utc};
+use ordered_float::OrderedFloat;
 use quote::{quote, ToTokens};
 use regex::Regex;
 use tracing;
 use try_from_expr::TryFromExpr;
 
-/// Generate a regex pattern for dates within a specified number of days from now
-fn generate_date_range_pattern(days: i64) -> String {
+/// Generate a regex pattern for dates within a specified number of days from `now`
+fn generate_date_range_pattern(now: DateTime<Utc>, days: i64) -> String {
     tracing::trace!(days = days, "Generating date range pattern");
-    let now = Utc::now();
 
     // Collect all valid dates in the range
     let mut date_patterns = Vec::new();
@@ -119,32 +119,73 @@ pub enum Format {
     TailwindColorSet(Option<String>), // Optional color name for seeding
     /// Custom format with a user-provided pattern
     Custom(String),
+    /// Generate with the `MockValueGenerator` registered under this name, see
+    /// `custom::register_generator`
+    CustomFn(String),
     /// Generate a completely random string of 8-16 characters
     Random,
     /// Generate appointment duration in nanoseconds (1-5 hours in 15-minute increments)
     AppointmentDurationNs,
+    /// Generate a number from a normal distribution (mean, standard deviation)
+    Normal(OrderedFloat<f64>, OrderedFloat<f64>),
+    /// Generate a number from a log-normal distribution (mean and standard deviation of
+    /// the logarithm)
+    LogNormal(OrderedFloat<f64>, OrderedFloat<f64>),
+    /// Generate a number from an exponential distribution (rate)
+    Exponential(OrderedFloat<f64>),
+    /// Generate a rank from 1 to n following Zipf's law (n, exponent)
+    Zipf(u64, OrderedFloat<f64>),
+    /// Pick one of the values (or enum variants) by weight; missing weights are 1
+    Weighted(Vec<String>, Vec<u32>),
+    /// Generate a datetime within the past N days, weighted by hour of day (24 weights),
+    /// day of week (7 weights, Monday first) and month (12 weights); empty weights are
+    /// uniform
+    Seasonal(i64, Vec<u32>, Vec<u32>, Vec<u32>),
 }
 
 impl Format {
     /// Helper function to generate a value from regex pattern
-    fn generate_from_regex(&self) -> String {
+    fn generate_from_regex(&self, rng: &mut MockRng, now: DateTime<Utc>) -> String {
         tracing::trace!(format = ?self, "Generating value from regex pattern");
-        let regex: Regex = self.clone().into();
+        let regex = self.clone().regex_at(now);
         let pattern = regex.as_str();
 
-        let mut maker = RegexValGen::new();
+        let mut maker = RegexValGen::from_rng(rng.clone());
 
         let result = maker
             .generate(pattern)
             .unwrap_or_else(|e| panic!("Failed to generate value for {:?}: {}", self, e));
 
+        // Continue the caller's stream so the next value differs from this one
+        *rng = maker.into_rng();
+
         tracing::trace!(value_length = result.len(), "Generated value from regex");
         result
     }
 
     pub fn generate_formatted_value(&self) -> String {
+        let mut rng = super::rng::mock_rng(None, "");
+        self.generate_formatted_value_with(&mut rng, Utc::now())
+    }
+
+    /// Generate a value drawing from `rng`, with relative dates counted from `now`
+    pub fn generate_formatted_value_with(&self, rng: &mut MockRng, now: DateTime<Utc>) -> String {
         tracing::debug!(format = ?self, "Generating formatted value");
-        self.generate_from_regex()
+        if let Some(value) = self.generate_distribution_value(rng, now) {
+            return value;
+        }
+        // Without a field to generate for, custom generators get an empty context
+        let context = GeneratorContext {
+            table_name: "",
+            field_path: "",
+            index: 0,
+            locale: &Locale::default(),
+            now,
+        };
+        if let Some(value) = self.generate_custom_value(&context, rng) {
+            return value;
+        }
+        self.generate_from_regex(rng, now)
     }
 
     /// Convert this Format into a Regex
@@ -156,6 +197,14 @@ impl Format {
 
 impl From<Format> for Regex {
     fn from(format: Format) -> Self {
+        format.regex_at(Utc::now())
+    }
+}
+
+impl Format {
+    /// Convert this Format into a Regex, with relative date ranges starting at `now`
+    pub fn regex_at(self, now: DateTime<Utc>) -> Regex {
+        let format = self;
         tracing::trace!(format = ?format, "Creating regex from format");
         let pattern = match format {
             Format::Uuid => {
@@ -237,13 +286,13 @@ impl From<Format> for Regex {
             }
             Format::DateWithinDays(days) => {
                 // Use the helper function to generate date range pattern
-                let date_pattern = generate_date_range_pattern(days);
+                let date_pattern = generate_date_range_pattern(now, days);
 
                 &format!(r"^{}T([01][0-9]|2[0-3]):(00|15|30|45):00Z$", date_pattern)
             }
             Format::AppointmentDateTime => {
                 // Use the helper function to generate date range pattern for next 10 days
-                let date_pattern = generate_date_range_pattern(10);
+                let date_pattern = generate_date_range_pattern(now, 10);
 
                 // Create a regex pattern for appointment hours (7am-8pm)
                 &format!(r"^{}T(0[7-9]|1[0-9]|20):(00|15|30|45):00Z$", date_pattern)
@@ -252,6 +301,8 @@ impl From<Format> for Regex {
                 r#"^\{\"main\":\"\#[0-9a-fA-F]{6}\",\"hover\":\"\#[0-9a-fA-F]{6}\",\"active\":\"\#[0-9a-fA-F]{6}\"\}$"#
             }
             Format::Custom(pattern) => &pattern.clone(),
+            // Registered generators may produce any value
+            Format::CustomFn(_) => r"^.*$",
 
             Format::Random => r"^[a-zA-Z0-9]{8,16}$",
 
@@ -263,6 +314,19 @@ impl From<Format> for Regex {
                 // Exhaustive list of all valid values wrapped in duration::from::nanos():
                 r"^(duration::from::nanos\(3600000000000\)|duration::from::nanos\(4500000000000\)|duration::from::nanos\(5400000000000\)|duration::from::nanos\(6300000000000\)|duration::from::nanos\(7200000000000\)|duration::from::nanos\(8100000000000\)|duration::from::nanos\(9000000000000\)|duration::from::nanos\(9900000000000\)|duration::from::nanos\(10800000000000\)|duration::from::nanos\(11700000000000\)|duration::from::nanos\(12600000000000\)|duration::from::nanos\(13500000000000\)|duration::from::nanos\(14400000000000\)|duration::from::nanos\(15300000000000\)|duration::from::nanos\(16200000000000\)|duration::from::nanos\(17100000000000\)|duration::from::nanos\(18000000000000\))$"
             }
+            Format::Normal(..) | Format::LogNormal(..) | Format::Exponential(_) => {
+                r"^-?\d+\.\d{2}$"
+            }
+            Format::Zipf(..) => r"^\d+$",
+            Format::Weighted(values, _) => &format!(
+                "^({})$",
+                values
+                    .iter()
+                    .map(|value| regex::escape(value))
+                    .collect::<Vec<_>>()
+                    .join("|")
+            ),
+            Format::Seasonal(..) => r"^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}Z$",
         };
 
         let regex = Regex::new(pattern).expect("Failed to create regex from Format");
@@ -388,12 +452,37 @@ impl ToTokens for Format {
             Format::Custom(pattern) => {
                 quote! { ::evenframe::schemasync::format::Format::Custom(#pattern.to_string()) }
             }
+            Format::CustomFn(name) => {
+                quote! { ::evenframe::schemasync::format::Format::CustomFn(#name.to_string()) }
+            }
             Format::Random => {
                 quote! { ::evenframe::schemasync::format::Format::Random }
             }
             Format::AppointmentDurationNs => {
                 quote! { ::evenframe::schemasync::format::Format::AppointmentDurationNs }
             }
+            Format::Normal(mean, std_dev) => {
+                let (mean, std_dev) = (mean.0, std_dev.0);
+                quote! { ::evenframe::schemasync::format::Format::Normal(::ordered_float::OrderedFloat(#mean), ::ordered_float::OrderedFloat(#std_dev)) }
+            }
+            Format::LogNormal(mu, sigma) => {
+                let (mu, sigma) = (mu.0, sigma.0);
+                quote! { ::evenframe::schemasync::format::Format::LogNormal(::ordered_float::OrderedFloat(#mu), ::ordered_float::OrderedFloat(#sigma)) }
+            }
+            Format::Exponential(rate) => {
+                let rate = rate.0;
+                quote! { ::evenframe::schemasync::format::Format::Exponential(::ordered_float::OrderedFloat(#rate)) }
+            }
+            Format::Zipf(n, exponent) => {
+                let exponent = exponent.0;
+                quote! { ::evenframe::schemasync::format::Format::Zipf(#n, ::ordered_float::OrderedFloat(#exponent)) }
+            }
+            Format::Weighted(values, weights) => {
+                quote! { ::evenframe::schemasync::format::Format::Weighted(vec![#(#values.to_string()),*], vec![#(#weights),*]) }
+            }
+            Format::Seasonal(days, hours, weekdays, months) => {
+                quote! { ::evenframe::schemasync::format::Format::Seasonal(#days, vec![#(#hours),*], vec![#(#weekdays),*], vec![#(#months),*]) }
+            }
         };
 
         tokens.extend(variant_tokens);
@@ -0,0 +1,141 @@
+use crate::types::GeometryKind;
+use crate::wrappers::Position;
+use rand::{Rng, seq::IndexedRandom};
+
+/// Spread of generated shapes around their center, in degrees (roughly a kilometre)
+const SHAPE_RADIUS: f64 = 0.01;
+
+/// Pick a random center anywhere on the map, away from the poles
+pub fn random_center<R: Rng>(rng: &mut R) -> Position {
+    [
+        rng.random_range(-180.0..180.0),
+        rng.random_range(-85.0..85.0),
+    ]
+}
+
+/// Generate a SurrealQL literal for a valid geometry of `kind` located around `center`
+pub fn random_geometry<R: Rng>(kind: GeometryKind, center: Position, rng: &mut R) -> String {
+    tracing::trace!(kind = ?kind, center = ?center, "Generating geometry value");
+    let kind = if kind == GeometryKind::Any {
+        *[
+            GeometryKind::Point,
+            GeometryKind::LineString,
+            GeometryKind::Polygon,
+            GeometryKind::MultiPoint,
+            GeometryKind::MultiLineString,
+            GeometryKind::MultiPolygon,
+        ]
+        .choose(rng)
+        .expect("Geometry kinds should not be empty")
+    } else {
+        kind
+    };
+
+    match kind {
+        GeometryKind::Point | GeometryKind::Any => point_literal(center),
+        GeometryKind::LineString => geojson(kind, positions(&line(center, rng))),
+        GeometryKind::Polygon => geojson(kind, format!("[{}]", positions(&ring(center, rng)))),
+        GeometryKind::MultiPoint => {
+            let count = rng.random_range(2..6);
+            let points: Vec<Position> = (0..count).map(|_| jitter(center, rng)).collect();
+            geojson(kind, positions(&points))
+        }
+        GeometryKind::MultiLineString => {
+            let count = rng.random_range(2..4);
+            let lines: Vec<String> = (0..count)
+                .map(|_| {
+                    let start = jitter(center, rng);
+                    positions(&line(start, rng))
+                })
+                .collect();
+            geojson(kind, format!("[{}]", lines.join(", ")))
+        }
+        GeometryKind::MultiPolygon => {
+            // Polygons are placed side by side so they never overlap
+            let count = rng.random_range(2..4);
+            let polygons: Vec<String> = (0..count)
+                .map(|i| {
+                    let offset = [center[0] + 3.0 * SHAPE_RADIUS * i as f64, center[1]];
+                    format!("[{}]", positions(&ring(clamp(offset), rng)))
+                })
+                .collect();
+            geojson(kind, format!("[{}]", polygons.join(", ")))
+        }
+    }
+}
+
+/// A fixed, valid geometry of `kind` around the origin, used for field defaults
+pub fn default_geometry(kind: GeometryKind) -> String {
+    let square = "[[0, 0], [0.01, 0], [0.01, 0.01], [0, 0.01], [0, 0]]";
+    match kind {
+        GeometryKind::Point | GeometryKind::Any => point_literal([0.0, 0.0]),
+        GeometryKind::LineString => geojson(kind, "[[0, 0], [0.01, 0.01]]".to_string()),
+        GeometryKind::Polygon => geojson(kind, format!("[{}]", square)),
+        GeometryKind::MultiPoint => geojson(kind, "[[0, 0], [0.01, 0.01]]".to_string()),
+        GeometryKind::MultiLineString => geojson(kind, "[[[0, 0], [0.01, 0.01]]]".to_string()),
+        GeometryKind::MultiPolygon => geojson(kind, format!("[[{}]]", square)),
+    }
+}
+
+fn point_literal(position: Position) -> String {
+    format!("({:.6}, {:.6})", position[0], position[1])
+}
+
+fn geojson(kind: GeometryKind, coordinates: String) -> String {
+    format!(
+        "{{ type: '{}', coordinates: {} }}",
+        kind.geojson_type().unwrap_or("Point"),
+        coordinates
+    )
+}
+
+fn positions(points: &[Position]) -> String {
+    let items: Vec<String> = points
+        .iter()
+        .map(|p| format!("[{:.6}, {:.6}]", p[0], p[1]))
+        .collect();
+    format!("[{}]", items.join(", "))
+}
+
+fn clamp(position: Position) -> Position {
+    [
+        position[0].clamp(-180.0, 180.0),
+        position[1].clamp(-90.0, 90.0),
+    ]
+}
+
+fn jitter<R: Rng>(center: Position, rng: &mut R) -> Position {
+    clamp([
+        center[0] + rng.random_range(-SHAPE_RADIUS..SHAPE_RADIUS),
+        center[1] + rng.random_range(-SHAPE_RADIUS..SHAPE_RADIUS),
+    ])
+}
+
+/// A short random walk starting at `start`
+fn line<R: Rng>(start: Position, rng: &mut R) -> Vec<Position> {
+    let count = rng.random_range(2..6);
+    let mut points = vec![start];
+    for _ in 1..count {
+        let last = points[points.len() - 1];
+        points.push(jitter(last, rng));
+    }
+    points
+}
+
+/// A closed ring around `center`. Vertices are taken at increasing angles, so the
+/// ring never intersects itself.
+fn ring<R: Rng>(center: Position, rng: &mut R) -> Vec<Position> {
+    let count = rng.random_range(3..8);
+    let mut points: Vec<Position> = (0..count)
+        .map(|i| {
+            let angle = std::f64::consts::TAU * i as f64 / count as f64;
+            let radius = SHAPE_RADIUS * rng.random_range(0.5..1.0);
+            clamp([
+                center[0] + radius * angle.cos(),
+                center[1] + radius * angle.sin(),
+            ])
+        })
+        .collect();
+    points.push(points[0]);
+    points
+}
@@ -0,0 +1,444 @@
+//! Locales for generated names, addresses and phone numbers.
+//!
+//! The name and address formats (`FirstName`, `City`, `PostalCode`, `PhoneNumber`, ..)
+//! generate US data by default. A locale set in the config (`locale = "de_DE"`), on a
+//! table (`#[mock_data(locale = "fr_FR")]`) or on single fields
+//! (`#[mock_data(locales(billing_city = "en_GB"))]`) draws them from the locale's
+//! dataset instead, as does `CoherentDataset::Address` for matching address tuples.
+//! Datasets for other locales are added with [`register_locale`].
+
+use crate::{
+    coordinate::EXTENDED_ADDRESSES,
+    format::Format,
+    mockmake::{Mockmaker, regex_val_gen::RegexValGen, rng::MockRng},
+};
+use chrono::{DateTime, Utc};
+use quote::{ToTokens, quote};
+use rand::{Rng, seq::IndexedRandom};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt,
+    str::FromStr,
+    sync::{LazyLock, RwLock},
+};
+
+/// A locale code such as `de_DE`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum Locale {
+    #[default]
+    EnUs,
+    EnGb,
+    DeDe,
+    FrFr,
+    /// A locale whose dataset is registered with [`register_locale`]
+    Custom(String),
+}
+
+impl Locale {
+    pub fn code(&self) -> &str {
+        match self {
+            Locale::EnUs => "en_US",
+            Locale::EnGb => "en_GB",
+            Locale::DeDe => "de_DE",
+            Locale::FrFr => "fr_FR",
+            Locale::Custom(code) => code,
+        }
+    }
+
+    /// The dataset of this locale: a registered one, else the built-in one. `en_US` has
+    /// no built-in dataset, its values come from the formats themselves.
+    pub fn dataset(&self) -> Option<&'static LocaleDataset> {
+        if let Some(dataset) = REGISTERED_DATASETS
+            .read()
+            .ok()
+            .and_then(|datasets| datasets.get(self).copied())
+        {
+            return Some(dataset);
+        }
+        match self {
+            Locale::EnGb => Some(&EN_GB),
+            Locale::DeDe => Some(&DE_DE),
+            Locale::FrFr => Some(&FR_FR),
+            Locale::EnUs => None,
+            Locale::Custom(code) => {
+                tracing::debug!(locale = %code, "No dataset registered for locale, using en_US");
+                None
+            }
+        }
+    }
+
+    /// (city, region, postal code, country) tuples of this locale
+    pub fn addresses(&self) -> &'static [(&'static str, &'static str, &'static str, &'static str)] {
+        self.dataset()
+            .map_or(EXTENDED_ADDRESSES, |dataset| dataset.addresses)
+    }
+}
+
+impl From<&str> for Locale {
+    /// Parses codes like `de_DE`, also accepting `de-DE` and any casing
+    fn from(code: &str) -> Self {
+        let code = code.trim().replace('-', "_");
+        match code.to_lowercase().as_str() {
+            "en_us" => Locale::EnUs,
+            "en_gb" => Locale::EnGb,
+            "de_de" => Locale::DeDe,
+            "fr_fr" => Locale::FrFr,
+            _ => Locale::Custom(code),
+        }
+    }
+}
+
+impl From<String> for Locale {
+    fn from(code: String) -> Self {
+        Locale::from(code.as_str())
+    }
+}
+
+impl FromStr for Locale {
+    type Err = std::convert::Infallible;
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        Ok(Locale::from(code))
+    }
+}
+
+impl From<Locale> for String {
+    fn from(locale: Locale) -> Self {
+        locale.code().to_string()
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl ToTokens for Locale {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let code = self.code();
+        tokens.extend(quote! {
+            ::evenframe::schemasync::mockmake::locale::Locale::from(#code)
+        });
+    }
+}
+
+/// The names, addresses and phone numbers of a locale
+#[derive(Debug)]
+pub struct LocaleDataset {
+    pub first_names: &'static [&'static str],
+    pub last_names: &'static [&'static str],
+    pub street_names: &'static [&'static str],
+    /// Whether the house number precedes the street name, as in "12 rue de Rivoli"
+    pub house_number_first: bool,
+    /// (city, region, postal code, country) tuples with matching values
+    pub addresses: &'static [(&'static str, &'static str, &'static str, &'static str)],
+    /// Regex of standalone postal codes
+    pub postal_code_pattern: &'static str,
+    /// Regex of phone numbers
+    pub phone_pattern: &'static str,
+}
+
+impl LocaleDataset {
+    /// A value of `format` from this dataset, `None` for formats that aren't localized
+    pub fn generate(&self, format: &Format, rng: &mut MockRng) -> Option<String> {
+        let value = match format {
+            Format::FirstName => pick(self.first_names, rng).to_string(),
+            Format::LastName => pick(self.last_names, rng).to_string(),
+            Format::FullName => format!(
+                "{} {}",
+                pick(self.first_names, rng),
+                pick(self.last_names, rng)
+            ),
+            Format::StreetAddress => {
+                let number = rng.random_range(1..200);
+                let street = pick(self.street_names, rng);
+                if self.house_number_first {
+                    format!("{} {}", number, street)
+                } else {
+                    format!("{} {}", street, number)
+                }
+            }
+            Format::City => pick(self.addresses, rng).0.to_string(),
+            Format::State => pick(self.addresses, rng).1.to_string(),
+            Format::Country => pick(self.addresses, rng).3.to_string(),
+            Format::PostalCode => from_pattern(self.postal_code_pattern, rng),
+            Format::PhoneNumber => from_pattern(self.phone_pattern, rng),
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+fn pick<'d, T>(values: &'d [T], rng: &mut MockRng) -> &'d T {
+    values
+        .choose(rng)
+        .expect("Locale datasets should not be empty")
+}
+
+fn from_pattern(pattern: &str, rng: &mut MockRng) -> String {
+    let mut maker = RegexValGen::from_rng(rng.clone());
+    let value = maker
+        .generate(pattern)
+        .unwrap_or_else(|e| panic!("Failed to generate value for '{}': {}", pattern, e));
+    *rng = maker.into_rng();
+    value
+}
+
+static REGISTERED_DATASETS: LazyLock<RwLock<HashMap<Locale, &'static LocaleDataset>>> =
+    LazyLock::new(Default::default);
+
+/// Registers the dataset of `locale`, replacing its built-in or previously registered one
+pub fn register_locale(locale: Locale, dataset: &'static LocaleDataset) {
+    tracing::debug!(locale = %locale, "Registering locale dataset");
+    REGISTERED_DATASETS
+        .write()
+        .expect("Locale registry lock poisoned")
+        .insert(locale, dataset);
+}
+
+impl Format {
+    /// Generate a value in `locale`, for the formats its dataset covers
+    pub fn generate_localized_value(
+        &self,
+        locale: &Locale,
+        rng: &mut MockRng,
+        now: DateTime<Utc>,
+    ) -> String {
+        locale
+            .dataset()
+            .and_then(|dataset| dataset.generate(self, rng))
+            .unwrap_or_else(|| self.generate_formatted_value_with(rng, now))
+    }
+}
+
+impl Mockmaker {
+    /// The locale of the field at `field_path` of `table_name`: the field's own locale,
+    /// else the table's, else the global one
+    pub fn locale_for(&self, table_name: &str, field_path: &str) -> &Locale {
+        let table_config = self
+            .tables
+            .get(table_name)
+            .and_then(|table| table.mock_generation_config.as_ref());
+        table_config
+            .and_then(|config| config.locales.get(field_path).or(config.locale.as_ref()))
+            .unwrap_or(&self.schemasync_config.mock_gen_config.locale)
+    }
+}
+
+pub const DE_DE: LocaleDataset = LocaleDataset {
+    first_names: &[
+        "Lukas",
+        "Anna",
+        "Maximilian",
+        "Lena",
+        "Felix",
+        "Leonie",
+        "Jonas",
+        "Hannah",
+        "Paul",
+        "Sophie",
+        "Leon",
+        "Marie",
+        "Finn",
+        "Laura",
+        "Tim",
+        "Julia",
+        "Jan",
+        "Katharina",
+        "Niklas",
+        "Sarah",
+    ],
+    last_names: &[
+        "Müller",
+        "Schmidt",
+        "Schneider",
+        "Fischer",
+        "Weber",
+        "Meyer",
+        "Wagner",
+        "Becker",
+        "Schulz",
+        "Hoffmann",
+        "Schäfer",
+        "Koch",
+        "Bauer",
+        "Richter",
+        "Klein",
+        "Wolf",
+        "Schröder",
+        "Neumann",
+        "Schwarz",
+        "Zimmermann",
+    ],
+    street_names: &[
+        "Hauptstraße",
+        "Schulstraße",
+        "Gartenstraße",
+        "Bahnhofstraße",
+        "Dorfstraße",
+        "Bergstraße",
+        "Lindenstraße",
+        "Kirchstraße",
+        "Waldstraße",
+        "Ringstraße",
+    ],
+    house_number_first: false,
+    addresses: &[
+        ("Berlin", "Berlin", "10115", "Deutschland"),
+        ("Hamburg", "Hamburg", "20095", "Deutschland"),
+        ("München", "Bayern", "80331", "Deutschland"),
+        ("Köln", "Nordrhein-Westfalen", "50667", "Deutschland"),
+        ("Frankfurt am Main", "Hessen", "60311", "Deutschland"),
+        ("Stuttgart", "Baden-Württemberg", "70173", "Deutschland"),
+        ("Düsseldorf", "Nordrhein-Westfalen", "40213", "Deutschland"),
+        ("Leipzig", "Sachsen", "04109", "Deutschland"),
+        ("Dortmund", "Nordrhein-Westfalen", "44135", "Deutschland"),
+        ("Essen", "Nordrhein-Westfalen", "45127", "Deutschland"),
+        ("Bremen", "Bremen", "28195", "Deutschland"),
+        ("Dresden", "Sachsen", "01067", "Deutschland"),
+        ("Hannover", "Niedersachsen", "30159", "Deutschland"),
+        ("Nürnberg", "Bayern", "90402", "Deutschland"),
+        ("Mainz", "Rheinland-Pfalz", "55116", "Deutschland"),
+    ],
+    postal_code_pattern: r"^[0-9]{5}$",
+    phone_pattern: r"^\+49 (30|40|89|221|69|711) [1-9][0-9]{6}$",
+};
+
+pub const FR_FR: LocaleDataset = LocaleDataset {
+    first_names: &[
+        "Gabriel", "Emma", "Louis", "Jade", "Raphaël", "Louise", "Jules", "Alice", "Adam", "Chloé",
+        "Lucas", "Léa", "Hugo", "Manon", "Arthur", "Camille", "Nathan", "Inès", "Thomas", "Sarah",
+    ],
+    last_names: &[
+        "Martin", "Bernard", "Thomas", "Petit", "Robert", "Richard", "Durand", "Dubois", "Moreau",
+        "Laurent", "Simon", "Michel", "Lefebvre", "Leroy", "Roux", "David", "Bertrand", "Morel",
+        "Fournier", "Girard",
+    ],
+    street_names: &[
+        "rue de la République",
+        "rue Victor Hugo",
+        "avenue Jean Jaurès",
+        "rue Pasteur",
+        "boulevard Gambetta",
+        "rue de la Paix",
+        "place de la Mairie",
+        "rue du Moulin",
+        "avenue de la Gare",
+        "rue des Écoles",
+    ],
+    house_number_first: true,
+    addresses: &[
+        ("Paris", "Île-de-France", "75001", "France"),
+        ("Marseille", "Provence-Alpes-Côte d’Azur", "13001", "France"),
+        ("Lyon", "Auvergne-Rhône-Alpes", "69001", "France"),
+        ("Toulouse", "Occitanie", "31000", "France"),
+        ("Nice", "Provence-Alpes-Côte d’Azur", "06000", "France"),
+        ("Nantes", "Pays de la Loire", "44000", "France"),
+        ("Strasbourg", "Grand Est", "67000", "France"),
+        ("Montpellier", "Occitanie", "34000", "France"),
+        ("Bordeaux", "Nouvelle-Aquitaine", "33000", "France"),
+        ("Lille", "Hauts-de-France", "59000", "France"),
+        ("Rennes", "Bretagne", "35000", "France"),
+        ("Reims", "Grand Est", "51100", "France"),
+        ("Toulon", "Provence-Alpes-Côte d’Azur", "83000", "France"),
+        ("Grenoble", "Auvergne-Rhône-Alpes", "38000", "France"),
+        ("Dijon", "Bourgogne-Franche-Comté", "21000", "France"),
+    ],
+    postal_code_pattern: r"^(0[1-9]|[1-8][0-9]|9[0-5])[0-9]{3}$",
+    phone_pattern: r"^\+33 [1-79]( [0-9]{2}){4}$",
+};
+
+pub const EN_GB: LocaleDataset = LocaleDataset {
+    first_names: &[
+        "Oliver", "Olivia", "George", "Amelia", "Harry", "Isla", "Noah", "Ava", "Jack", "Mia",
+        "Leo", "Ivy", "Arthur", "Lily", "Muhammad", "Isabella", "Oscar", "Rosie", "Charlie",
+        "Sophia",
+    ],
+    last_names: &[
+        "Smith", "Jones", "Taylor", "Brown", "Williams", "Wilson", "Johnson", "Davies", "Robinson",
+        "Wright", "Thompson", "Evans", "Walker", "White", "Roberts", "Green", "Hall", "Wood",
+        "Jackson", "Clarke",
+    ],
+    street_names: &[
+        "High Street",
+        "Station Road",
+        "Main Street",
+        "Park Road",
+        "Church Road",
+        "Church Street",
+        "London Road",
+        "Victoria Road",
+        "Green Lane",
+        "Manor Road",
+    ],
+    house_number_first: true,
+    addresses: &[
+        ("London", "Greater London", "SW1A 1AA", "United Kingdom"),
+        ("Birmingham", "West Midlands", "B1 1BB", "United Kingdom"),
+        (
+            "Manchester",
+            "Greater Manchester",
+            "M1 1AE",
+            "United Kingdom",
+        ),
+        ("Leeds", "West Yorkshire", "LS1 1UR", "United Kingdom"),
+        ("Liverpool", "Merseyside", "L1 8JQ", "United Kingdom"),
+        ("Bristol", "Bristol", "BS1 4DJ", "United Kingdom"),
+        ("Sheffield", "South Yorkshire", "S1 2HH", "United Kingdom"),
+        (
+            "Newcastle upon Tyne",
+            "Tyne and Wear",
+            "NE1 7RU",
+            "United Kingdom",
+        ),
+        ("Nottingham", "Nottinghamshire", "NG1 5FS", "United Kingdom"),
+        ("Edinburgh", "Scotland", "EH1 1YZ", "United Kingdom"),
+        ("Glasgow", "Scotland", "G1 1XQ", "United Kingdom"),
+        ("Cardiff", "Wales", "CF10 1EP", "United Kingdom"),
+        ("Belfast", "Northern Ireland", "BT1 5GS", "United Kingdom"),
+        ("Oxford", "Oxfordshire", "OX1 1BX", "United Kingdom"),
+        ("Cambridge", "Cambridgeshire", "CB2 1TN", "United Kingdom"),
+    ],
+    postal_code_pattern: r"^[A-Z]{1,2}[0-9]{1,2} [0-9][A-Z]{2}$",
+    phone_pattern: r"^\+44 (20 [78][0-9]{3}|161 [2-9][0-9]{2}|121 [2-9][0-9]{2}|113 [2-9][0-9]{2}) [0-9]{4}$",
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mockmake::rng::mock_rng;
+    use regex::Regex;
+
+    #[test]
+    fn test_parse_locale() {
+        assert_eq!("de_DE".parse::<Locale>().unwrap(), Locale::DeDe);
+        assert_eq!("fr-fr".parse::<Locale>().unwrap(), Locale::FrFr);
+        assert_eq!(
+            "nl_NL".parse::<Locale>().unwrap(),
+            Locale::Custom("nl_NL".to_string())
+        );
+        assert_eq!(Locale::EnGb.to_string(), "en_GB");
+    }
+
+    #[test]
+    fn test_localized_values() {
+        let mut rng = mock_rng(Some(7), "test");
+        let now = Utc::now();
+        let phone = Regex::new(EN_GB.phone_pattern).unwrap();
+        let postal_code = Regex::new(FR_FR.postal_code_pattern).unwrap();
+        for _ in 0..50 {
+            let name = Format::FirstName.generate_localized_value(&Locale::DeDe, &mut rng, now);
+            assert!(DE_DE.first_names.contains(&name.as_str()));
+            let number = Format::PhoneNumber.generate_localized_value(&Locale::EnGb, &mut rng, now);
+            assert!(phone.is_match(&number), "{number}");
+            let code = Format::PostalCode.generate_localized_value(&Locale::FrFr, &mut rng, now);
+            assert!(postal_code.is_match(&code), "{code}");
+        }
+        // en_US keeps the format's own values
+        let city = Format::City.generate_localized_value(&Locale::EnUs, &mut rng, now);
+        assert!(Format::City.into_regex().is_match(&city));
+    }
+}
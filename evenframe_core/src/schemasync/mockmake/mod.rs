@@ -1,8 +1,19 @@
+pub mod bulk;
+pub mod cardinality;
+pub mod constraints;
 pub mod coordinate;
+pub mod custom;
+pub mod distribution;
+pub mod export;
 pub mod field_value;
 pub mod field_value_recursive;
 pub mod format;
+pub mod geometry;
+pub mod locale;
 pub mod regex_val_gen;
+pub mod rng;
+pub mod scenario;
+pub mod snapshot;
 
 use crate::{
     compare::Comparator,
@@ -11,26 +22,33 @@ use crate::{
     },
     dependency::sort_tables_by_dependencies,
     evenframe_log,
-    mockmake::format::Format,
+    mockmake::{
+        cardinality::{Cardinality, ReferencePlan},
+        format::Format,
+        locale::Locale,
+        rng::MockRng,
+        snapshot::Anonymization,
+    },
     schemasync::{
         StructConfig, TableConfig, TaggedUnion, compare::PreservationMode,
         surql::access::execute_access_query,
     },
-    types::StructField,
+    types::{StructField, register_generic_instances},
     wrappers::EvenframeRecordId,
 };
 use bon::Builder;
+use chrono::{DateTime, Utc};
 use rand::Rng;
 use std::collections::{HashMap, HashSet};
 use surrealdb::Surreal;
+use surrealdb::engine::any::Any;
 use surrealdb::engine::local::Db;
-use surrealdb::engine::remote::http::Client;
 use tracing;
 use uuid::Uuid;
 
 #[derive(Debug, Builder)]
 pub struct Mockmaker {
-    db: Surreal<Client>,
+    db: Surreal<Any>,
     pub(super) tables: HashMap<String, TableConfig>,
     objects: HashMap<String, StructConfig>,
     enums: HashMap<String, TaggedUnion>,
@@ -40,6 +58,9 @@ pub struct Mockmaker {
     // Runtime state
     pub(super) id_map: HashMap<String, Vec<String>>,
     pub(super) record_diffs: HashMap<String, i32>,
+    pub(super) reference_plans: HashMap<(String, String), ReferencePlan>,
+    /// Mock data statements per table copied from a snapshot, instead of generated ones
+    pub(super) snapshot_statements: Option<Vec<(String, String)>>,
     filtered_tables: HashMap<String, TableConfig>,
     filtered_objects: HashMap<String, StructConfig>,
     pub coordinated_values: HashMap<CoordinationId, String>,
@@ -47,12 +68,22 @@ pub struct Mockmaker {
 
 impl Mockmaker {
     pub fn new(
-        db: Surreal<Client>,
+        db: Surreal<Any>,
         tables: HashMap<String, TableConfig>,
         objects: HashMap<String, StructConfig>,
         enums: HashMap<String, TaggedUnion>,
         schemasync_config: crate::schemasync::config::SchemasyncConfig,
     ) -> Self {
+        // Register concrete copies of generic structs/enums used by table fields
+        // so the value generators can resolve e.g. `Paginated<User>` by name
+        let mut objects = objects;
+        let mut enums = enums;
+        for table in tables.values() {
+            for field in &table.struct_config.fields {
+                register_generic_instances(&field.field_type, &mut objects, &mut enums);
+            }
+        }
+
         Self {
             db: db.clone(),
             tables,
@@ -62,6 +93,8 @@ impl Mockmaker {
             comparator: Some(Comparator::new(db, schemasync_config)),
             id_map: HashMap::new(),
             record_diffs: HashMap::new(),
+            reference_plans: HashMap::new(),
+            snapshot_statements: None,
             filtered_tables: HashMap::new(),
             filtered_objects: HashMap::new(),
             coordinated_values: HashMap::new(),
@@ -97,8 +130,19 @@ impl Mockmaker {
         Ok(())
     }
 
+    /// Fail when the mock data can't be generated: a generator or coordinator isn't
+    /// registered, or the validators of a field can't all be satisfied
+    pub fn check_mock_generation(&self) -> crate::error::Result<()> {
+        self.check_custom_generators()?;
+        self.check_value_constraints()
+    }
+
     /// Generate IDs for tables
     pub async fn generate_ids(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        // Before the first step that depends on the mock configuration
+        if self.schemasync_config.should_generate_mocks {
+            self.check_mock_generation()?;
+        }
         evenframe_log!("", "record_diffs.log");
         tracing::trace!("Starting ID generation for all tables");
         let mut map = HashMap::new();
@@ -110,12 +154,7 @@ impl Mockmaker {
             tracing::trace!(table = %table_name, "Generating IDs for table");
 
             // Determine desired count from config or default
-            let desired_count =
-                if let Some(mock_generation_config) = &table_config.mock_generation_config {
-                    mock_generation_config.n
-                } else {
-                    self.schemasync_config.mock_gen_config.default_batch_size
-                };
+            let desired_count = self.record_count(table_config);
 
             // Query existing IDs
             let query = format!("SELECT id FROM {table_name};",);
@@ -184,6 +223,7 @@ impl Mockmaker {
 
         self.id_map = map;
         self.record_diffs = record_diffs;
+        self.plan_references();
 
         tracing::debug!(table_count = self.id_map.len(), "ID generation complete");
 
@@ -199,6 +239,19 @@ impl Mockmaker {
     /// Remove old data based on schema changes
     pub async fn remove_old_data(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         tracing::trace!("Removing old data based on schema changes");
+        let remove_statements = self.remove_statements();
+
+        if !remove_statements.is_empty() {
+            tracing::trace!("Executing remove statements");
+            self.db.query(remove_statements).await?;
+        }
+
+        tracing::trace!("Old data removal complete");
+        Ok(())
+    }
+
+    /// Statements removing data invalidated by the schema changes
+    pub fn remove_statements(&self) -> String {
         let comparator = self.comparator.as_ref().unwrap();
         let schema_changes = comparator.get_schema_changes().unwrap();
 
@@ -210,25 +263,23 @@ impl Mockmaker {
         );
 
         evenframe_log!(&remove_statements, "remove_statements.surql");
-
-        if !remove_statements.is_empty() {
-            tracing::trace!("Executing remove statements");
-            self.db.query(remove_statements).await?;
-        }
-
-        tracing::trace!("Old data removal complete");
-        Ok(())
+        remove_statements
     }
 
     /// Execute access query on main database
     pub async fn execute_access(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         tracing::trace!("Executing access definitions");
-        let comparator = self.comparator.as_ref().unwrap();
-        let access_query = comparator.get_access_query();
+        let access_query = self.access_statements();
 
         tracing::debug!(query_length = access_query.len(), "Executing access query");
 
-        execute_access_query(&self.db, access_query).await
+        execute_access_query(&self.db, &access_query).await
+    }
+
+    /// Access definitions for the main database
+    pub fn access_statements(&self) -> String {
+        let comparator = self.comparator.as_ref().unwrap();
+        comparator.get_access_query().to_string()
     }
 
     /// Filter changed tables and objects
@@ -271,6 +322,58 @@ impl Mockmaker {
 
     pub(super) async fn generate_mock_data(&self) -> Result<(), Box<dyn std::error::Error>> {
         tracing::trace!("Starting mock data generation");
+        use crate::schemasync::surql::execute::execute_and_validate;
+
+        if self.snapshot_statements.is_none() {
+            // Generated records are inserted in chunks rather than as one query per table
+            for table_name in self.mock_data_tables() {
+                let Some(table) = self.filtered_tables.get(&table_name) else {
+                    continue;
+                };
+                self.bulk_insert(&table_name, table).await.inspect_err(|e| {
+                    tracing::error!(table = %table_name, error = %e, "Failed to insert mock data");
+                    evenframe_log!(
+                        &format!("Failed to insert mock data for table {}: {}", table_name, e),
+                        "results.log",
+                        true
+                    );
+                })?;
+                tracing::debug!(table = %table_name, "Mock data inserted successfully");
+            }
+            tracing::info!("Mock data generation complete");
+            return Ok(());
+        }
+
+        for (table_name, stmts) in self.mock_data_statements() {
+            // Execute and validate upsert statements
+            match execute_and_validate(&self.db, &stmts, "UPSERT", &table_name).await {
+                Ok(_results) => {
+                    tracing::debug!(table = %table_name, "Mock data inserted successfully");
+                }
+                Err(e) => {
+                    tracing::error!(
+                        table = %table_name,
+                        error = %e,
+                        "Failed to execute statements"
+                    );
+                    let error_msg = format!(
+                        "Failed to execute upsert statements for table {}: {}",
+                        table_name, e
+                    );
+                    evenframe_log!(&error_msg, "results.log", true);
+                    return Err(e);
+                }
+            }
+        }
+        tracing::info!("Mock data generation complete");
+        Ok(())
+    }
+
+    /// Tables mock data is generated for, in dependency order
+    fn mock_data_tables(&self) -> Vec<String> {
+        if !self.schemasync_config.should_generate_mocks {
+            return Vec::new();
+        }
 
         // Sort tables by dependencies to ensure proper insertion order
         let sorted_table_names =
@@ -286,8 +389,21 @@ impl Mockmaker {
             "table_order.log",
             true
         );
+        sorted_table_names
+    }
 
-        for table_name in &sorted_table_names {
+    /// Mock data statements per table, in dependency order
+    pub(super) fn mock_data_statements(&self) -> Vec<(String, String)> {
+        let mut statements = Vec::new();
+        if !self.schemasync_config.should_generate_mocks {
+            return statements;
+        }
+        if let Some(snapshot_statements) = &self.snapshot_statements {
+            tracing::debug!("Using the mock data statements of the snapshot");
+            return snapshot_statements.clone();
+        }
+
+        for table_name in &self.mock_data_tables() {
             if let Some(table) = &self.filtered_tables.get(table_name) {
                 tracing::trace!(
                     table = %table_name,
@@ -295,49 +411,20 @@ impl Mockmaker {
                     "Processing table for mock data"
                 );
 
-                if self.schemasync_config.should_generate_mocks {
-                    let stmts = if table.relation.is_some() {
-                        tracing::trace!(table = %table_name, "Generating INSERT statements for relation");
-                        self.generate_insert_statements(table_name, table)
-                    } else {
-                        tracing::trace!(table = %table_name, "Generating UPSERT statements for table");
-                        self.generate_upsert_statements(table_name, table)
-                    };
-
-                    tracing::debug!(
-                        table = %table_name,
-                        statement_count = stmts.lines().count(),
-                        "Generated mock data statements"
-                    );
-
-                    evenframe_log!(&stmts, "all_statements.surql", true);
+                // Chunked like `generate_mock_data` inserts them
+                let stmts = self.bulk_insert_statements(table_name, table);
 
-                    // Execute and validate upsert statements
-                    use crate::schemasync::surql::execute::execute_and_validate;
+                tracing::debug!(
+                    table = %table_name,
+                    statement_count = stmts.lines().count(),
+                    "Generated mock data statements"
+                );
 
-                    match execute_and_validate(&self.db, &stmts, "UPSERT", &table_name).await {
-                        Ok(_results) => {
-                            tracing::debug!(table = %table_name, "Mock data inserted successfully");
-                        }
-                        Err(e) => {
-                            tracing::error!(
-                                table = %table_name,
-                                error = %e,
-                                "Failed to execute statements"
-                            );
-                            let error_msg = format!(
-                                "Failed to execute upsert statements for table {}: {}",
-                                table_name, e
-                            );
-                            evenframe_log!(&error_msg, "results.log", true);
-                            return Err(e);
-                        }
-                    }
-                }
+                evenframe_log!(&stmts, "all_statements.surql", true);
+                statements.push((table_name.clone(), stmts));
             }
         }
-        tracing::info!("Mock data generation complete");
-        Ok(())
+        statements
     }
 
     // Getter for new_schema so Schemasync can access it
@@ -345,12 +432,33 @@ impl Mockmaker {
         self.comparator.as_ref()?.get_new_schema()
     }
 
-    pub fn random_string(len: usize) -> String {
+    pub fn random_string_with<R: Rng + ?Sized>(rng: &mut R, len: usize) -> String {
         use rand::distr::Alphanumeric;
-        let mut rng = rand::rng();
         (0..len).map(|_| rng.sample(Alphanumeric) as char).collect()
     }
 
+    /// Mock data seed of a table: its own, else the global one
+    pub fn seed_for(&self, table_name: &str) -> Option<u64> {
+        self.tables
+            .get(table_name)
+            .and_then(|table| table.mock_generation_config.as_ref())
+            .and_then(|config| config.seed)
+            .or(self.schemasync_config.mock_gen_config.seed)
+    }
+
+    /// RNG for one generated value of `table_name`, identified by `scope`
+    pub fn rng_for(&self, table_name: &str, scope: &str) -> MockRng {
+        rng::mock_rng(
+            self.seed_for(table_name),
+            &format!("{}/{}", table_name, scope),
+        )
+    }
+
+    /// The time relative dates of `table_name` are generated from
+    pub fn reference_time(&self, table_name: &str) -> DateTime<Utc> {
+        rng::reference_time(self.seed_for(table_name))
+    }
+
     /// Builds coordination groups from the provided table configs
     pub fn build_coordination_groups(&mut self) -> Vec<CoordinationGroup> {
         let mut coordination_groups = Vec::new();
@@ -368,6 +476,7 @@ impl Mockmaker {
                             field_names.clone()
                         }
                         Coordination::InitializeSum { field_names, .. } => field_names.clone(),
+                        Coordination::InitializeCustom { field_names, .. } => field_names.clone(),
                         Coordination::InitializeDerive {
                             source_field_names,
                             target_field_name,
@@ -448,6 +557,7 @@ impl Mockmaker {
                     Coordination::InitializeSum { .. } => "sum",
                     Coordination::InitializeDerive { .. } => "derive",
                     Coordination::InitializeCoherent(_) => "coherent",
+                    Coordination::InitializeCustom { .. } => "custom",
                 };
 
                 coordination_by_type
@@ -477,6 +587,7 @@ impl Mockmaker {
                             field_names.clone()
                         }
                         Coordination::InitializeSum { field_names, .. } => field_names.clone(),
+                        Coordination::InitializeCustom { field_names, .. } => field_names.clone(),
                         Coordination::InitializeDerive {
                             source_field_names,
                             target_field_name,
@@ -537,6 +648,7 @@ impl Mockmaker {
                                     f.clone()
                                 }
                                 Coordination::InitializeSum { field_names: f, .. } => f.clone(),
+                                Coordination::InitializeCustom { field_names: f, .. } => f.clone(),
                                 Coordination::InitializeDerive {
                                     source_field_names,
                                     target_field_name,
@@ -649,6 +761,24 @@ pub struct MockGenerationConfig {
     pub batch_size: usize,
     pub regenerate_fields: Vec<String>,
     pub preservation_mode: PreservationMode,
+    /// Overrides the global mock generation seed for this table
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// How the records of this table reference other tables
+    #[serde(default)]
+    pub cardinality: Vec<Cardinality>,
+    /// Distribution formats by field name, for fields without a `#[format]`
+    #[serde(default)]
+    pub distributions: HashMap<String, Format>,
+    /// Overrides the global locale of names, addresses and phone numbers for this table
+    #[serde(default)]
+    pub locale: Option<Locale>,
+    /// Locales by field name, overriding the table's locale
+    #[serde(default)]
+    pub locales: HashMap<String, Locale>,
+    /// Anonymization of fields copied from a snapshot, by field name
+    #[serde(default)]
+    pub anonymize: HashMap<String, Anonymization>,
 }
 
 impl Default for MockGenerationConfig {
@@ -673,6 +803,12 @@ impl Default for MockGenerationConfig {
             batch_size,
             regenerate_fields: vec![],
             preservation_mode,
+            seed: None,
+            cardinality: Vec::new(),
+            distributions: HashMap::new(),
+            locale: None,
+            locales: HashMap::new(),
+            anonymize: HashMap::new(),
         }
     }
 }
@@ -707,6 +843,21 @@ impl quote::ToTokens for MockGenerationConfig {
             }
         };
 
+        let seed_tokens = match self.seed {
+            Some(seed) => quote::quote! { Some(#seed) },
+            None => quote::quote! { None },
+        };
+
+        let cardinality = &self.cardinality;
+        let (distribution_fields, distributions): (Vec<_>, Vec<_>) =
+            self.distributions.iter().unzip();
+        let locale_tokens = match &self.locale {
+            Some(locale) => quote::quote! { Some(#locale) },
+            None => quote::quote! { None },
+        };
+        let (locale_fields, locales): (Vec<_>, Vec<_>) = self.locales.iter().unzip();
+        let (anonymized_fields, anonymizations): (Vec<_>, Vec<_>) = self.anonymize.iter().unzip();
+
         // Generate the full config token stream
         let config_tokens = quote::quote! {
             MockGenerationConfig {
@@ -716,6 +867,18 @@ impl quote::ToTokens for MockGenerationConfig {
                 batch_size: #batch_size,
                 regenerate_fields: vec![#(#regenerate_fields.to_string()),*],
                 preservation_mode: #preservation_mode_tokens,
+                seed: #seed_tokens,
+                cardinality: vec![#(#cardinality),*],
+                distributions: ::std::collections::HashMap::from([
+                    #((#distribution_fields.to_string(), #distributions)),*
+                ]),
+                locale: #locale_tokens,
+                locales: ::std::collections::HashMap::from([
+                    #((#locale_fields.to_string(), #locales)),*
+                ]),
+                anonymize: ::std::collections::HashMap::from([
+                    #((#anonymized_fields.to_string(), #anonymizations)),*
+                ]),
             }
         };
 
@@ -12,7 +12,8 @@
 //! // Might generate: "abc12"
 //! ```
 
-use rand::Rng;
+use super::rng::MockRng;
+use rand::{Rng, SeedableRng};
 use std::error::Error;
 use std::fmt;
 use tracing;
@@ -103,16 +104,33 @@ pub enum RegexComponent {
 }
 
 /// A regex pattern generator that creates random strings matching regex patterns
-#[derive(Default)]
 pub struct RegexValGen {
-    rng: rand::rngs::ThreadRng,
+    rng: MockRng,
+}
+
+impl Default for RegexValGen {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl RegexValGen {
     /// Creates a new RegexValGen instance
     pub fn new() -> Self {
         tracing::trace!("Creating new RegexValGen instance");
-        Self { rng: rand::rng() }
+        Self {
+            rng: MockRng::from_rng(&mut rand::rng()),
+        }
+    }
+
+    /// Creates a RegexValGen drawing from `rng`, e.g. a seeded one for reproducible output
+    pub fn from_rng(rng: MockRng) -> Self {
+        Self { rng }
+    }
+
+    /// Gives back the RNG, advanced past everything generated so far
+    pub fn into_rng(self) -> MockRng {
+        self.rng
     }
 
     /// Generates a random string matching the given regex pattern
@@ -826,6 +844,20 @@ mod tests {
         assert_eq!(result, "hello");
     }
 
+    #[test]
+    fn test_seeded_generation_is_reproducible() {
+        let pattern = r"[A-Z]{3}-\d{4}-[a-f0-9]{8}";
+        let generate = |seed| {
+            let mut value_generator =
+                RegexValGen::from_rng(super::super::rng::mock_rng(Some(seed), "test"));
+            (0..5)
+                .map(|_| value_generator.generate(pattern).unwrap())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(generate(42), generate(42));
+        assert_ne!(generate(42), generate(43));
+    }
+
     #[test]
     fn test_digit_class() {
         let mut value_generator = RegexValGen::new();
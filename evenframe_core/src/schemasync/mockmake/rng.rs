@@ -0,0 +1,54 @@
+//! Seedable randomness for mock data.
+//!
+//! With a seed, every generated value draws from its own stream derived from the seed and
+//! a scope (table, field and record index), so the output only depends on the schema and
+//! the seed, not on the order tables or fields happen to be generated in. Without a seed
+//! the streams are seeded from the OS and every run differs.
+
+use chrono::{DateTime, TimeZone, Utc};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use uuid::Uuid;
+
+/// The RNG threaded through the mock generators. Unlike `StdRng`, whose algorithm may
+/// change between rand releases, ChaCha8 streams stay the same for a given seed.
+pub type MockRng = ChaCha8Rng;
+
+/// Stand-in for "now" in seeded runs, so relative dates are reproducible too
+pub const SEEDED_REFERENCE_TIME: i64 = 1_735_689_600; // 2025-01-01T00:00:00Z
+
+/// RNG for `scope`, derived from `seed` when given
+pub fn mock_rng(seed: Option<u64>, scope: &str) -> MockRng {
+    match seed {
+        Some(seed) => MockRng::seed_from_u64(derive_seed(seed, scope)),
+        None => MockRng::from_rng(&mut rand::rng()),
+    }
+}
+
+/// Mix `scope` into `seed` (FNV-1a, stable across platforms and Rust versions)
+pub fn derive_seed(seed: u64, scope: &str) -> u64 {
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+    let mut hash = 0xcbf2_9ce4_8422_2325 ^ seed;
+    for byte in scope.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+/// The time relative dates are generated from: fixed in seeded runs, the current time
+/// otherwise
+pub fn reference_time(seed: Option<u64>) -> DateTime<Utc> {
+    match seed {
+        Some(_) => Utc
+            .timestamp_opt(SEEDED_REFERENCE_TIME, 0)
+            .single()
+            .expect("Seeded reference time should be valid"),
+        None => Utc::now(),
+    }
+}
+
+/// A v4 UUID drawn from `rng`
+pub fn random_uuid<R: Rng + ?Sized>(rng: &mut R) -> Uuid {
+    uuid::Builder::from_random_bytes(rng.random()).into_uuid()
+}
@@ -0,0 +1,862 @@
+//! Named scenarios: hand-authored expected states of the database.
+//!
+//! Random mock data can't express "an organization with two overdue invoices". A
+//! scenario can: it is a file `<name>.toml`, `<name>.json` or `<name>.ron` in
+//! `schemasync.scenarios.directory` listing records by Evenframe type, with the values
+//! of the fields that matter. Those values are checked against the field types and
+//! validators, the other fields are generated like mock data, seeded by the scenario
+//! name so every application is identical. A record named with `ref` is linked to from
+//! other records with `"@<ref>"`. `evenframe scenario <name>` applies a scenario, on top
+//! of the existing records unless it sets `reset = true`.
+//!
+//! ```toml
+//! description = "Organization with an overdue invoice"
+//!
+//! [[records]]
+//! type = "Organization"
+//! ref = "acme"
+//! fields = { name = "Acme" }
+//!
+//! [[records]]
+//! type = "Invoice"
+//! fields = { organization = "@acme", status = "Overdue" }
+//! ```
+
+use crate::{
+    dependency::sort_tables_by_dependencies,
+    error::{EvenframeError, Result},
+    mockmake::{
+        Mockmaker,
+        constraints::{LetterCase, ValueConstraints},
+        export::surql_to_json,
+        field_value::FieldValueGenerator,
+        rng::derive_seed,
+    },
+    schemasync::{TableConfig, surql::is_generated_field},
+    types::{FieldType, StructField},
+};
+use chrono::{DateTime, Utc};
+use convert_case::{Case, Casing};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::{collections::HashMap, fs, path::Path};
+use tracing::{debug, info};
+
+/// File extensions of scenarios, in the order they are looked up
+const SCENARIO_EXTENSIONS: [&str; 3] = ["toml", "json", "ron"];
+
+/// A named set of records, see the module documentation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Scenario {
+    /// The scenario's file name, without extension
+    #[serde(skip)]
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Delete the existing records of the tables the scenario has records in before
+    /// applying it, so those tables hold exactly its records. Other tables are left alone.
+    #[serde(default)]
+    pub reset: bool,
+    /// Seed of the generated values, derived from the name when not given
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(default)]
+    pub records: Vec<ScenarioRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioRecord {
+    /// The record's type: the name of its struct or table
+    #[serde(rename = "type")]
+    pub type_name: String,
+    /// Name other records link to this one with, as `"@<ref>"`. It is also the key of
+    /// the record's id; unnamed records get `⟨<scenario>_<n>⟩`, numbered per table, so
+    /// they never take the id of a mock record.
+    #[serde(default, rename = "ref")]
+    pub reference: Option<String>,
+    /// Values of the fields the scenario cares about, the others are generated
+    #[serde(default)]
+    pub fields: Map<String, Value>,
+}
+
+impl Scenario {
+    /// Load the scenario `name` from `directory`
+    pub fn load(directory: &Path, name: &str) -> Result<Self> {
+        for extension in SCENARIO_EXTENSIONS {
+            let path = directory.join(format!("{name}.{extension}"));
+            if !path.is_file() {
+                continue;
+            }
+            debug!(path = %path.display(), "Loading scenario");
+            let content = fs::read_to_string(&path)?;
+            let parsed = match extension {
+                "toml" => toml::from_str(&content).map_err(|e| e.to_string()),
+                "json" => serde_json::from_str(&content).map_err(|e| e.to_string()),
+                _ => ron::from_str(&content).map_err(|e| e.to_string()),
+            };
+            let mut scenario: Scenario =
+                parsed.map_err(|message| EvenframeError::parse_error(&path, message))?;
+            scenario.name = name.to_string();
+            return Ok(scenario);
+        }
+        Err(EvenframeError::config(format!(
+            "No scenario '{name}' in '{}', expected {name}.toml, {name}.json or {name}.ron",
+            directory.display()
+        )))
+    }
+}
+
+impl Mockmaker {
+    /// Apply `scenario` to the database. The tables need to be defined already.
+    pub async fn apply_scenario(&mut self, scenario: &Scenario) -> Result<()> {
+        self.check_mock_generation()?;
+        let records = self.scenario_records(scenario)?;
+
+        if scenario.reset {
+            let statements: String = records
+                .iter()
+                .map(|(table_name, _)| format!("DELETE {table_name};\n"))
+                .collect();
+            let mut response = self.db.query(statements).await.map_err(|e| {
+                EvenframeError::database(format!("Failed to clear the tables: {e}"))
+            })?;
+            if let Some(e) = response.take_errors().into_values().next() {
+                return Err(EvenframeError::database(format!(
+                    "Failed to clear the tables: {e}"
+                )));
+            }
+        }
+
+        for (table_name, table_records) in records {
+            debug!(
+                table = %table_name,
+                records = table_records.len(),
+                "Inserting scenario records"
+            );
+            let statement = self.bulk_insert_statement(&table_name, &self.tables[&table_name]);
+            let mut response = self
+                .db
+                .query(statement)
+                .bind(("records", table_records))
+                .await
+                .map_err(|e| {
+                    EvenframeError::database(format!(
+                        "Failed to insert the scenario records of '{table_name}': {e}"
+                    ))
+                })?;
+            if let Some(e) = response.take_errors().into_values().next() {
+                return Err(EvenframeError::database(format!(
+                    "Failed to insert the scenario records of '{table_name}': {e}"
+                )));
+            }
+        }
+        info!(
+            scenario = %scenario.name,
+            records = scenario.records.len(),
+            "Applied scenario"
+        );
+        Ok(())
+    }
+
+    /// The records of `scenario` as JSON, by table in dependency order. Specified values
+    /// are checked and their references resolved, the other fields are generated.
+    pub fn scenario_records(&mut self, scenario: &Scenario) -> Result<Vec<(String, Vec<Value>)>> {
+        // The scenario's seed and ids only apply while its records are generated
+        let seed = scenario
+            .seed
+            .unwrap_or_else(|| derive_seed(0, &format!("scenario:{}", scenario.name)));
+        let seed = std::mem::replace(&mut self.schemasync_config.mock_gen_config.seed, Some(seed));
+        let id_map = std::mem::take(&mut self.id_map);
+        let reference_plans = std::mem::take(&mut self.reference_plans);
+
+        let records = self.generate_scenario_records(scenario);
+
+        self.schemasync_config.mock_gen_config.seed = seed;
+        self.id_map = id_map;
+        self.reference_plans = reference_plans;
+        records
+    }
+
+    fn generate_scenario_records(
+        &mut self,
+        scenario: &Scenario,
+    ) -> Result<Vec<(String, Vec<Value>)>> {
+        // Every id is known before any value is generated, so records can link to records
+        // listed after them
+        let mut table_records: HashMap<String, Vec<(String, &ScenarioRecord)>> = HashMap::new();
+        let mut references = HashMap::new();
+        for record in &scenario.records {
+            let table_name = self
+                .scenario_table(&record.type_name)
+                .ok_or_else(|| {
+                    EvenframeError::validation(format!(
+                        "Scenario '{}' has a record of type '{}', which is not a table",
+                        scenario.name, record.type_name
+                    ))
+                })?
+                .table_name
+                .clone();
+            let records = table_records.entry(table_name.clone()).or_default();
+            let id = match &record.reference {
+                Some(reference) => {
+                    if !is_valid_reference(reference) {
+                        return Err(EvenframeError::validation(format!(
+                            "Invalid ref '{reference}' in scenario '{}': refs start with a letter or '_' followed by letters, digits or '_'",
+                            scenario.name
+                        )));
+                    }
+                    let id = format!("{table_name}:{reference}");
+                    if references.insert(reference.clone(), id.clone()).is_some() {
+                        return Err(EvenframeError::validation(format!(
+                            "Scenario '{}' names more than one record '{reference}'",
+                            scenario.name
+                        )));
+                    }
+                    id
+                }
+                None => format!("{table_name}:⟨{}_{}⟩", scenario.name, records.len() + 1),
+            };
+            records.push((id, record));
+        }
+
+        self.id_map = self
+            .tables
+            .keys()
+            .map(|table_name| {
+                let ids = table_records
+                    .get(table_name)
+                    .map(|records| records.iter().map(|(id, _)| id.clone()).collect())
+                    .unwrap_or_default();
+                (table_name.clone(), ids)
+            })
+            .collect();
+        self.plan_references();
+
+        let mut records = Vec::new();
+        for table_name in sort_tables_by_dependencies(&self.tables, &self.objects, &self.enums) {
+            let Some(scenario_records) = table_records.get(&table_name) else {
+                continue;
+            };
+            let table = &self.tables[&table_name];
+            let values = scenario_records
+                .iter()
+                .enumerate()
+                .map(|(index, (id, record))| {
+                    self.scenario_record(table, index, id, record, &references)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            records.push((table_name, values));
+        }
+        Ok(records)
+    }
+
+    /// The table of a scenario record's type, by table name or struct name
+    fn scenario_table(&self, type_name: &str) -> Option<&TableConfig> {
+        self.tables
+            .get(type_name)
+            .or_else(|| self.tables.get(&type_name.to_case(Case::Snake)))
+            .or_else(|| {
+                self.tables
+                    .values()
+                    .find(|table| table.struct_config.struct_name == type_name)
+            })
+    }
+
+    /// The record at `index` of its table as JSON, with the specified values checked
+    /// and the others generated
+    fn scenario_record(
+        &self,
+        table: &TableConfig,
+        index: usize,
+        id: &str,
+        record: &ScenarioRecord,
+        references: &HashMap<String, String>,
+    ) -> Result<Value> {
+        let table_name = &table.table_name;
+        let fields: Vec<&StructField> = table
+            .struct_config
+            .fields
+            .iter()
+            .filter(|field| is_generated_field(field) && field.field_name != "id")
+            .collect();
+        if let Some(unknown) = record
+            .fields
+            .keys()
+            .find(|name| !fields.iter().any(|field| &field.field_name == *name))
+        {
+            return Err(EvenframeError::validation(format!(
+                "'{table_name}' has no field '{unknown}' a scenario can set (record ids come from `ref`)"
+            )));
+        }
+
+        let mut object = Map::new();
+        object.insert("id".to_string(), Value::String(id.to_string()));
+        for field in fields {
+            let path = format!("{}.{}", id, field.field_name);
+            let value = match record.fields.get(&field.field_name) {
+                Some(value) => {
+                    self.check_value(&field.field_type, value, &path)?;
+                    check_constraints(
+                        &ValueConstraints::from_validators(&field.validators),
+                        value,
+                        &path,
+                    )?;
+                    self.resolve_references(&field.field_type, value, references, &path)?
+                }
+                None => self.generated_value(table, field, index, &path)?,
+            };
+            object.insert(field.field_name.clone(), value);
+        }
+        Ok(Value::Object(object))
+    }
+
+    /// A generated value for an unspecified field. Links to tables without records in
+    /// the scenario are left empty when the field allows it.
+    fn generated_value(
+        &self,
+        table: &TableConfig,
+        field: &StructField,
+        index: usize,
+        path: &str,
+    ) -> Result<Value> {
+        let target = match (&table.relation, field.field_name.as_str()) {
+            (Some(relation), "in") => Some(relation.from.clone()),
+            (Some(relation), "out") => Some(relation.to.clone()),
+            _ => self.linked_table(&field.field_type),
+        };
+        if let Some(target) = target
+            && self.id_map.get(&target).is_none_or(Vec::is_empty)
+        {
+            return match &field.field_type {
+                FieldType::Option(_) => Ok(Value::Null),
+                FieldType::Vec(_) | FieldType::HashSet(_) | FieldType::BTreeSet(_) => {
+                    Ok(Value::Array(Vec::new()))
+                }
+                _ => Err(EvenframeError::validation(format!(
+                    "'{path}' links to '{target}', which has no records in the scenario. Set it or add a '{target}' record"
+                ))),
+            };
+        }
+
+        let literal = FieldValueGenerator::builder()
+            .field(field)
+            .id_index(&index)
+            .mockmaker(self)
+            .table_config(table)
+            .build()
+            .run();
+        Ok(surql_to_json(&literal))
+    }
+
+    /// The table a link of `field_type`, or a collection of them, points at
+    fn linked_table(&self, field_type: &FieldType) -> Option<String> {
+        match field_type {
+            FieldType::RecordLink(inner) => match inner.as_ref() {
+                FieldType::Other(type_name) => self
+                    .tables
+                    .get(&type_name.to_case(Case::Snake))
+                    .map(|table| table.table_name.clone()),
+                _ => None,
+            },
+            FieldType::Option(inner)
+            | FieldType::Vec(inner)
+            | FieldType::HashSet(inner)
+            | FieldType::BTreeSet(inner) => self.linked_table(inner),
+            _ => None,
+        }
+    }
+
+    /// Whether `type_name` is the type of a table, which values link to by id
+    fn is_table(&self, type_name: &str) -> bool {
+        self.tables.contains_key(&type_name.to_case(Case::Snake))
+    }
+
+    /// Check that `value` has the shape of `field_type`. Enums and values the database
+    /// parses (dates, ids, geometries) are left to the database.
+    fn check_value(&self, field_type: &FieldType, value: &Value, path: &str) -> Result<()> {
+        let valid = match (field_type, value) {
+            (FieldType::Option(_) | FieldType::Unit, Value::Null) => true,
+            (FieldType::Option(inner) | FieldType::OrderedFloat(inner), _) => {
+                return self.check_value(inner, value, path);
+            }
+            (FieldType::Bool, Value::Bool(_)) => true,
+            (FieldType::F32 | FieldType::F64, Value::Number(_)) => true,
+            (
+                FieldType::I8
+                | FieldType::I16
+                | FieldType::I32
+                | FieldType::I64
+                | FieldType::I128
+                | FieldType::Isize,
+                Value::Number(number),
+            ) => number.is_i64() || number.is_u64(),
+            (
+                FieldType::U8
+                | FieldType::U16
+                | FieldType::U32
+                | FieldType::U64
+                | FieldType::U128
+                | FieldType::Usize,
+                Value::Number(number),
+            ) => number.is_u64(),
+            (FieldType::Decimal, Value::Number(_) | Value::String(_)) => true,
+            (
+                FieldType::String
+                | FieldType::Char
+                | FieldType::EvenframeRecordId
+                | FieldType::RecordLink(_)
+                | FieldType::DateTime
+                | FieldType::NaiveDate
+                | FieldType::NaiveTime
+                | FieldType::Timezone
+                | FieldType::Uuid
+                | FieldType::EvenframeDuration
+                | FieldType::Duration
+                | FieldType::IpAddr
+                | FieldType::Url,
+                Value::String(_),
+            ) => true,
+            (
+                FieldType::Vec(item) | FieldType::HashSet(item) | FieldType::BTreeSet(item),
+                Value::Array(items),
+            ) => {
+                for (i, item_value) in items.iter().enumerate() {
+                    self.check_value(item, item_value, &format!("{path}[{i}]"))?;
+                }
+                true
+            }
+            (FieldType::Tuple(types), Value::Array(items)) if types.len() == items.len() => {
+                for (i, (item, item_value)) in types.iter().zip(items).enumerate() {
+                    self.check_value(item, item_value, &format!("{path}[{i}]"))?;
+                }
+                true
+            }
+            (
+                FieldType::HashMap(_, item) | FieldType::BTreeMap(_, item),
+                Value::Object(entries),
+            ) => {
+                for (key, item_value) in entries {
+                    self.check_value(item, item_value, &format!("{path}.{key}"))?;
+                }
+                true
+            }
+            (FieldType::Struct(fields), Value::Object(entries)) => {
+                let fields = fields
+                    .iter()
+                    .map(|(name, field_type)| (name.as_str(), field_type));
+                self.check_object(fields, entries, path)?;
+                true
+            }
+            (FieldType::Other(type_name), _) if self.is_table(type_name) => value.is_string(),
+            (FieldType::Other(type_name), _) => match self.objects.get(type_name) {
+                Some(object) => {
+                    let Value::Object(entries) = value else {
+                        return Err(mismatch(field_type, value, path));
+                    };
+                    let fields = object
+                        .fields
+                        .iter()
+                        .map(|field| (field.field_name.as_str(), &field.field_type));
+                    self.check_object(fields, entries, path)?;
+                    for field in &object.fields {
+                        if let Some(entry) = entries.get(&field.field_name) {
+                            check_constraints(
+                                &ValueConstraints::from_validators(&field.validators),
+                                entry,
+                                &format!("{path}.{}", field.field_name),
+                            )?;
+                        }
+                    }
+                    true
+                }
+                None => true,
+            },
+            (
+                FieldType::JsonValue
+                | FieldType::Bytes
+                | FieldType::Geometry(_)
+                | FieldType::Generic(..)
+                | FieldType::GenericParam(_),
+                _,
+            ) => true,
+            _ => false,
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(mismatch(field_type, value, path))
+        }
+    }
+
+    /// Check the entries of an object against its fields. Missing fields are left to the
+    /// database.
+    fn check_object<'f>(
+        &self,
+        fields: impl Iterator<Item = (&'f str, &'f FieldType)> + Clone,
+        entries: &Map<String, Value>,
+        path: &str,
+    ) -> Result<()> {
+        for (key, value) in entries {
+            let (_, field_type) = fields
+                .clone()
+                .find(|(name, _)| *name == key.as_str())
+                .ok_or_else(|| {
+                    EvenframeError::validation(format!("'{path}' has no field '{key}'"))
+                })?;
+            self.check_value(field_type, value, &format!("{path}.{key}"))?;
+        }
+        Ok(())
+    }
+
+    /// `value` with its `"@<ref>"` links replaced by the ids of the referenced records
+    fn resolve_references(
+        &self,
+        field_type: &FieldType,
+        value: &Value,
+        references: &HashMap<String, String>,
+        path: &str,
+    ) -> Result<Value> {
+        match (field_type, value) {
+            (FieldType::RecordLink(_) | FieldType::EvenframeRecordId, Value::String(link)) => {
+                resolve_reference(link, references, path)
+            }
+            (FieldType::Other(type_name), Value::String(link)) if self.is_table(type_name) => {
+                resolve_reference(link, references, path)
+            }
+            (FieldType::Option(inner), _) => {
+                self.resolve_references(inner, value, references, path)
+            }
+            (
+                FieldType::Vec(item) | FieldType::HashSet(item) | FieldType::BTreeSet(item),
+                Value::Array(items),
+            ) => items
+                .iter()
+                .enumerate()
+                .map(|(i, item_value)| {
+                    self.resolve_references(item, item_value, references, &format!("{path}[{i}]"))
+                })
+                .collect::<Result<Vec<_>>>()
+                .map(Value::Array),
+            (FieldType::Tuple(types), Value::Array(items)) => types
+                .iter()
+                .zip(items)
+                .enumerate()
+                .map(|(i, (item, item_value))| {
+                    self.resolve_references(item, item_value, references, &format!("{path}[{i}]"))
+                })
+                .collect::<Result<Vec<_>>>()
+                .map(Value::Array),
+            (
+                FieldType::HashMap(_, item) | FieldType::BTreeMap(_, item),
+                Value::Object(entries),
+            ) => self.resolve_entries(entries, |_| Some(item.as_ref()), references, path),
+            (FieldType::Struct(fields), Value::Object(entries)) => self.resolve_entries(
+                entries,
+                |key| {
+                    fields
+                        .iter()
+                        .find(|(name, _)| name == key)
+                        .map(|(_, field_type)| field_type)
+                },
+                references,
+                path,
+            ),
+            (FieldType::Other(type_name), Value::Object(entries)) => {
+                match self.objects.get(type_name) {
+                    Some(object) => self.resolve_entries(
+                        entries,
+                        |key| {
+                            object
+                                .fields
+                                .iter()
+                                .find(|field| field.field_name == key)
+                                .map(|field| &field.field_type)
+                        },
+                        references,
+                        path,
+                    ),
+                    None => Ok(value.clone()),
+                }
+            }
+            _ => Ok(value.clone()),
+        }
+    }
+
+    /// `entries` with the references of each value resolved, by the type `entry_type`
+    /// gives its key
+    fn resolve_entries<'t>(
+        &self,
+        entries: &Map<String, Value>,
+        entry_type: impl Fn(&str) -> Option<&'t FieldType>,
+        references: &HashMap<String, String>,
+        path: &str,
+    ) -> Result<Value> {
+        entries
+            .iter()
+            .map(|(key, entry)| {
+                let resolved = match entry_type(key) {
+                    Some(field_type) => self.resolve_references(
+                        field_type,
+                        entry,
+                        references,
+                        &format!("{path}.{key}"),
+                    )?,
+                    None => entry.clone(),
+                };
+                Ok((key.clone(), resolved))
+            })
+            .collect::<Result<Map<_, _>>>()
+            .map(Value::Object)
+    }
+}
+
+/// The id `link` refers to: the record named by an `"@<ref>"`, else the link itself
+fn resolve_reference(
+    link: &str,
+    references: &HashMap<String, String>,
+    path: &str,
+) -> Result<Value> {
+    let Some(reference) = link.strip_prefix('@') else {
+        return Ok(Value::String(link.to_string()));
+    };
+    references
+        .get(reference)
+        .map(|id| Value::String(id.clone()))
+        .ok_or_else(|| {
+            EvenframeError::validation(format!(
+                "'{path}' links to '@{reference}', but no record of the scenario has that ref"
+            ))
+        })
+}
+
+/// Refs are used as record keys, so they are identifiers that can't be mistaken for
+/// numbered ids
+fn is_valid_reference(reference: &str) -> bool {
+    let mut chars = reference.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn mismatch(field_type: &FieldType, value: &Value, path: &str) -> EvenframeError {
+    EvenframeError::validation(format!("'{path}' is a {field_type}, which {value} is not"))
+}
+
+/// Check `value` against the constraints of its field's validators. They apply to the
+/// value, and to the items of a collection.
+fn check_constraints(constraints: &ValueConstraints, value: &Value, path: &str) -> Result<()> {
+    let violation = match value {
+        Value::String(string) => string_violation(constraints, string),
+        Value::Number(number) => number
+            .as_f64()
+            .filter(|number| !constraints.admits(*number))
+            .map(|_| "is outside the allowed range".to_string()),
+        Value::Array(items) => {
+            if constraints.min_items.is_some_and(|min| items.len() < min)
+                || constraints.max_items.is_some_and(|max| items.len() > max)
+            {
+                Some(format!("has {} items", items.len()))
+            } else {
+                for (i, item) in items.iter().enumerate() {
+                    check_constraints(constraints, item, &format!("{path}[{i}]"))?;
+                }
+                None
+            }
+        }
+        _ => None,
+    };
+    match violation {
+        Some(violation) => Err(EvenframeError::validation(format!(
+            "'{path}' {violation}, which its validators don't allow"
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// How `value` breaks the string and date constraints, if it does. Formats are
+/// patterns for generating values, narrower than what their validators accept, so they
+/// aren't checked.
+fn string_violation(constraints: &ValueConstraints, value: &str) -> Option<String> {
+    let length = value.chars().count();
+    if let Some(literal) = constraints
+        .literal
+        .as_ref()
+        .filter(|literal| *literal != value)
+    {
+        return Some(format!("is not '{literal}'"));
+    }
+    if constraints.min_length.is_some_and(|min| length < min)
+        || constraints.max_length.is_some_and(|max| length > max)
+    {
+        return Some(format!("has {length} characters"));
+    }
+    if let Some(prefix) = constraints
+        .prefix
+        .as_ref()
+        .filter(|p| !value.starts_with(*p))
+    {
+        return Some(format!("doesn't start with '{prefix}'"));
+    }
+    if let Some(suffix) = constraints.suffix.as_ref().filter(|s| !value.ends_with(*s)) {
+        return Some(format!("doesn't end with '{suffix}'"));
+    }
+    if let Some(part) = constraints
+        .includes
+        .iter()
+        .find(|part| !value.contains(*part))
+    {
+        return Some(format!("doesn't include '{part}'"));
+    }
+    let cased = match constraints.case {
+        Some(LetterCase::Lower) => value == value.to_lowercase(),
+        Some(LetterCase::Upper) => value == value.to_uppercase(),
+        Some(LetterCase::Capitalized) => !value.chars().next().is_some_and(char::is_lowercase),
+        None => true,
+    };
+    if !cased {
+        return Some(format!("is not {:?} case", constraints.case?));
+    }
+    if constraints.constrains_dates()
+        && let Ok(date) = DateTime::parse_from_rfc3339(value)
+    {
+        let date = date.with_timezone(&Utc);
+        if constraints.earliest.is_some_and(|earliest| date < earliest)
+            || constraints.latest.is_some_and(|latest| date > latest)
+        {
+            return Some("is outside the allowed dates".to_string());
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        schemasync::{StructConfig, config::SchemasyncConfig},
+        validator::StringValidator,
+    };
+
+    #[test]
+    fn test_scenario_file() {
+        let scenario: Scenario = toml::from_str(
+            r#"
+            description = "Organization with an overdue invoice"
+
+            [[records]]
+            type = "Organization"
+            ref = "acme"
+            fields = { name = "Acme" }
+
+            [[records]]
+            type = "Invoice"
+            fields = { organization = "@acme", total = 120 }
+            "#,
+        )
+        .unwrap();
+        assert!(!scenario.reset);
+        assert_eq!(scenario.records.len(), 2);
+        assert_eq!(scenario.records[0].reference.as_deref(), Some("acme"));
+        assert_eq!(scenario.records[1].fields["organization"], "@acme");
+    }
+
+    #[test]
+    fn test_references_and_constraints() {
+        let references = HashMap::from([("acme".to_string(), "organization:acme".to_string())]);
+        assert_eq!(
+            resolve_reference("@acme", &references, "invoice:1.organization").unwrap(),
+            "organization:acme"
+        );
+        assert!(resolve_reference("@globex", &references, "invoice:1.organization").is_err());
+        assert!(is_valid_reference("acme_2"));
+        assert!(!is_valid_reference("2"));
+
+        let constraints = ValueConstraints::from_validators(&[
+            StringValidator::StartsWith("ord_".to_string()).into(),
+            StringValidator::MinLength(6).into(),
+        ]);
+        let check =
+            |value: &str| check_constraints(&constraints, &Value::from(value), "order:1.code");
+        assert!(check("ord_1234").is_ok());
+        assert!(check("ord_1").is_err());
+        assert!(check("inv_1234").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_scenario_records_leave_the_run_alone() {
+        let organization = TableConfig {
+            table_name: "organization".to_string(),
+            struct_config: StructConfig::for_testing(
+                "Organization",
+                vec![StructField {
+                    field_name: "name".to_string(),
+                    field_type: FieldType::String,
+                    ..Default::default()
+                }],
+            ),
+            relation: None,
+            permissions: None,
+            mock_generation_config: None,
+        };
+        let config = SchemasyncConfig::for_testing();
+        let db = surrealdb::engine::any::connect("mem://").await.unwrap();
+        let mut mockmaker = Mockmaker::new(
+            db,
+            HashMap::from([("organization".to_string(), organization)]),
+            HashMap::new(),
+            HashMap::new(),
+            config,
+        );
+
+        let mut scenario: Scenario = toml::from_str(
+            r#"
+            [[records]]
+            type = "Organization"
+            ref = "acme"
+
+            [[records]]
+            type = "Organization"
+            "#,
+        )
+        .unwrap();
+        scenario.name = "onboarding".to_string();
+        let records = mockmaker.scenario_records(&scenario).unwrap();
+        assert_eq!(records[0].1[0]["id"], "organization:acme");
+        // Unnamed records don't take the ids of mock records
+        assert_eq!(records[0].1[1]["id"], "organization:⟨onboarding_2⟩");
+        assert!(mockmaker.id_map.is_empty());
+        assert_eq!(mockmaker.schemasync_config.mock_gen_config.seed, None);
+
+        // Links nested in maps and inline structs are resolved too
+        let link = FieldType::RecordLink(Box::new(FieldType::Other("Organization".to_string())));
+        let references = HashMap::from([("acme".to_string(), "organization:acme".to_string())]);
+        let by_region = FieldType::HashMap(Box::new(FieldType::String), Box::new(link.clone()));
+        assert_eq!(
+            mockmaker
+                .resolve_references(
+                    &by_region,
+                    &serde_json::json!({ "eu": "@acme" }),
+                    &references,
+                    "user:1.organizations",
+                )
+                .unwrap(),
+            serde_json::json!({ "eu": "organization:acme" })
+        );
+        let membership = FieldType::Struct(vec![
+            ("organization".to_string(), link),
+            ("role".to_string(), FieldType::String),
+        ]);
+        assert_eq!(
+            mockmaker
+                .resolve_references(
+                    &membership,
+                    &serde_json::json!({ "organization": "@acme", "role": "@admin" }),
+                    &references,
+                    "user:1.membership",
+                )
+                .unwrap(),
+            serde_json::json!({ "organization": "organization:acme", "role": "@admin" })
+        );
+    }
+}
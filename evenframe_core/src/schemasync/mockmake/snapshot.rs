@@ -0,0 +1,485 @@
+//! Anonymized production snapshots as a mock data source.
+//!
+//! With `schemasync.snapshot.enabled`, the mock data of a run is copied from a backup
+//! (such as the exports `write_backup` produces) instead of being generated. The backup
+//! is imported into an in-memory database, where every field is anonymized by its rule:
+//! kept as is, replaced by a generated value, hashed or removed. Rules come from
+//! `schemasync.snapshot.anonymize` and `#[mock_data(anonymize(country = Keep))]`.
+//!
+//! Anonymization fails closed: fields without a rule are faked, and columns the struct
+//! does not declare (such as leftovers of older schemas) are removed before the records
+//! are copied. Only record ids and links are kept by default, so links between records
+//! stay valid, and faked links point at records of the snapshot.
+
+use crate::{
+    dependency::sort_tables_by_dependencies,
+    error::{EvenframeError, Result},
+    mockmake::{Mockmaker, field_value::FieldValueGenerator},
+    schemasync::{
+        TableConfig,
+        backup::{export_records, resolve_backup},
+        surql::{is_stored_field, transaction::database_definitions},
+    },
+    types::{FieldType, StructField},
+    wrappers::EvenframeRecordId,
+};
+use quote::{ToTokens, quote};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs};
+use surrealdb::{
+    Surreal,
+    engine::local::{Db, Mem},
+};
+use tracing::{debug, info, trace};
+
+/// What happens to the value of a field copied from a snapshot
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Anonymization {
+    /// Copy the value as is
+    Keep,
+    /// Replace the value with a generated one, following the field's `#[format]`. Fields
+    /// without a rule are faked.
+    #[default]
+    Fake,
+    /// Replace a string with its salted SHA-256 hash, keeping equal values equal
+    Hash,
+    /// Remove the value
+    Null,
+}
+
+impl TryFrom<&str> for Anonymization {
+    type Error = String;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        match value.to_lowercase().as_str() {
+            "keep" => Ok(Anonymization::Keep),
+            "fake" => Ok(Anonymization::Fake),
+            "hash" => Ok(Anonymization::Hash),
+            "null" => Ok(Anonymization::Null),
+            _ => Err(format!(
+                "Unknown anonymization '{}', expected Keep, Fake, Hash or Null",
+                value
+            )),
+        }
+    }
+}
+
+impl ToTokens for Anonymization {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let variant = match self {
+            Anonymization::Keep => quote! { Keep },
+            Anonymization::Fake => quote! { Fake },
+            Anonymization::Hash => quote! { Hash },
+            Anonymization::Null => quote! { Null },
+        };
+        tokens.extend(quote! {
+            ::evenframe::schemasync::mockmake::snapshot::Anonymization::#variant
+        });
+    }
+}
+
+impl Mockmaker {
+    /// Load the configured snapshot, anonymize it and keep its records as the mock data
+    /// of this run. The ids of the snapshot replace the generated ones.
+    pub async fn load_snapshot(&mut self) -> Result<()> {
+        let config = &self.schemasync_config.snapshot;
+        if config.backup.is_empty() {
+            return Err(EvenframeError::config(
+                "schemasync.snapshot.backup must name the backup to copy mock data from",
+            ));
+        }
+        let path = resolve_backup(&self.schemasync_config, Some(&config.backup))?;
+        info!(path = %path.display(), "Loading mock data snapshot");
+
+        let imported = memory_database().await?;
+        run(&imported, fs::read_to_string(&path)?, "import the snapshot").await?;
+
+        // Only the records of the managed tables are anonymized. The field definitions of
+        // the backup (types, READONLY, ASSERT) would reject the anonymized values.
+        let (defined_tables, _) = database_definitions(&imported).await?;
+        let record_tables: Vec<String> = defined_tables
+            .into_iter()
+            .filter(|table_name| self.tables.contains_key(table_name))
+            .collect();
+        let mut records = Vec::new();
+        export_records(&imported, &record_tables, &mut records).await?;
+        drop(imported);
+        let snapshot = memory_database().await?;
+        if !records.is_empty() {
+            run(
+                &snapshot,
+                String::from_utf8_lossy(&records).into_owned(),
+                "import the snapshot records",
+            )
+            .await?;
+        }
+
+        let mut id_map = HashMap::new();
+        for table_name in self.tables.keys() {
+            let mut response = snapshot
+                .query("SELECT VALUE id FROM type::table($table)")
+                .bind(("table", table_name.clone()))
+                .await
+                .map_err(|e| {
+                    EvenframeError::database(format!(
+                        "Failed to read the ids of '{table_name}' from the snapshot: {e}"
+                    ))
+                })?;
+            let ids: Vec<EvenframeRecordId> = response.take(0).map_err(|e| {
+                EvenframeError::database(format!(
+                    "Failed to read the ids of '{table_name}' from the snapshot: {e}"
+                ))
+            })?;
+            debug!(table = %table_name, records = ids.len(), "Read snapshot ids");
+            id_map.insert(
+                table_name.clone(),
+                ids.iter().map(ToString::to_string).collect(),
+            );
+        }
+        // Faked links point at records of the snapshot, and so do the planned references
+        self.id_map = id_map;
+        self.plan_references();
+
+        for (table_name, table) in &self.tables {
+            let undeclared = undeclared_columns(&snapshot, table_name, table).await?;
+            if !undeclared.is_empty() {
+                info!(
+                    table = %table_name,
+                    columns = ?undeclared,
+                    "Removing undeclared snapshot columns"
+                );
+                run(
+                    &snapshot,
+                    format!("UPDATE {} UNSET {};", table_name, undeclared.join(", ")),
+                    "remove undeclared snapshot columns",
+                )
+                .await?;
+            }
+
+            let statements = self.anonymization_statements(table_name, table)?;
+            if statements.is_empty() {
+                continue;
+            }
+            trace!(table = %table_name, statements = %statements, "Anonymizing snapshot records");
+            run(
+                &snapshot,
+                format!(
+                    "LET $salt = {};\n{}",
+                    serde_json::to_string(&self.schemasync_config.snapshot.salt)?,
+                    statements
+                ),
+                "anonymize the snapshot",
+            )
+            .await?;
+        }
+
+        let mut snapshot_statements = Vec::new();
+        for table_name in sort_tables_by_dependencies(&self.tables, &self.objects, &self.enums) {
+            let mut records = Vec::new();
+            export_records(&snapshot, std::slice::from_ref(&table_name), &mut records).await?;
+            snapshot_statements.push((
+                table_name.clone(),
+                format!(
+                    "DELETE {};\n{}",
+                    table_name,
+                    String::from_utf8_lossy(&records)
+                ),
+            ));
+        }
+        info!(
+            tables = snapshot_statements.len(),
+            "Anonymized mock data snapshot"
+        );
+        self.snapshot_statements = Some(snapshot_statements);
+        Ok(())
+    }
+
+    /// The anonymization rules of `table_name` by field name: every stored field except
+    /// ids and links is faked, unless the config's rules or the table's own say otherwise
+    fn anonymization_rules(
+        &self,
+        table_name: &str,
+        table: &TableConfig,
+    ) -> HashMap<String, Anonymization> {
+        let mut rules = default_rules(table);
+        if let Some(config_rules) = self.schemasync_config.snapshot.anonymize.get(table_name) {
+            rules.extend(config_rules.clone());
+        }
+        if let Some(config) = &table.mock_generation_config {
+            rules.extend(config.anonymize.clone());
+        }
+        rules
+    }
+
+    /// Statements applying the anonymization rules of `table_name` to the snapshot
+    fn anonymization_statements(&self, table_name: &str, table: &TableConfig) -> Result<String> {
+        let mut statements = String::new();
+        for (field_name, rule) in self.anonymization_rules(table_name, table) {
+            let field = table
+                .struct_config
+                .fields
+                .iter()
+                .find(|field| field.field_name == field_name)
+                .ok_or_else(|| {
+                    EvenframeError::config(format!(
+                        "Anonymization rule for unknown field '{field_name}' of table '{table_name}'"
+                    ))
+                })?;
+            check_rule(
+                table_name,
+                field,
+                rule,
+                &self.schemasync_config.snapshot.salt,
+            )?;
+
+            if let Some(statement) = rule_statement(table_name, &field_name, rule) {
+                statements.push_str(&statement);
+            } else if rule == Anonymization::Fake {
+                let ids = self.id_map.get(table_name).map_or(&[][..], Vec::as_slice);
+                for (index, id) in ids.iter().enumerate() {
+                    let value = FieldValueGenerator::builder()
+                        .mockmaker(self)
+                        .table_config(table)
+                        .field(field)
+                        .id_index(&index)
+                        .build()
+                        .run();
+                    statements.push_str(&format!("UPDATE {id} SET {field_name} = {value};\n"));
+                }
+            }
+        }
+        Ok(statements)
+    }
+}
+
+/// The statement applying `rule` to every record of `table_name`, `None` for rules
+/// applied per record or not at all
+fn rule_statement(table_name: &str, field_name: &str, rule: Anonymization) -> Option<String> {
+    match rule {
+        Anonymization::Null => Some(format!("UPDATE {table_name} SET {field_name} = NONE;\n")),
+        Anonymization::Hash => Some(format!(
+            "UPDATE {table_name} SET {field_name} = crypto::sha256(string::concat($salt, {field_name})) WHERE {field_name} != NONE;\n"
+        )),
+        Anonymization::Keep | Anonymization::Fake => None,
+    }
+}
+
+/// `Fake` for every stored field of the table that is neither its id nor a link. Free
+/// text, objects and fields without a `#[format]` can hold personal data just as well.
+fn default_rules(table: &TableConfig) -> HashMap<String, Anonymization> {
+    table
+        .struct_config
+        .fields
+        .iter()
+        .filter(|field| is_stored_field(field) && !is_kept_by_default(table, field))
+        .map(|field| (field.field_name.clone(), Anonymization::Fake))
+        .collect()
+}
+
+/// Ids, links and the ends of relations, which only point at other records
+fn is_kept_by_default(table: &TableConfig, field: &StructField) -> bool {
+    field.field_name == "id"
+        || (table.relation.is_some() && (field.field_name == "in" || field.field_name == "out"))
+        || is_link(&field.field_type)
+}
+
+fn is_link(field_type: &FieldType) -> bool {
+    match field_type {
+        FieldType::RecordLink(_) | FieldType::EvenframeRecordId => true,
+        FieldType::Option(inner)
+        | FieldType::Vec(inner)
+        | FieldType::HashSet(inner)
+        | FieldType::BTreeSet(inner) => is_link(inner),
+        _ => false,
+    }
+}
+
+/// Columns of the snapshot records of `table_name` that the struct does not declare,
+/// escaped for use in a statement
+async fn undeclared_columns(
+    snapshot: &Surreal<Db>,
+    table_name: &str,
+    table: &TableConfig,
+) -> Result<Vec<String>> {
+    let mut response = snapshot
+        .query(
+            "array::distinct(array::flatten((SELECT VALUE object::keys($this) FROM type::table($table))))",
+        )
+        .bind(("table", table_name.to_string()))
+        .await
+        .map_err(|e| {
+            EvenframeError::database(format!(
+                "Failed to read the columns of '{table_name}' from the snapshot: {e}"
+            ))
+        })?;
+    let columns: Vec<String> = response.take(0).map_err(|e| {
+        EvenframeError::database(format!(
+            "Failed to read the columns of '{table_name}' from the snapshot: {e}"
+        ))
+    })?;
+    Ok(columns
+        .into_iter()
+        .filter(|column| {
+            column != "id"
+                && !(table.relation.is_some() && (column == "in" || column == "out"))
+                && !table
+                    .struct_config
+                    .fields
+                    .iter()
+                    .any(|field| &field.field_name == column)
+        })
+        .map(|column| format!("`{}`", column.replace('`', "\\`")))
+        .collect())
+}
+
+/// Fail when `rule` can't be applied to `field`
+fn check_rule(
+    table_name: &str,
+    field: &StructField,
+    rule: Anonymization,
+    salt: &str,
+) -> Result<()> {
+    if rule != Anonymization::Hash {
+        return Ok(());
+    }
+    if !is_string(&field.field_type) {
+        return Err(EvenframeError::config(format!(
+            "Only string fields can be hashed, '{table_name}.{}' is {:?}",
+            field.field_name, field.field_type
+        )));
+    }
+    if salt.is_empty() {
+        return Err(EvenframeError::config(format!(
+            "'{table_name}.{}' is hashed, but schemasync.snapshot.salt is empty. Unsalted hashes of guessable values can be reversed, set a secret salt",
+            field.field_name
+        )));
+    }
+    Ok(())
+}
+
+fn is_string(field_type: &FieldType) -> bool {
+    match field_type {
+        FieldType::String => true,
+        FieldType::Option(inner) => is_string(inner),
+        _ => false,
+    }
+}
+
+/// An empty in-memory database
+async fn memory_database() -> Result<Surreal<Db>> {
+    let db = Surreal::new::<Mem>(()).await.map_err(|e| {
+        EvenframeError::database(format!("Failed to start the snapshot database: {e}"))
+    })?;
+    db.use_ns("snapshot")
+        .use_db("snapshot")
+        .await
+        .map_err(|e| {
+            EvenframeError::database(format!("Failed to use the snapshot database: {e}"))
+        })?;
+    Ok(db)
+}
+
+/// Run `query` on the snapshot database, failing on the first statement error
+async fn run(snapshot: &Surreal<Db>, query: String, action: &str) -> Result<()> {
+    let mut response = snapshot
+        .query(query)
+        .await
+        .map_err(|e| EvenframeError::database(format!("Failed to {action}: {e}")))?;
+    match response.take_errors().into_values().next() {
+        Some(e) => Err(EvenframeError::database(format!("Failed to {action}: {e}"))),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mockmake::format::Format, types::StructConfig};
+
+    #[test]
+    fn test_rule_statements() {
+        assert_eq!(
+            rule_statement("user", "phone", Anonymization::Null).as_deref(),
+            Some("UPDATE user SET phone = NONE;\n")
+        );
+        assert!(
+            rule_statement("user", "email", Anonymization::Hash)
+                .unwrap()
+                .contains("crypto::sha256(string::concat($salt, email))")
+        );
+        assert_eq!(rule_statement("user", "name", Anonymization::Keep), None);
+        assert_eq!(Anonymization::try_from("fake"), Ok(Anonymization::Fake));
+        assert!(Anonymization::try_from("scramble").is_err());
+    }
+
+    fn user_table() -> TableConfig {
+        let field = |name: &str, field_type, format| StructField {
+            field_name: name.to_string(),
+            field_type,
+            format,
+            ..Default::default()
+        };
+        TableConfig {
+            table_name: "user".to_string(),
+            struct_config: StructConfig::for_testing(
+                "User",
+                vec![
+                    field("id", FieldType::EvenframeRecordId, None),
+                    field("email", FieldType::String, Some(Format::Email)),
+                    field("notes", FieldType::String, None),
+                    field("address", FieldType::Other("Address".to_string()), None),
+                    field(
+                        "team",
+                        FieldType::Option(Box::new(FieldType::RecordLink(Box::new(
+                            FieldType::Other("Team".to_string()),
+                        )))),
+                        None,
+                    ),
+                ],
+            ),
+            relation: None,
+            permissions: None,
+            mock_generation_config: None,
+        }
+    }
+
+    #[test]
+    fn test_fields_are_faked_unless_they_are_ids_or_links() {
+        let table = user_table();
+        assert_eq!(
+            default_rules(&table),
+            HashMap::from([
+                ("email".to_string(), Anonymization::Fake),
+                ("notes".to_string(), Anonymization::Fake),
+                ("address".to_string(), Anonymization::Fake),
+            ])
+        );
+        assert_eq!(Anonymization::default(), Anonymization::Fake);
+
+        let email = &table.struct_config.fields[1];
+        assert!(check_rule("user", email, Anonymization::Hash, "").is_err());
+        assert!(check_rule("user", email, Anonymization::Hash, "s3cret").is_ok());
+        assert!(check_rule("user", email, Anonymization::Keep, "").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_undeclared_columns() {
+        let snapshot = memory_database().await.unwrap();
+        run(
+            &snapshot,
+            "CREATE user:1 SET email = 'a@example.com', ssn = '078-05-1120'; \
+             CREATE user:2 SET notes = 'n', `legacy name` = 'x';"
+                .to_string(),
+            "create records",
+        )
+        .await
+        .unwrap();
+
+        let mut columns = undeclared_columns(&snapshot, "user", &user_table())
+            .await
+            .unwrap();
+        columns.sort();
+        assert_eq!(columns, vec!["`legacy name`", "`ssn`"]);
+    }
+}
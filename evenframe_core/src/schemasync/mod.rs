@@ -1,4 +1,5 @@
 // SchemaSync - Database schema synchronization
+pub mod backup;
 pub mod compare;
 pub mod config;
 pub mod edge;
@@ -8,29 +9,42 @@ pub mod surql;
 pub mod table;
 
 use crate::{
-    compare::SchemaChanges,
+    compare::{PreservationMode, SchemaChanges},
     config::EvenframeConfig,
     error::{EvenframeError, Result},
-    schemasync::surql::{define::generate_define_statements, execute::execute_and_validate},
+    schemasync::{
+        config::{AuthConfig, DatabaseConfig},
+        surql::{
+            define::generate_define_statements,
+            execute::execute_and_validate,
+            transaction::{execute_in_transaction, restore_from_backup},
+        },
+    },
+};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
 };
-use std::collections::HashMap;
 use tracing::{debug, error, info, trace};
 
 // Re-export commonly used types
 pub use edge::{Direction, EdgeConfig, Subquery};
 pub use mockmake::{coordinate, format};
 pub use permissions::PermissionsConfig;
-pub use surql::{QueryType, define::DefineConfig, generate_query};
+#[allow(deprecated)]
+pub use surql::{
+    BoundQuery, QueryType, define::DefineConfig, generate_bound_query, generate_query,
+};
 use surrealdb::{
     Surreal,
-    engine::remote::http::{Client, Http},
-    opt::auth::Root,
+    engine::any::{self, Any},
+    opt::auth::{Database, Namespace, Record, Root},
 };
 pub use table::TableConfig;
 
 use crate::{
     evenframe_log,
-    mockmake::Mockmaker,
+    mockmake::{Mockmaker, scenario::Scenario},
     types::{StructConfig, TaggedUnion},
 };
 
@@ -42,7 +56,7 @@ pub struct Schemasync<'a> {
     enums: Option<&'a HashMap<String, TaggedUnion>>,
 
     // Internal state - initialized automatically
-    db: Option<Surreal<Client>>,
+    db: Option<Surreal<Any>>,
     schemasync_config: Option<crate::schemasync::config::SchemasyncConfig>,
 }
 
@@ -96,42 +110,7 @@ impl<'a> Schemasync<'a> {
         );
         trace!("Database name: {}", config.schemasync.database.database);
 
-        let db = Surreal::new::<Http>(&config.schemasync.database.url)
-            .await
-            .map_err(|e| {
-                EvenframeError::database(format!(
-                    "There was a problem creating the HTTP surrealdb client: {e}"
-                ))
-            })
-            .unwrap();
-        debug!("Created SurrealDB connection");
-
-        let username = std::env::var("SURREALDB_USER")
-            .map_err(|_| EvenframeError::EnvVarNotSet("SURREALDB_USER".to_string()))?;
-        let password = std::env::var("SURREALDB_PASSWORD")
-            .map_err(|_| EvenframeError::EnvVarNotSet("SURREALDB_PASSWORD".to_string()))?;
-        debug!("Retrieved database credentials from environment");
-
-        db.signin(Root {
-            username: &username,
-            password: &password,
-        })
-        .await
-        .map_err(|e| {
-            EvenframeError::database(format!("There was a problem signing in as root: {e}"))
-        })?;
-        debug!("Successfully signed in to SurrealDB");
-
-        db.use_ns(&config.schemasync.database.namespace)
-            .use_db(&config.schemasync.database.database)
-            .await
-            .map_err(|e| {
-                EvenframeError::database(format!("There was a problem using to the namespace: {e}"))
-            })?;
-        info!(
-            "Connected to database namespace '{}' and database '{}'",
-            config.schemasync.database.namespace, config.schemasync.database.database
-        );
+        let db = open(&config.schemasync.database).await?;
 
         self.db = Some(db);
         self.schemasync_config = Some(config.schemasync);
@@ -167,6 +146,32 @@ impl<'a> Schemasync<'a> {
             .take()
             .ok_or_else(|| EvenframeError::config("Config failed to initialize"))?;
 
+        let auth = config.database.auth.clone();
+        if auth.can_define_schema() == Some(false) {
+            return Err(EvenframeError::permission(format!(
+                "Schemasync defines tables, fields and accesses, which requires root, namespace or database sign-in, but the database is configured for {} sign-in",
+                auth.level()
+            )));
+        }
+
+        // Fixtures are generated again after the mock data is inserted, so they only
+        // hold the inserted records when every table is seeded
+        if config.fixtures.enabled
+            && config.mock_gen_config.seed.is_none()
+            && let Some(table) = tables.values().find(|table| {
+                table
+                    .mock_generation_config
+                    .as_ref()
+                    .and_then(|mock_config| mock_config.seed)
+                    .is_none()
+            })
+        {
+            return Err(EvenframeError::config(format!(
+                "fixtures.enabled needs mock_gen_config.seed, or a seed on every table ('{}' has none), for the fixtures to match the inserted mock data",
+                table.table_name
+            )));
+        }
+
         info!(
             "Pipeline validation completed - {} tables, {} objects, {} enums",
             tables.len(),
@@ -226,74 +231,271 @@ impl<'a> Schemasync<'a> {
 
         // Run initial ID generation and comparator setup
         info!("Generating IDs for mock data");
-        mockmaker.generate_ids().await?;
+        mockmaker
+            .generate_ids()
+            .await
+            .map_err(|e| permission_context("generate ids", &auth, e.into()))?;
         debug!("ID generation completed");
 
+        if config.should_generate_mocks && config.snapshot.enabled {
+            info!("Copying mock data from an anonymized snapshot");
+            mockmaker.load_snapshot().await?;
+        }
+
+        // Only tables that preserve existing data need their records backed up,
+        // the others are regenerated
+        let default_preservation_mode = &config.mock_gen_config.default_preservation_mode;
+        let record_tables: Vec<String> = tables
+            .iter()
+            .filter(|(_, table)| {
+                let preservation_mode = table
+                    .mock_generation_config
+                    .as_ref()
+                    .map_or(default_preservation_mode, |config| {
+                        &config.preservation_mode
+                    });
+                !matches!(preservation_mode, PreservationMode::None)
+            })
+            .map(|(table_name, _)| table_name.clone())
+            .collect();
+        debug!("Backing up records of {} tables", record_tables.len());
+
         // Run the comparator pipeline
         info!("Running schema comparison pipeline");
         let comparator = mockmaker.comparator.take().unwrap();
-        mockmaker.comparator = Some(comparator.run(&define_statements_string).await?);
+        mockmaker.comparator = Some(
+            comparator
+                .run(&define_statements_string, &record_tables)
+                .await
+                .map_err(|e| permission_context("compare schemas", &auth, e))?,
+        );
         debug!("Schema comparison completed");
 
-        // Continue with the rest of the mockmaker pipeline
+        let comparator = mockmaker.comparator.as_ref().unwrap();
+        let schema_changes = comparator.get_schema_changes().unwrap().clone();
+        let backup_path = comparator.get_backup_path().map(Path::to_path_buf);
+        let migration = &config.migration;
+
+        let migrated = if migration.transactional {
+            info!("Applying schema changes in a transaction");
+            let mut statements = vec![mockmaker.remove_statements(), mockmaker.access_statements()];
+            statements.extend(
+                Self::table_define_statements(&define_statements, &schema_changes)
+                    .into_iter()
+                    .map(|(_, stmt)| stmt),
+            );
+            if migration.include_mock_data {
+                Self::filter_changes(&mut mockmaker)
+                    .await
+                    .map_err(|e| permission_context("filter changes", &auth, e))?;
+                statements.extend(
+                    mockmaker
+                        .mock_data_statements()
+                        .into_iter()
+                        .map(|(_, stmts)| stmts),
+                );
+            }
+            // A cancelled transaction leaves the database untouched, nothing to restore
+            execute_in_transaction(&db, &statements)
+                .await
+                .map_err(|e| permission_context("apply migration", &auth, e))?;
+            debug!("Migration transaction committed");
+
+            if config.should_generate_mocks && !migration.include_mock_data {
+                match Self::filter_changes(&mut mockmaker).await {
+                    Ok(()) => {
+                        info!("Generating mock data");
+                        mockmaker.generate_mock_data().await.map_err(|e| {
+                            error!("Failed to generate mock data: {}", e);
+                            permission_context("generate mock data", &auth, e.into())
+                        })
+                    }
+                    Err(e) => Err(permission_context("filter changes", &auth, e)),
+                }
+            } else {
+                Ok(())
+            }
+        } else {
+            self.migrate_step_by_step(&db, &mut mockmaker, define_statements, &schema_changes)
+                .await
+                .map_err(|(step, e)| permission_context(step, &auth, e))
+        };
+
+        if let Err(e) = migrated {
+            if !migration.restore_on_failure {
+                return Err(e);
+            }
+            let Some(backup_path) = backup_path else {
+                error!(
+                    "Migration failed and backups are disabled, not restoring: {}",
+                    e
+                );
+                return Err(e);
+            };
+            error!(
+                "Migration failed, restoring the database from {}: {}",
+                backup_path.display(),
+                e
+            );
+            let restored = match std::fs::read_to_string(&backup_path) {
+                Ok(backup) => {
+                    let managed_tables: Vec<String> = tables.keys().cloned().collect();
+                    let managed_accesses: Vec<String> = config
+                        .database
+                        .accesses
+                        .iter()
+                        .map(|access| access.name.clone())
+                        .collect();
+                    restore_from_backup(&db, &backup, &managed_tables, &managed_accesses).await
+                }
+                Err(read_error) => Err(read_error.into()),
+            };
+            return match restored {
+                Ok(()) => Err(e),
+                Err(restore_error) => Err(EvenframeError::database(format!(
+                    "{e}; restoring from {} also failed: {restore_error}",
+                    backup_path.display()
+                ))),
+            };
+        }
+
+        debug!("Mock data generation completed");
+
+        if config.fixtures.enabled {
+            info!("Writing mock data fixtures");
+            mockmaker.export_fixtures(
+                Path::new(&config.fixtures.directory),
+                &config.fixtures.formats,
+            )?;
+        }
+
+        info!("Schemasync pipeline execution completed successfully");
+        Ok(())
+    }
+
+    /// Write the mock data fixtures configured in `schemasync.fixtures` without
+    /// connecting to the database. Record ids are numbered from 1 per table, as in an
+    /// empty database. Returns the written files.
+    ///
+    /// Nothing is read back from a previous run: the data is generated again, and only
+    /// matches what a run inserted into an empty database when `mock_gen_config.seed` is
+    /// set.
+    pub fn export_fixtures(self) -> Result<Vec<PathBuf>> {
+        info!("Exporting mock data fixtures without a database");
+        let tables = self
+            .tables
+            .ok_or_else(|| EvenframeError::config("Tables not provided"))?;
+        let objects = self
+            .objects
+            .ok_or_else(|| EvenframeError::config("Objects not provided"))?;
+        let enums = self
+            .enums
+            .ok_or_else(|| EvenframeError::config("Enums not provided"))?;
+        let config = EvenframeConfig::new()?.schemasync;
+
+        // The client is never connected, only the offline generation paths are used
+        let mut mockmaker = Mockmaker::new(
+            Surreal::init(),
+            tables.clone(),
+            objects.clone(),
+            enums.clone(),
+            config.clone(),
+        );
+        mockmaker.generate_offline_ids()?;
+        mockmaker.export_fixtures(
+            Path::new(&config.fixtures.directory),
+            &config.fixtures.formats,
+        )
+    }
+
+    /// Apply the scenario `name` from `schemasync.scenarios.directory` to the configured
+    /// database, whose tables are expected to be defined already
+    pub async fn apply_scenario(self, name: &str) -> Result<()> {
+        let tables = self
+            .tables
+            .ok_or_else(|| EvenframeError::config("Tables not provided"))?;
+        let objects = self
+            .objects
+            .ok_or_else(|| EvenframeError::config("Objects not provided"))?;
+        let enums = self
+            .enums
+            .ok_or_else(|| EvenframeError::config("Enums not provided"))?;
+        let config = EvenframeConfig::new()?.schemasync;
+        let scenario = Scenario::load(Path::new(&config.scenarios.directory), name)?;
+        info!(
+            scenario = %name,
+            records = scenario.records.len(),
+            "Applying scenario"
+        );
+
+        let db = open(&config.database).await?;
+        let mut mockmaker =
+            Mockmaker::new(db, tables.clone(), objects.clone(), enums.clone(), config);
+        mockmaker.apply_scenario(&scenario).await
+    }
+
+    /// Apply removes, accesses, table definitions and mock data one step at a time.
+    /// Errors carry the name of the failing step.
+    async fn migrate_step_by_step(
+        &self,
+        db: &Surreal<Any>,
+        mockmaker: &mut Mockmaker,
+        define_statements: HashMap<&String, String>,
+        schema_changes: &SchemaChanges,
+    ) -> std::result::Result<(), (&'static str, EvenframeError)> {
         info!("Removing old data from database");
         mockmaker.remove_old_data().await.map_err(|e| {
             error!("Failed to remove old data: {}", e);
-            e
+            ("remove old data", e.into())
         })?;
         debug!("Old data removal completed");
 
         info!("Executing access control setup");
         mockmaker.execute_access().await.map_err(|e| {
             error!("Failed to execute access setup: {}", e);
-            e
+            ("define accesses", e.into())
         })?;
-
-        let comparator = mockmaker.comparator.take().unwrap();
-        let comparator_clone = comparator.clone();
-        let schema_changes = comparator.get_schema_changes().unwrap();
-        mockmaker.comparator = Some(comparator_clone);
+        debug!("Access control setup completed");
 
         info!("Defining database tables and schema");
-        self.define_tables(&db, define_statements, schema_changes)
+        self.define_tables(db, define_statements, schema_changes)
             .await
             .map_err(|e| {
                 error!("Failed to define tables: {}", e);
-                e
+                ("define tables", e)
             })?;
         debug!("Table definitions completed successfully");
 
-        debug!("Access control setup completed");
-
-        info!("Filtering schema changes");
-        mockmaker.filter_changes().await.map_err(|e| {
-            error!("Failed to filter changes: {}", e);
-            e
-        })?;
-        debug!("Schema changes filtering completed");
+        Self::filter_changes(mockmaker)
+            .await
+            .map_err(|e| ("filter changes", e))?;
 
-        if self
-            .schemasync_config
-            .unwrap_or(EvenframeConfig::new()?.schemasync)
-            .should_generate_mocks
-        {
+        if mockmaker.schemasync_config.should_generate_mocks {
             info!("Generating mock data");
             mockmaker.generate_mock_data().await.map_err(|e| {
                 error!("Failed to generate mock data: {}", e);
-                e
+                ("generate mock data", e.into())
             })?;
         }
+        Ok(())
+    }
 
-        debug!("Mock data generation completed");
-
-        info!("Schemasync pipeline execution completed successfully");
+    /// Narrow the tables and objects that get mock data to the ones the schema changes
+    /// touch. Runs after the remove and define statements were generated.
+    async fn filter_changes(mockmaker: &mut Mockmaker) -> Result<()> {
+        info!("Filtering schema changes");
+        mockmaker.filter_changes().await.map_err(|e| {
+            error!("Failed to filter changes: {}", e);
+            EvenframeError::from(e)
+        })?;
+        debug!("Schema changes filtering completed");
         Ok(())
     }
 
     /// Define tables in both schemas (this stays in Schemasync)
     async fn define_tables(
         &self,
-        db: &Surreal<Client>,
+        db: &Surreal<Any>,
         define_statments: HashMap<&String, String>,
         schema_changes: &SchemaChanges,
     ) -> Result<()> {
@@ -303,8 +505,8 @@ impl<'a> Schemasync<'a> {
             schema_changes
         );
 
-        let execute = async |name, stmt: &str| -> Result<()> {
-            let define_result = execute_and_validate(db, stmt, "define", name).await;
+        for (table_name, stmt) in Self::table_define_statements(&define_statments, schema_changes) {
+            let define_result = execute_and_validate(db, &stmt, "define", &table_name).await;
             match define_result {
                 Ok(_) => {
                     evenframe_log!(
@@ -312,7 +514,6 @@ impl<'a> Schemasync<'a> {
                         "results.log",
                         true
                     );
-                    Ok(())
                 }
                 Err(e) => {
                     let error_msg =
@@ -321,7 +522,19 @@ impl<'a> Schemasync<'a> {
                     return Err(e.into());
                 }
             }
-        };
+        }
+
+        Ok(())
+    }
+
+    /// The define statements needed for the schema changes, paired with their table:
+    /// every statement of new tables, and for modified tables the table definition
+    /// plus new or changed fields
+    fn table_define_statements(
+        define_statments: &HashMap<&String, String>,
+        schema_changes: &SchemaChanges,
+    ) -> Vec<(String, String)> {
+        let mut statements = Vec::new();
 
         // Process new tables first
         if !schema_changes.new_tables.is_empty() {
@@ -331,7 +544,7 @@ impl<'a> Schemasync<'a> {
                     debug!("Defining new table: {}", table_name);
                     for stmt in define_stmt.split_inclusive(';') {
                         if stmt.starts_with("DEFINE TABLE") || stmt.starts_with("DEFINE FIELD") {
-                            execute(table_name, stmt).await?;
+                            statements.push((table_name.clone(), stmt.to_string()));
                         }
                     }
                 }
@@ -354,7 +567,7 @@ impl<'a> Schemasync<'a> {
                     for stmt in define_stmt.split_inclusive(';') {
                         if stmt.starts_with("DEFINE TABLE") {
                             debug!("Redefining table structure for: {}", table_name);
-                            execute(table_name, stmt).await?;
+                            statements.push((table_name.clone(), stmt.to_string()));
                         }
                     }
 
@@ -388,7 +601,7 @@ impl<'a> Schemasync<'a> {
                                             "Defining field: {} on table: {}",
                                             field_name, table_name
                                         );
-                                        execute(table_name, stmt).await?;
+                                        statements.push((table_name.clone(), stmt.to_string()));
                                     } else {
                                         trace!(
                                             "Skipping unchanged field: {} on table: {}",
@@ -431,6 +644,151 @@ impl<'a> Schemasync<'a> {
             }
         }
 
-        Ok(())
+        statements
+    }
+}
+
+/// Connect, sign in (unless embedded) and select the configured namespace and database
+pub async fn open(database: &DatabaseConfig) -> Result<Surreal<Any>> {
+    let db = connect(&database.endpoint()).await?;
+    debug!("Created SurrealDB connection");
+
+    if database.is_embedded() {
+        debug!("Embedded database, skipping sign in");
+    } else {
+        signin(&db, database).await?;
+    }
+
+    db.use_ns(&database.namespace)
+        .use_db(&database.database)
+        .await
+        .map_err(|e| {
+            EvenframeError::database(format!("There was a problem using to the namespace: {e}"))
+        })?;
+    info!(
+        "Connected to database namespace '{}' and database '{}'",
+        database.namespace, database.database
+    );
+    Ok(db)
+}
+
+/// Connect to SurrealDB, picking the engine from the URL scheme (`http://`, `ws://`,
+/// `mem://`, `surrealkv://`, ...)
+pub async fn connect(url: &str) -> Result<Surreal<Any>> {
+    debug!(url = %url, "Connecting to SurrealDB");
+    any::connect(url).await.map_err(|e| {
+        EvenframeError::database(format!(
+            "There was a problem connecting to surrealdb at '{url}': {e}"
+        ))
+    })
+}
+
+/// Sign in with the configured authentication level
+pub async fn signin(db: &Surreal<Any>, database: &DatabaseConfig) -> Result<()> {
+    let auth = &database.auth;
+    debug!(level = auth.level(), "Signing in to SurrealDB");
+
+    let credentials =
+        |username: &Option<String>, password: &Option<String>| -> Result<(String, String)> {
+            let username = match username {
+                Some(username) => username.clone(),
+                None => std::env::var("SURREALDB_USER")
+                    .map_err(|_| EvenframeError::EnvVarNotSet("SURREALDB_USER".to_string()))?,
+            };
+            let password = match password {
+                Some(password) => password.clone(),
+                None => std::env::var("SURREALDB_PASSWORD")
+                    .map_err(|_| EvenframeError::EnvVarNotSet("SURREALDB_PASSWORD".to_string()))?,
+            };
+            Ok((username, password))
+        };
+
+    let result = match auth {
+        AuthConfig::Root { username, password } => {
+            let (username, password) = credentials(username, password)?;
+            db.signin(Root {
+                username: &username,
+                password: &password,
+            })
+            .await
+            .map(|_| ())
+        }
+        AuthConfig::Namespace { username, password } => {
+            let (username, password) = credentials(username, password)?;
+            db.signin(Namespace {
+                namespace: &database.namespace,
+                username: &username,
+                password: &password,
+            })
+            .await
+            .map(|_| ())
+        }
+        AuthConfig::Database { username, password } => {
+            let (username, password) = credentials(username, password)?;
+            db.signin(Database {
+                namespace: &database.namespace,
+                database: &database.database,
+                username: &username,
+                password: &password,
+            })
+            .await
+            .map(|_| ())
+        }
+        AuthConfig::Record { access, params } => {
+            let mut params = params.clone();
+            if let Ok(env_params) = std::env::var("SURREALDB_RECORD_PARAMS") {
+                let env_params: serde_json::Map<String, serde_json::Value> =
+                    serde_json::from_str(&env_params).map_err(|e| {
+                        EvenframeError::InvalidConfigValue {
+                            key: "SURREALDB_RECORD_PARAMS".to_string(),
+                            value: e.to_string(),
+                        }
+                    })?;
+                params.extend(env_params);
+            }
+            db.signin(Record {
+                namespace: &database.namespace,
+                database: &database.database,
+                access,
+                params,
+            })
+            .await
+            .map(|_| ())
+        }
+        AuthConfig::Token { token } => {
+            let token = match token {
+                Some(token) => token.clone(),
+                None => std::env::var("SURREALDB_TOKEN")
+                    .map_err(|_| EvenframeError::EnvVarNotSet("SURREALDB_TOKEN".to_string()))?,
+            };
+            db.authenticate(token).await.map(|_| ())
+        }
+        AuthConfig::None => Ok(()),
+    };
+
+    result.map_err(|e| {
+        EvenframeError::database(format!(
+            "There was a problem signing in at {} level: {e}",
+            auth.level()
+        ))
+    })?;
+    debug!("Successfully signed in to SurrealDB");
+    Ok(())
+}
+
+/// Turn permission failures of a pipeline step into an error naming the step and the
+/// configured sign-in level
+fn permission_context(step: &str, auth: &AuthConfig, error: EvenframeError) -> EvenframeError {
+    let message = error.to_string();
+    let lowered = message.to_lowercase();
+    if lowered.contains("permission") || lowered.contains("iam error") {
+        EvenframeError::permission(format!(
+            "Step '{}' failed because the {} sign-in lacks the required permissions: {}",
+            step,
+            auth.level(),
+            message
+        ))
+    } else {
+        error
     }
 }
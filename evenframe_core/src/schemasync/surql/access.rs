@@ -2,7 +2,7 @@ use crate::evenframe_log;
 use crate::schemasync::config::{AccessConfig, AccessType};
 use std::env;
 use surrealdb::{
-    engine::{local::Db, remote::http::Client},
+    engine::{any::Any, local::Db},
     Surreal,
 };
 use tracing;
@@ -57,7 +57,7 @@ pub fn generate_access_definition(access_config: &AccessConfig) -> String {
     query
 }
 pub async fn execute_access_query(
-    db: &Surreal<Client>,
+    db: &Surreal<Any>,
     access_query: &str,
 ) -> Result<(), Box<dyn std::error::Error>> {
     tracing::debug!(query_length = access_query.len(), "Executing access query");
@@ -0,0 +1,171 @@
+//! Typed live query subscriptions.
+//!
+//! `LIVE SELECT` notifications are decoded through the struct's `Deserialize` impl, so
+//! pushed records pass the same validators as records read with a regular query. Live
+//! queries need a connection that supports them (WebSocket or an embedded engine).
+
+use crate::{
+    error::{EvenframeError, Result},
+    schemasync::surql::repository::{Filter, decode, table_config},
+    traits::EvenframePersistableStruct,
+};
+use futures::{StreamExt, stream::BoxStream};
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use surrealdb::{Action, Connection, Notification, Surreal};
+use tracing::{debug, trace};
+
+/// A change pushed by a live query
+#[derive(Debug, Clone, PartialEq)]
+pub enum LiveEvent<T> {
+    Created(T),
+    Updated(T),
+    /// The record as it was before deletion
+    Deleted(T),
+}
+
+impl<T> LiveEvent<T> {
+    pub fn record(&self) -> &T {
+        match self {
+            LiveEvent::Created(record)
+            | LiveEvent::Updated(record)
+            | LiveEvent::Deleted(record) => record,
+        }
+    }
+
+    pub fn into_record(self) -> T {
+        match self {
+            LiveEvent::Created(record)
+            | LiveEvent::Updated(record)
+            | LiveEvent::Deleted(record) => record,
+        }
+    }
+}
+
+/// Stream of typed notifications, ended when the live query is killed or the
+/// connection closes
+pub type LiveStream<T> = BoxStream<'static, Result<LiveEvent<T>>>;
+
+/// Subscribe to changes of records of `T` matching `filter`
+pub async fn live<T, C>(db: &Surreal<C>, filter: Filter) -> Result<LiveStream<T>>
+where
+    T: EvenframePersistableStruct + DeserializeOwned + Send + 'static,
+    C: Connection,
+{
+    let table = table_config::<T>()?;
+    let mut bindings = Map::new();
+
+    // LIVE SELECT needs a literal table name; it comes from the struct, never from input
+    let mut query = format!("LIVE SELECT * FROM {}", table.table_name);
+    if let Some(clause) = filter.where_clause(&table, &mut bindings)? {
+        query.push_str(&format!(" WHERE {}", clause));
+    }
+    debug!(table = %table.table_name, query = %query, "Starting live query");
+
+    let mut response = db
+        .query(query)
+        .bind(Value::Object(bindings))
+        .await
+        .map_err(|e| EvenframeError::database(e.to_string()))?;
+    let notifications = response
+        .stream::<Notification<surrealdb::Value>>(0)
+        .map_err(|e| EvenframeError::query_execution(e.to_string()))?;
+
+    let events = notifications.filter_map(|notification| async move {
+        let notification = match notification {
+            Ok(notification) => notification,
+            Err(e) => return Some(Err(EvenframeError::database(e.to_string()))),
+        };
+        let record = match serde_json::to_value(&notification.data) {
+            Ok(value) => decode::<T>(value),
+            Err(e) => Err(e.into()),
+        };
+        match notification.action {
+            Action::Create => Some(record.map(LiveEvent::Created)),
+            Action::Update => Some(record.map(LiveEvent::Updated)),
+            Action::Delete => Some(record.map(LiveEvent::Deleted)),
+            action => {
+                trace!(action = ?action, "Skipping unsupported live notification");
+                None
+            }
+        }
+    });
+    Ok(events.boxed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        schemasync::TableConfig,
+        types::{FieldType, StructConfig, StructField},
+    };
+    use serde::Deserialize;
+    use std::time::Duration;
+    use surrealdb::engine::local::Mem;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Message {
+        text: String,
+    }
+
+    impl EvenframePersistableStruct for Message {
+        fn table_config() -> Option<TableConfig> {
+            Some(TableConfig {
+                table_name: "message".to_string(),
+                struct_config: StructConfig::for_testing(
+                    "Message",
+                    vec![StructField {
+                        field_type: FieldType::String,
+                        ..StructField::unit("text".to_string())
+                    }],
+                ),
+                relation: None,
+                permissions: None,
+                mock_generation_config: None,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_live_events() {
+        let db = Surreal::new::<Mem>(()).await.unwrap();
+        db.use_ns("test").use_db("test").await.unwrap();
+        db.query("DEFINE TABLE message SCHEMALESS")
+            .await
+            .unwrap()
+            .check()
+            .unwrap();
+
+        let mut events = live::<Message, _>(&db, Filter::new().ne("text", "muted"))
+            .await
+            .unwrap();
+        db.query(
+            "CREATE message:1 SET text = 'hello';
+            CREATE message:2 SET text = 'muted';
+            UPDATE message:1 SET text = 'edited';
+            DELETE message:1;",
+        )
+        .await
+        .unwrap()
+        .check()
+        .unwrap();
+
+        let message = |text: &str| Message {
+            text: text.to_string(),
+        };
+        // Records that don't match the filter are not pushed
+        for expected in [
+            LiveEvent::Created(message("hello")),
+            LiveEvent::Updated(message("edited")),
+            LiveEvent::Deleted(message("edited")),
+        ] {
+            let event = tokio::time::timeout(Duration::from_secs(5), events.next())
+                .await
+                .expect("Live event should arrive")
+                .unwrap()
+                .unwrap();
+            assert_eq!(event, expected);
+        }
+    }
+}
@@ -3,15 +3,24 @@ pub mod assert;
 pub mod define;
 pub mod execute;
 pub mod insert;
+pub mod live;
+pub mod query;
+pub mod relation;
 pub mod remove;
-pub mod upsert;
+pub mod repository;
+pub mod transaction;
 
 use crate::{
-    schemasync::table::TableConfig,
+    error::{EvenframeError, Result},
+    schemasync::{
+        surql::repository::{self, bound_expression},
+        table::TableConfig,
+    },
     types::{FieldType, StructField},
 };
 use serde::Serialize;
-use serde_json::Value;
+use serde_json::{Map, Value};
+use surrealdb::{Connection, RecordId, Surreal};
 
 pub enum QueryType {
     Create,
@@ -43,7 +52,10 @@ fn is_nullable_partial_struct(field: &StructField, original_table: Option<&Table
 }
 
 /// Check if any field needs null-preserving conditional logic
-fn needs_null_preservation(field: &StructField, original_table: Option<&TableConfig>) -> bool {
+pub(crate) fn needs_null_preservation(
+    field: &StructField,
+    original_table: Option<&TableConfig>,
+) -> bool {
     // Direct nullable check
     if is_nullable_field(field) {
         return true;
@@ -57,8 +69,33 @@ fn needs_null_preservation(field: &StructField, original_table: Option<&TableCon
     false
 }
 
+/// Whether a field is stored on the record: edges only when they are defined as fields
+pub(crate) fn is_stored_field(field: &StructField) -> bool {
+    field.edge_config.is_none()
+        || field
+            .define_config
+            .as_ref()
+            .is_some_and(|define_config| !define_config.should_skip)
+}
+
+/// Whether mock data is generated for a field: stored fields that are not readonly
+pub(crate) fn is_generated_field(field: &StructField) -> bool {
+    let readonly = field
+        .define_config
+        .as_ref()
+        .and_then(|define_config| define_config.readonly)
+        .unwrap_or(false);
+    is_stored_field(field) && !readonly
+}
+
 /// Generate a CREATE or UPDATE query for SurrealDB using a given schema definition and object
 /// corresponding to the `table_schema` and the fields in `object`.
+///
+/// Values are interpolated into the statement text, so this is only suitable for trusted,
+/// generated data. Use [`generate_bound_query`] for anything that reaches a live database.
+#[deprecated(
+    note = "interpolates values into the statement; use `generate_bound_query` for runtime queries"
+)]
 pub fn generate_query<T: Serialize>(
     query_type: QueryType,
     table_config: &TableConfig,
@@ -102,6 +139,88 @@ pub fn generate_query<T: Serialize>(
     }
 }
 
+/// A SurrealQL statement with `$field_name` placeholders and the values bound to them
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundQuery {
+    pub statement: String,
+    pub bindings: Map<String, Value>,
+    /// The record the statement targets, bound as `$id`
+    pub record_id: Option<RecordId>,
+}
+
+impl BoundQuery {
+    /// Prepare the statement on `db` with every binding applied
+    pub fn prepare<'a, C: Connection>(
+        &self,
+        db: &'a Surreal<C>,
+    ) -> surrealdb::method::Query<'a, C> {
+        let query = db
+            .query(self.statement.clone())
+            .bind(Value::Object(self.bindings.clone()));
+        match &self.record_id {
+            Some(record_id) => query.bind(("id", record_id.clone())),
+            None => query,
+        }
+    }
+}
+
+/// Generate a CREATE or UPDATE query like [`generate_query`], but with every value passed
+/// as a bound parameter. Each field is bound under its own name (`$name`) and cast back to
+/// its SurrealDB type inside the statement; the record id is bound as `$id`, with integer
+/// keys kept as integers.
+pub fn generate_bound_query<T: Serialize>(
+    query_type: QueryType,
+    table_config: &TableConfig,
+    object: &T,
+    explicit_id: Option<String>,
+) -> Result<BoundQuery> {
+    let value = serde_json::to_value(object)?;
+    let table_name = &table_config.table_name;
+
+    let mut record_id = explicit_id;
+    let mut content_parts = Vec::new();
+    let mut bindings = Map::new();
+    for field in &table_config.struct_config.fields {
+        let Some(field_value) = value.get(&field.field_name) else {
+            continue;
+        };
+        if field.field_name == "id" {
+            if record_id.is_none() {
+                record_id = field_value.as_str().map(str::to_string);
+            }
+        } else if field.edge_config.is_none() {
+            content_parts.push(format!(
+                "{}: {}",
+                field.field_name,
+                bound_expression(&field.field_type, &format!("${}", field.field_name), 0)
+            ));
+            bindings.insert(field.field_name.clone(), field_value.clone());
+        }
+    }
+
+    // Ids may be given as `table:key` or bare, and SurrealDB may wrap keys in ⟨⟩
+    let record_id = record_id
+        .filter(|id| !id.is_empty())
+        .map(|id| repository::record_id(table_name, &id))
+        .transpose()?;
+    let target = match record_id {
+        Some(_) => "$id".to_string(),
+        None if matches!(query_type, QueryType::Create) => table_name.clone(),
+        None => return Err(EvenframeError::missing_field("id")),
+    };
+
+    let content_body = content_parts.join(", ");
+    let statement = match query_type {
+        QueryType::Create => format!("CREATE {} CONTENT {{ {} }};", target, content_body),
+        QueryType::Update => format!("UPDATE {} CONTENT {{ {} }};", target, content_body),
+    };
+    Ok(BoundQuery {
+        statement,
+        bindings,
+        record_id,
+    })
+}
+
 /// Convert a JSON value (already extracted from our struct) into the SurrealDB
 /// syntax, guided by a FieldType.  Strings get single quotes in SurrealDB,
 /// numeric/bool remain unquoted, arrays get bracketed, etc. This function
@@ -122,7 +241,9 @@ fn to_surreal_string(field_type: &FieldType, value: &Value) -> String {
             }
         }
 
-        FieldType::Other(_) => value.to_string(),
+        FieldType::Other(_) | FieldType::Generic(..) | FieldType::GenericParam(_) => {
+            value.to_string()
+        }
         // For numeric types, rely on the JSON representation to yield
         // an unquoted string (e.g. "123", "3.14")
         FieldType::Decimal => {
@@ -187,6 +308,41 @@ fn to_surreal_string(field_type: &FieldType, value: &Value) -> String {
                 "duration::from::nanos(0)".to_string()
             }
         }
+        FieldType::Uuid => match value.as_str() {
+            Some(s) => format!("u'{}'", escape_single_quotes(s)),
+            None => "NONE".to_string(),
+        },
+        FieldType::NaiveDate => match value.as_str() {
+            // SurrealDB has no plain date type; midnight UTC on that day is stored
+            Some(s) => format!("d'{}'", escape_single_quotes(s)),
+            None => "NONE".to_string(),
+        },
+        FieldType::NaiveTime | FieldType::IpAddr | FieldType::Url => match value.as_str() {
+            Some(s) => format!("'{}'", escape_single_quotes(s)),
+            None => "NONE".to_string(),
+        },
+        FieldType::Duration => {
+            // serde writes std::time::Duration as { secs, nanos }
+            let secs = value.get("secs").and_then(Value::as_u64).unwrap_or(0);
+            let nanos = value.get("nanos").and_then(Value::as_u64).unwrap_or(0);
+            format!("duration::from::nanos({})", secs * 1_000_000_000 + nanos)
+        }
+        FieldType::Bytes => {
+            let bytes = value
+                .as_array()
+                .map(|array| array.iter().filter_map(Value::as_u64).collect::<Vec<_>>())
+                .unwrap_or_default();
+            format!(
+                "<bytes>[{}]",
+                bytes
+                    .iter()
+                    .map(u64::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        }
+        // GeoJSON objects are recognised as geometries by SurrealDB
+        FieldType::JsonValue | FieldType::Geometry(_) => value.to_string(),
         FieldType::Timezone => {
             // Timezone values are stored as IANA timezone strings
             // SurrealDB stores them as regular strings
@@ -209,6 +365,18 @@ fn to_surreal_string(field_type: &FieldType, value: &Value) -> String {
                 "[]".to_string()
             }
         }
+        // Sets serialize as arrays; the cast lets SurrealDB deduplicate them
+        FieldType::HashSet(inner_type) | FieldType::BTreeSet(inner_type) => {
+            if let Some(array) = value.as_array() {
+                let items: Vec<String> = array
+                    .iter()
+                    .map(|item_value| to_surreal_string(inner_type, item_value))
+                    .collect();
+                format!("<set>[{}]", items.join(", "))
+            } else {
+                "<set>[]".to_string()
+            }
+        }
         // Option<T> -> either null or the T type, recursively
         FieldType::Option(inner_type) => {
             if value.is_null() {
@@ -326,3 +494,73 @@ fn to_surreal_string(field_type: &FieldType, value: &Value) -> String {
 fn escape_single_quotes(s: &str) -> String {
     s.replace('\'', "\\'")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::StructConfig;
+    use serde_json::json;
+    use surrealdb::engine::local::Mem;
+
+    fn user_table() -> TableConfig {
+        let field = |name: &str, field_type| StructField {
+            field_name: name.to_string(),
+            field_type,
+            ..Default::default()
+        };
+        TableConfig {
+            table_name: "user".to_string(),
+            struct_config: StructConfig::for_testing(
+                "User",
+                vec![
+                    field("id", FieldType::EvenframeRecordId),
+                    field("name", FieldType::String),
+                    field("joined_at", FieldType::DateTime),
+                ],
+            ),
+            relation: None,
+            permissions: None,
+            mock_generation_config: None,
+        }
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_generate_query() {
+        let user = json!({
+            "id": "user:1",
+            "name": "O'Brien",
+            "joined_at": "2024-01-01T00:00:00Z",
+        });
+        assert_eq!(
+            generate_query(QueryType::Update, &user_table(), &user, None),
+            "UPDATE user:1 CONTENT { name: 'O\\'Brien', joined_at: d'2024-01-01T00:00:00Z' };"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generate_bound_query() {
+        let table = user_table();
+        let user = json!({ "id": "1", "name": "O'Brien", "joined_at": "2024-01-01T00:00:00Z" });
+        let query = generate_bound_query(QueryType::Create, &table, &user, None).unwrap();
+        assert_eq!(
+            query.statement,
+            "CREATE $id CONTENT { name: $name, joined_at: <datetime> $joined_at };"
+        );
+        assert_eq!(query.record_id, Some(RecordId::from(("user", 1))));
+        assert_eq!(query.bindings["name"], "O'Brien");
+        assert!(
+            generate_bound_query(QueryType::Update, &table, &json!({ "name": "x" }), None).is_err()
+        );
+
+        let db = Surreal::new::<Mem>(()).await.unwrap();
+        db.use_ns("test").use_db("test").await.unwrap();
+        query.prepare(&db).await.unwrap().check().unwrap();
+        let mut response = db
+            .query("SELECT VALUE type::is::datetime(joined_at) FROM ONLY user:1")
+            .await
+            .unwrap();
+        let is_datetime: Option<bool> = response.take(0).unwrap();
+        assert_eq!(is_datetime, Some(true));
+    }
+}
@@ -0,0 +1,314 @@
+//! Type-safe SELECT queries built from field-path constants.
+//!
+//! The derive macro generates one [`Field`] constant per struct field (`User::EMAIL`),
+//! so field names are checked by the compiler. Queries compile to parameterised
+//! SurrealQL and decode into the struct, with `FETCH` resolving `RecordLink<T>` fields.
+
+use crate::{
+    error::Result,
+    schemasync::surql::repository::{
+        Filter, FilterOp, decode, field_path, table_config, take_value,
+    },
+    traits::EvenframePersistableStruct,
+};
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::{Map, Value};
+use std::marker::PhantomData;
+use surrealdb::{Connection, Surreal};
+use tracing::debug;
+
+/// A field of the struct `T` holding values of type `V`
+pub struct Field<T, V> {
+    name: &'static str,
+    _marker: PhantomData<fn() -> (T, V)>,
+}
+
+// Manual impls: deriving would require `T` and `V` to be `Clone`/`Copy`
+impl<T, V> Clone for Field<T, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, V> Copy for Field<T, V> {}
+
+impl<T, V> std::fmt::Debug for Field<T, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Field").field(&self.name).finish()
+    }
+}
+
+impl<T, V> Field<T, V> {
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn asc(&self) -> Ordering {
+        Ordering {
+            field: self.name,
+            descending: false,
+        }
+    }
+
+    pub fn desc(&self) -> Ordering {
+        Ordering {
+            field: self.name,
+            descending: true,
+        }
+    }
+}
+
+impl<T, V: Serialize> Field<T, V> {
+    fn compare(&self, op: FilterOp, value: impl Into<V>) -> Filter {
+        Filter::new().condition(self.name, op, value.into())
+    }
+
+    pub fn eq(&self, value: impl Into<V>) -> Filter {
+        self.compare(FilterOp::Eq, value)
+    }
+
+    pub fn ne(&self, value: impl Into<V>) -> Filter {
+        self.compare(FilterOp::Ne, value)
+    }
+
+    pub fn gt(&self, value: impl Into<V>) -> Filter {
+        self.compare(FilterOp::Gt, value)
+    }
+
+    pub fn gte(&self, value: impl Into<V>) -> Filter {
+        self.compare(FilterOp::Gte, value)
+    }
+
+    pub fn lt(&self, value: impl Into<V>) -> Filter {
+        self.compare(FilterOp::Lt, value)
+    }
+
+    pub fn lte(&self, value: impl Into<V>) -> Filter {
+        self.compare(FilterOp::Lte, value)
+    }
+
+    /// Matches collections containing `item`
+    pub fn contains(&self, item: impl Serialize) -> Filter {
+        Filter::new().condition(self.name, FilterOp::Contains, item)
+    }
+}
+
+/// An `ORDER BY` entry, created with [`Field::asc`] or [`Field::desc`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ordering {
+    field: &'static str,
+    descending: bool,
+}
+
+/// A SELECT over the table of `T`
+pub struct Query<T> {
+    filter: Filter,
+    order: Vec<Ordering>,
+    fetch: Vec<&'static str>,
+    limit: Option<u64>,
+    start: Option<u64>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for Query<T> {
+    fn default() -> Self {
+        Self {
+            filter: Filter::new(),
+            order: Vec::new(),
+            fetch: Vec::new(),
+            limit: None,
+            start: None,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Query<T>
+where
+    T: EvenframePersistableStruct,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add conditions, combined with any existing ones using `AND`
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.filter = self.filter.and(filter);
+        self
+    }
+
+    pub fn order_by(mut self, ordering: Ordering) -> Self {
+        self.order.push(ordering);
+        self
+    }
+
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn start(mut self, start: u64) -> Self {
+        self.start = Some(start);
+        self
+    }
+
+    /// Resolve a linked record in place of its id
+    pub fn fetch<V>(mut self, field: Field<T, V>) -> Self {
+        self.fetch.push(field.name);
+        self
+    }
+
+    /// The SurrealQL statement and the values bound to its parameters
+    pub fn build(&self) -> Result<(String, Value)> {
+        let table = table_config::<T>()?;
+        let mut bindings = Map::new();
+        bindings.insert("table".to_string(), table.table_name.clone().into());
+
+        let mut query = "SELECT * FROM type::table($table)".to_string();
+        if let Some(clause) = self.filter.where_clause(&table, &mut bindings)? {
+            query.push_str(&format!(" WHERE {}", clause));
+        }
+        if !self.order.is_empty() {
+            let mut orderings = Vec::new();
+            for ordering in &self.order {
+                let (path, _) = field_path(&table, ordering.field)?;
+                orderings.push(format!(
+                    "{} {}",
+                    path,
+                    if ordering.descending { "DESC" } else { "ASC" }
+                ));
+            }
+            query.push_str(&format!(" ORDER BY {}", orderings.join(", ")));
+        }
+        if let Some(limit) = self.limit {
+            query.push_str(" LIMIT $limit");
+            bindings.insert("limit".to_string(), limit.into());
+        }
+        if let Some(start) = self.start {
+            query.push_str(" START $start");
+            bindings.insert("start".to_string(), start.into());
+        }
+        if !self.fetch.is_empty() {
+            let paths = self
+                .fetch
+                .iter()
+                .map(|field| field_path(&table, field).map(|(path, _)| path))
+                .collect::<Result<Vec<_>>>()?;
+            query.push_str(&format!(" FETCH {}", paths.join(", ")));
+        }
+
+        Ok((query, Value::Object(bindings)))
+    }
+
+    /// Run the query and decode every matching record
+    pub async fn all<C: Connection>(&self, db: &Surreal<C>) -> Result<Vec<T>>
+    where
+        T: DeserializeOwned,
+    {
+        let (query, bindings) = self.build()?;
+        debug!(query = %query, "Running typed query");
+
+        let response = db.query(query).bind(bindings).await;
+        match take_value(response)? {
+            Value::Array(items) => items.into_iter().map(decode).collect(),
+            Value::Null => Ok(Vec::new()),
+            other => Ok(vec![decode(other)?]),
+        }
+    }
+
+    /// Run the query limited to one record
+    pub async fn first<C: Connection>(mut self, db: &Surreal<C>) -> Result<Option<T>>
+    where
+        T: DeserializeOwned,
+    {
+        self.limit = Some(1);
+        Ok(self.all(db).await?.into_iter().next())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        error::EvenframeError,
+        schemasync::TableConfig,
+        types::{FieldType, StructConfig, StructField},
+        wrappers::EvenframeRecordId,
+    };
+    use chrono::{DateTime, Utc};
+    use serde_json::json;
+
+    struct Post;
+
+    impl EvenframePersistableStruct for Post {
+        fn table_config() -> Option<TableConfig> {
+            let field = |name: &str, field_type| StructField {
+                field_type,
+                ..StructField::unit(name.to_string())
+            };
+            Some(TableConfig {
+                table_name: "post".to_string(),
+                struct_config: StructConfig::for_testing(
+                    "Post",
+                    vec![
+                        field("title", FieldType::String),
+                        field("published_at", FieldType::DateTime),
+                        field(
+                            "author",
+                            FieldType::RecordLink(Box::new(FieldType::Other("User".to_string()))),
+                        ),
+                    ],
+                ),
+                relation: None,
+                permissions: None,
+                mock_generation_config: None,
+            })
+        }
+    }
+
+    const TITLE: Field<Post, String> = Field::new("title");
+    const PUBLISHED_AT: Field<Post, DateTime<Utc>> = Field::new("published_at");
+    const AUTHOR: Field<Post, EvenframeRecordId> = Field::new("author");
+
+    #[test]
+    fn test_build() {
+        let (query, bindings) = Query::<Post>::new()
+            .filter(TITLE.eq("Hello"))
+            .order_by(PUBLISHED_AT.desc())
+            .order_by(TITLE.asc())
+            .limit(10)
+            .start(20)
+            .fetch(AUTHOR)
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            "SELECT * FROM type::table($table) WHERE `title` = $filter_0 ORDER BY `published_at` DESC, `title` ASC LIMIT $limit START $start FETCH `author`"
+        );
+        assert_eq!(
+            bindings,
+            json!({ "table": "post", "filter_0": "Hello", "limit": 10, "start": 20 })
+        );
+
+        let (query, _) = Query::<Post>::new().build().unwrap();
+        assert_eq!(query, "SELECT * FROM type::table($table)");
+
+        // Orderings and fetches are checked against the table's fields
+        let missing: Field<Post, String> = Field::new("missing");
+        assert!(matches!(
+            Query::<Post>::new().order_by(missing.asc()).build(),
+            Err(EvenframeError::FieldNotFound { .. })
+        ));
+        assert!(matches!(
+            Query::<Post>::new().fetch(missing).build(),
+            Err(EvenframeError::FieldNotFound { .. })
+        ));
+    }
+}
@@ -0,0 +1,206 @@
+//! Typed graph operations for relation tables and `#[edge]` fields.
+//!
+//! Relation tables declared with `#[relation(...)]` can be related, and their edges listed
+//! from either end. Edge fields are not stored on the record, so they are resolved by
+//! traversing the graph in the direction given by their `EdgeConfig`.
+
+use crate::{
+    error::{EvenframeError, Result},
+    schemasync::{
+        Direction, EdgeConfig,
+        surql::repository::{decode, prepare_write, record_id, table_config, take_one, take_value},
+        table::TableConfig,
+    },
+    traits::EvenframePersistableStruct,
+};
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use surrealdb::{Connection, Surreal};
+use tracing::debug;
+
+/// Create an edge `from -> T -> to` whose content is taken from `payload`
+pub async fn relate<T, C>(db: &Surreal<C>, from: String, to: String, payload: &T) -> Result<T>
+where
+    T: EvenframePersistableStruct + Serialize + DeserializeOwned,
+    C: Connection,
+{
+    let (table, relation) = relation_config::<T>()?;
+    let (_, content, bindings) = prepare_write(&table, payload)?;
+
+    let query = format!(
+        "RELATE $from->{}->$to CONTENT {}",
+        table.table_name, content
+    );
+    debug!(table = %table.table_name, from = %from, to = %to, "Relating records");
+
+    let response = db
+        .query(query)
+        .bind(bindings)
+        .bind(("from", record_id(&relation.from, &from)?))
+        .bind(("to", record_id(&relation.to, &to)?))
+        .await;
+    take_one(response)?.ok_or_else(|| {
+        EvenframeError::query_execution(format!(
+            "RELATE on '{}' returned no edge",
+            table.table_name
+        ))
+    })
+}
+
+/// Edges of `T` leaving the record `id`
+pub async fn outgoing<T, C>(db: &Surreal<C>, id: String) -> Result<Vec<T>>
+where
+    T: EvenframePersistableStruct + DeserializeOwned,
+    C: Connection,
+{
+    let (table, relation) = relation_config::<T>()?;
+    edges(db, &table, "in", record_id(&relation.from, &id)?).await
+}
+
+/// Edges of `T` arriving at the record `id`
+pub async fn incoming<T, C>(db: &Surreal<C>, id: String) -> Result<Vec<T>>
+where
+    T: EvenframePersistableStruct + DeserializeOwned,
+    C: Connection,
+{
+    let (table, relation) = relation_config::<T>()?;
+    edges(db, &table, "out", record_id(&relation.to, &id)?).await
+}
+
+/// Resolve an `#[edge]` field of the record `id` of `T` by walking the graph.
+///
+/// `Direction::From` follows edges leaving the record, `Direction::To` edges arriving at
+/// it, and `Direction::Both` returns the records on the other end of either.
+pub async fn traverse<T, V, C>(db: &Surreal<C>, id: String, edge: &EdgeConfig) -> Result<V>
+where
+    T: EvenframePersistableStruct,
+    V: DeserializeOwned,
+    C: Connection,
+{
+    let table = table_config::<T>()?;
+    let outgoing = format!("->{}->{}", edge.edge_name, edge.to);
+    let incoming = format!("<-{}<-{}", edge.edge_name, edge.from);
+    let path = match edge.direction {
+        Direction::From => outgoing,
+        Direction::To => incoming,
+        Direction::Both => format!("array::union({}, {})", outgoing, incoming),
+    };
+    let query = format!("SELECT VALUE {} FROM ONLY $id", path);
+    debug!(table = %table.table_name, id = %id, query = %query, "Traversing edge");
+
+    let response = db
+        .query(query)
+        .bind(("id", record_id(&table.table_name, &id)?))
+        .await;
+    let value = take_value(response)?;
+
+    // Single-valued fields (`Option<RecordLink<T>>`, `RecordLink<T>`) take the first node
+    match decode::<V>(value.clone()) {
+        Ok(decoded) => Ok(decoded),
+        Err(err) => match value {
+            Value::Array(mut items) if items.len() <= 1 => {
+                decode(items.pop().unwrap_or(Value::Null))
+            }
+            _ => Err(err),
+        },
+    }
+}
+
+fn relation_config<T: EvenframePersistableStruct>() -> Result<(TableConfig, EdgeConfig)> {
+    let table = table_config::<T>()?;
+    let relation = table.relation.clone().ok_or_else(|| {
+        EvenframeError::edge_definition(format!("'{}' is not a relation table", table.table_name))
+    })?;
+    Ok((table, relation))
+}
+
+async fn edges<T, C>(
+    db: &Surreal<C>,
+    table: &TableConfig,
+    side: &str,
+    id: surrealdb::RecordId,
+) -> Result<Vec<T>>
+where
+    T: DeserializeOwned,
+    C: Connection,
+{
+    let query = format!("SELECT * FROM type::table($table) WHERE {} = $id", side);
+    debug!(table = %table.table_name, side = %side, "Listing edges");
+
+    let response = db
+        .query(query)
+        .bind(("table", table.table_name.clone()))
+        .bind(("id", id))
+        .await;
+    match take_value(response)? {
+        Value::Array(items) => items.into_iter().map(decode).collect(),
+        Value::Null => Ok(Vec::new()),
+        other => Ok(vec![decode(other)?]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{types::StructConfig, wrappers::EvenframeRecordId};
+    use surrealdb::engine::local::Mem;
+
+    struct User;
+
+    impl EvenframePersistableStruct for User {
+        fn table_config() -> Option<TableConfig> {
+            Some(TableConfig {
+                table_name: "user".to_string(),
+                struct_config: StructConfig::for_testing("User", Vec::new()),
+                relation: None,
+                permissions: None,
+                mock_generation_config: None,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_traverse() {
+        let db = Surreal::new::<Mem>(()).await.unwrap();
+        db.use_ns("test").use_db("test").await.unwrap();
+        db.query(
+            "CREATE user:ada, user:bob, user:cy;
+            RELATE user:ada->follows->user:bob;
+            RELATE user:cy->follows->user:ada;",
+        )
+        .await
+        .unwrap()
+        .check()
+        .unwrap();
+        let follows = |direction| EdgeConfig {
+            edge_name: "follows".to_string(),
+            from: "user".to_string(),
+            to: "user".to_string(),
+            direction,
+        };
+
+        let neighbours = async |direction| -> Vec<String> {
+            let ids: Vec<EvenframeRecordId> =
+                traverse::<User, _, _>(&db, "ada".to_string(), &follows(direction))
+                    .await
+                    .unwrap();
+            let mut ids: Vec<String> = ids.iter().map(ToString::to_string).collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(neighbours(Direction::From).await, ["user:bob"]);
+        assert_eq!(neighbours(Direction::To).await, ["user:cy"]);
+        // Both directions are merged with `array::union`
+        assert_eq!(neighbours(Direction::Both).await, ["user:bob", "user:cy"]);
+
+        // Single-valued fields take the only node
+        let followed: Option<EvenframeRecordId> =
+            traverse::<User, _, _>(&db, "cy".to_string(), &follows(Direction::From))
+                .await
+                .unwrap();
+        assert_eq!(
+            followed.map(|id| id.to_string()).as_deref(),
+            Some("user:ada")
+        );
+    }
+}
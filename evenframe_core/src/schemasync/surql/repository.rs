@@ -0,0 +1,648 @@
+//! Typed CRUD operations for persistable structs.
+//!
+//! Every statement uses bound parameters: only table and field names checked against the
+//! struct's `TableConfig` are written into the query text, never user supplied values.
+//! Values are bound as JSON and cast back to their SurrealDB types inside the query,
+//! so record links, datetimes and durations are stored with their proper types.
+
+use crate::{
+    error::{EvenframeError, Result},
+    schemasync::{surql::query::Query, table::TableConfig},
+    traits::EvenframePersistableStruct,
+    types::FieldType,
+};
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::{Map, Value};
+use surrealdb::{Connection, RecordId, Surreal};
+use tracing::{debug, trace};
+
+/// Comparison used by a [`Filter`] condition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterOp {
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Contains,
+}
+
+impl FilterOp {
+    fn as_surql(&self) -> &'static str {
+        match self {
+            FilterOp::Eq => "=",
+            FilterOp::Ne => "!=",
+            FilterOp::Gt => ">",
+            FilterOp::Gte => ">=",
+            FilterOp::Lt => "<",
+            FilterOp::Lte => "<=",
+            FilterOp::Contains => "CONTAINS",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Condition {
+    field: String,
+    op: FilterOp,
+    /// The serialized value, or why it could not be serialized
+    value: std::result::Result<Value, String>,
+}
+
+/// Conditions for [`list`], combined with `AND`.
+///
+/// Field paths are checked against the table's fields when the query is built.
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    conditions: Vec<Condition>,
+}
+
+impl Filter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a condition. A value that fails to serialize is reported when the query is
+    /// built.
+    pub fn condition(
+        mut self,
+        field: impl Into<String>,
+        op: FilterOp,
+        value: impl Serialize,
+    ) -> Self {
+        self.conditions.push(Condition {
+            field: field.into(),
+            op,
+            value: serde_json::to_value(value).map_err(|e| e.to_string()),
+        });
+        self
+    }
+
+    pub fn eq(self, field: impl Into<String>, value: impl Serialize) -> Self {
+        self.condition(field, FilterOp::Eq, value)
+    }
+
+    pub fn ne(self, field: impl Into<String>, value: impl Serialize) -> Self {
+        self.condition(field, FilterOp::Ne, value)
+    }
+
+    pub fn gt(self, field: impl Into<String>, value: impl Serialize) -> Self {
+        self.condition(field, FilterOp::Gt, value)
+    }
+
+    pub fn gte(self, field: impl Into<String>, value: impl Serialize) -> Self {
+        self.condition(field, FilterOp::Gte, value)
+    }
+
+    pub fn lt(self, field: impl Into<String>, value: impl Serialize) -> Self {
+        self.condition(field, FilterOp::Lt, value)
+    }
+
+    pub fn lte(self, field: impl Into<String>, value: impl Serialize) -> Self {
+        self.condition(field, FilterOp::Lte, value)
+    }
+
+    pub fn contains(self, field: impl Into<String>, value: impl Serialize) -> Self {
+        self.condition(field, FilterOp::Contains, value)
+    }
+
+    /// Combine with the conditions of `other`
+    pub fn and(mut self, other: Filter) -> Self {
+        self.conditions.extend(other.conditions);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.conditions.is_empty()
+    }
+
+    /// Render the conditions as a `WHERE` body, adding their values to `bindings`
+    pub(crate) fn where_clause(
+        &self,
+        table: &TableConfig,
+        bindings: &mut Map<String, Value>,
+    ) -> Result<Option<String>> {
+        let mut clauses = Vec::new();
+        for condition in &self.conditions {
+            let (path, field_type) = field_path(table, &condition.field)?;
+            let value = condition.value.clone().map_err(|e| {
+                EvenframeError::serialization(format!("filter value of `{}`: {e}", condition.field))
+            })?;
+            let param = format!("filter_{}", bindings.len());
+            let expr = format!("${}", param);
+            clauses.push(format!(
+                "{} {} {}",
+                path,
+                condition.op.as_surql(),
+                match field_type {
+                    Some(field_type) => bound_expression(field_type, &expr, 0),
+                    None => expr,
+                }
+            ));
+            bindings.insert(param, value);
+        }
+        Ok((!clauses.is_empty()).then(|| clauses.join(" AND ")))
+    }
+}
+
+/// A window of results for [`list`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Page {
+    pub start: u64,
+    pub limit: u64,
+}
+
+impl Page {
+    pub fn new(start: u64, limit: u64) -> Self {
+        Self { start, limit }
+    }
+
+    /// The zero-based page `number` when pages hold `size` records
+    pub fn number(number: u64, size: u64) -> Self {
+        Self {
+            start: number * size,
+            limit: size,
+        }
+    }
+}
+
+/// Fetch a single record by id. Accepts either `table:key` or a bare key.
+pub async fn get<T, C>(db: &Surreal<C>, id: String) -> Result<Option<T>>
+where
+    T: EvenframePersistableStruct + DeserializeOwned,
+    C: Connection,
+{
+    let table = table_config::<T>()?;
+    let record_id = record_id(&table.table_name, &id)?;
+    debug!(table = %table.table_name, id = %id, "Fetching record");
+
+    let response = db
+        .query("SELECT * FROM ONLY $id")
+        .bind(("id", record_id))
+        .await;
+    take_one(response)
+}
+
+/// List records matching `filter`, optionally restricted to a `page`
+pub async fn list<T, C>(db: &Surreal<C>, filter: Filter, page: Option<Page>) -> Result<Vec<T>>
+where
+    T: EvenframePersistableStruct + DeserializeOwned,
+    C: Connection,
+{
+    let mut query = Query::<T>::new().filter(filter);
+    if let Some(page) = page {
+        query = query.start(page.start).limit(page.limit);
+    }
+    query.all(db).await
+}
+
+/// Validate and insert `record`. A missing or empty id lets SurrealDB generate one.
+pub async fn create<T, C>(db: &Surreal<C>, record: &T) -> Result<T>
+where
+    T: EvenframePersistableStruct + Serialize + DeserializeOwned,
+    C: Connection,
+{
+    let table = table_config::<T>()?;
+    let (id, content, bindings) = prepare_write(&table, record)?;
+
+    let (target, record_id) = match id {
+        Some(id) => ("$id", Some(record_id(&table.table_name, &id)?)),
+        None => ("type::table($table)", None),
+    };
+    let query = format!("CREATE ONLY {} CONTENT {}", target, content);
+    debug!(table = %table.table_name, query = %query, "Creating record");
+
+    let mut request = db
+        .query(query)
+        .bind(("table", table.table_name.clone()))
+        .bind(bindings);
+    if let Some(record_id) = record_id {
+        request = request.bind(("id", record_id));
+    }
+    take_one(request.await)?.ok_or_else(|| {
+        EvenframeError::query_execution(format!(
+            "CREATE on '{}' returned no record",
+            table.table_name
+        ))
+    })
+}
+
+/// Validate `record` and replace the stored record with the same id
+pub async fn update<T, C>(db: &Surreal<C>, record: &T) -> Result<T>
+where
+    T: EvenframePersistableStruct + Serialize + DeserializeOwned,
+    C: Connection,
+{
+    let table = table_config::<T>()?;
+    let (id, content, bindings) = prepare_write(&table, record)?;
+    let id = id.ok_or_else(|| EvenframeError::missing_field("id"))?;
+
+    let query = format!("UPDATE ONLY $id CONTENT {}", content);
+    debug!(table = %table.table_name, id = %id, query = %query, "Updating record");
+
+    let response = db
+        .query(query)
+        .bind(("id", record_id(&table.table_name, &id)?))
+        .bind(bindings)
+        .await;
+    take_one(response)?
+        .ok_or_else(|| EvenframeError::query_execution(format!("No record '{}' to update", id)))
+}
+
+/// Delete a record by id, returning it as it was before deletion
+pub async fn delete<T, C>(db: &Surreal<C>, id: String) -> Result<Option<T>>
+where
+    T: EvenframePersistableStruct + DeserializeOwned,
+    C: Connection,
+{
+    let table = table_config::<T>()?;
+    let record_id = record_id(&table.table_name, &id)?;
+    debug!(table = %table.table_name, id = %id, "Deleting record");
+
+    let response = db
+        .query("DELETE ONLY $id RETURN BEFORE")
+        .bind(("id", record_id))
+        .await;
+    take_one(response)
+}
+
+/// Run the struct's validators by passing it through its own `Deserialize` impl,
+/// which the derive macro generates with validation when validators are declared
+pub fn validate<T>(record: &T) -> Result<()>
+where
+    T: Serialize + DeserializeOwned,
+{
+    let value = serde_json::to_value(record)?;
+    serde_json::from_value::<T>(value)
+        .map(|_| ())
+        .map_err(|e| EvenframeError::validation(e.to_string()))
+}
+
+pub(crate) fn table_config<T: EvenframePersistableStruct>() -> Result<TableConfig> {
+    T::table_config().ok_or_else(|| {
+        EvenframeError::table_definition(format!(
+            "{} has no table configuration",
+            std::any::type_name::<T>()
+        ))
+    })
+}
+
+/// Resolve a field path such as `address.city` against the table's fields. Every segment
+/// has to be a plain identifier naming a field of the struct, or of the inline object
+/// the previous segment holds. Returns the path with each segment escaped, and the type
+/// of the last segment.
+///
+/// Objects of named types and linked records are not described by the `TableConfig`:
+/// segments below them only have to be identifiers, and their type is unknown (`None`).
+pub(crate) fn field_path<'a>(
+    table: &'a TableConfig,
+    path: &str,
+) -> Result<(String, Option<&'a FieldType>)> {
+    let not_found = || EvenframeError::FieldNotFound {
+        field: path.to_string(),
+        type_name: table.struct_config.struct_name.clone(),
+    };
+    let mut escaped = Vec::new();
+    // `None` once the path enters a value whose fields are unknown
+    let mut current = None;
+    for (index, segment) in path.split('.').enumerate() {
+        let is_identifier = segment
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !is_identifier {
+            return Err(EvenframeError::validation(format!(
+                "Invalid field path '{}' for {}",
+                path, table.struct_config.struct_name
+            )));
+        }
+        escaped.push(format!("`{}`", segment));
+
+        if index == 0 {
+            let field = table
+                .struct_config
+                .fields
+                .iter()
+                .find(|field| field.field_name == segment)
+                .ok_or_else(not_found)?;
+            current = Some(&field.field_type);
+            continue;
+        }
+        let Some(parent) = current else {
+            continue;
+        };
+        current = match container_item(parent) {
+            FieldType::Struct(fields) => fields
+                .iter()
+                .find(|(name, _)| name == segment)
+                .map(|(_, field_type)| Some(field_type))
+                .ok_or_else(not_found)?,
+            FieldType::Other(_)
+            | FieldType::RecordLink(_)
+            | FieldType::JsonValue
+            | FieldType::HashMap(_, _)
+            | FieldType::BTreeMap(_, _)
+            | FieldType::GenericParam(_)
+            | FieldType::Generic(_, _) => None,
+            _ => return Err(not_found()),
+        };
+    }
+    Ok((escaped.join("."), current))
+}
+
+/// The type a nested path continues into: the item of options and collections
+fn container_item(field_type: &FieldType) -> &FieldType {
+    match field_type {
+        FieldType::Option(inner)
+        | FieldType::Vec(inner)
+        | FieldType::HashSet(inner)
+        | FieldType::BTreeSet(inner) => container_item(inner),
+        other => other,
+    }
+}
+
+/// Build a `RecordId` for `table` from either `table:key` or a bare key. Integer keys
+/// stay integers, as SurrealDB treats `user:1` and `user:⟨1⟩` as different records.
+pub(crate) fn record_id(table: &str, id: &str) -> Result<RecordId> {
+    let key = match id.split_once(':') {
+        Some((prefix, key)) if prefix == table => key,
+        Some((prefix, _)) => {
+            return Err(EvenframeError::validation(format!(
+                "Record id '{}' belongs to table '{}', expected '{}'",
+                id, prefix, table
+            )));
+        }
+        None => id,
+    };
+    let escaped = key.starts_with('⟨') && key.ends_with('⟩');
+    let key = key.trim_start_matches('⟨').trim_end_matches('⟩');
+    if key.is_empty() {
+        return Err(EvenframeError::validation(format!(
+            "Record id for table '{}' is empty",
+            table
+        )));
+    }
+    match key.parse::<i64>() {
+        Ok(number) if !escaped => Ok(RecordId::from((table, number))),
+        _ => Ok(RecordId::from((table, key))),
+    }
+}
+
+/// Validate `record` and split it into its id, a `CONTENT` object expression, and
+/// the bindings that expression refers to. Edge fields are not stored on the record, and
+/// the `in`/`out` fields of relation tables are set by `RELATE` instead.
+pub(crate) fn prepare_write<T>(
+    table: &TableConfig,
+    record: &T,
+) -> Result<(Option<String>, String, Value)>
+where
+    T: Serialize + DeserializeOwned,
+{
+    validate(record)?;
+    let value = serde_json::to_value(record)?;
+
+    let mut id = None;
+    let mut parts = Vec::new();
+    let mut bindings = Map::new();
+    for field in &table.struct_config.fields {
+        let Some(field_value) = value.get(&field.field_name) else {
+            continue;
+        };
+        if field.field_name == "id" {
+            id = field_value
+                .as_str()
+                .filter(|id| !id.is_empty())
+                .map(str::to_string);
+        } else if field.edge_config.is_none()
+            && !(table.relation.is_some()
+                && (field.field_name == "in" || field.field_name == "out"))
+        {
+            let param = format!("field_{}", field.field_name);
+            parts.push(format!(
+                "{}: {}",
+                field.field_name,
+                bound_expression(&field.field_type, &format!("${}", param), 0)
+            ));
+            bindings.insert(param, field_value.clone());
+        }
+    }
+    trace!(id = ?id, fields = parts.len(), "Prepared record content");
+
+    Ok((
+        id,
+        format!("{{ {} }}", parts.join(", ")),
+        Value::Object(bindings),
+    ))
+}
+
+/// SurrealQL expression that turns the JSON bound at `expr` back into the value
+/// SurrealDB expects for `field_type`
+pub(crate) fn bound_expression(field_type: &FieldType, expr: &str, depth: usize) -> String {
+    match field_type {
+        FieldType::EvenframeRecordId => format!("<record> {}", expr),
+        // Links may be serialized as an id or as the whole linked object
+        FieldType::RecordLink(_) => format!(
+            "(IF type::is::object({expr}) THEN <record> {expr}.id ELSE <record> {expr} END)"
+        ),
+        FieldType::DateTime | FieldType::NaiveDate => format!("<datetime> {}", expr),
+        FieldType::Uuid => format!("<uuid> {}", expr),
+        FieldType::Decimal => format!("<decimal> {}", expr),
+        FieldType::EvenframeDuration => format!("duration::from::nanos({})", expr),
+        FieldType::Duration => {
+            format!("duration::from::nanos({expr}.secs * 1000000000 + {expr}.nanos)")
+        }
+        FieldType::Bytes => format!("<bytes> {}", expr),
+        FieldType::Option(inner) if needs_cast(inner) => format!(
+            "(IF {expr} = NONE OR {expr} = NULL THEN NONE ELSE {} END)",
+            bound_expression(inner, expr, depth)
+        ),
+        FieldType::Vec(inner) | FieldType::HashSet(inner) | FieldType::BTreeSet(inner)
+            if needs_cast(inner) =>
+        {
+            let item = format!("$item_{}", depth);
+            let mapped = format!(
+                "array::map({}, |{}| {})",
+                expr,
+                item,
+                bound_expression(inner, &item, depth + 1)
+            );
+            if matches!(field_type, FieldType::Vec(_)) {
+                mapped
+            } else {
+                format!("<set> {}", mapped)
+            }
+        }
+        FieldType::HashSet(_) | FieldType::BTreeSet(_) => format!("<set> {}", expr),
+        _ => expr.to_string(),
+    }
+}
+
+fn needs_cast(field_type: &FieldType) -> bool {
+    bound_expression(field_type, "$value", 0) != "$value"
+}
+
+pub(crate) fn take_value(response: surrealdb::Result<surrealdb::Response>) -> Result<Value> {
+    let mut response = response.map_err(|e| EvenframeError::database(e.to_string()))?;
+    let value: surrealdb::Value = response
+        .take(0)
+        .map_err(|e| EvenframeError::query_execution(e.to_string()))?;
+    Ok(serde_json::to_value(&value)?)
+}
+
+pub(crate) fn take_one<T: DeserializeOwned>(
+    response: surrealdb::Result<surrealdb::Response>,
+) -> Result<Option<T>> {
+    match take_value(response)? {
+        Value::Null => Ok(None),
+        Value::Array(mut items) => match items.len() {
+            0 => Ok(None),
+            _ => decode(items.swap_remove(0)).map(Some),
+        },
+        value => decode(value).map(Some),
+    }
+}
+
+/// Decode a record through `T`'s `Deserialize` impl, which runs its validators
+pub(crate) fn decode<T: DeserializeOwned>(value: Value) -> Result<T> {
+    serde_json::from_value(value).map_err(|e| EvenframeError::deserialization(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{StructConfig, StructField};
+    use std::collections::HashMap;
+    use surrealdb::engine::local::Mem;
+
+    #[test]
+    fn test_unserializable_filter_value_is_an_error() {
+        let table = TableConfig {
+            table_name: "user".to_string(),
+            struct_config: StructConfig::for_testing(
+                "User",
+                vec![StructField {
+                    field_type: FieldType::String,
+                    ..StructField::unit("name".to_string())
+                }],
+            ),
+            relation: None,
+            permissions: None,
+            mock_generation_config: None,
+        };
+        let mut bindings = Map::new();
+
+        let clause = Filter::new()
+            .eq("name", "Ada")
+            .where_clause(&table, &mut bindings)
+            .unwrap();
+        assert_eq!(clause.as_deref(), Some("`name` = $filter_0"));
+        assert_eq!(bindings["filter_0"], Value::String("Ada".to_string()));
+
+        // Maps with non-string keys have no JSON representation
+        let value = HashMap::from([((1, 2), "a")]);
+        let error = Filter::new()
+            .eq("name", value)
+            .where_clause(&table, &mut Map::new())
+            .unwrap_err();
+        assert!(matches!(error, EvenframeError::Serialization(_)));
+    }
+
+    #[test]
+    fn test_field_paths() {
+        let table = TableConfig {
+            table_name: "user".to_string(),
+            struct_config: StructConfig::for_testing(
+                "User",
+                vec![
+                    StructField {
+                        field_type: FieldType::String,
+                        ..StructField::unit("name".to_string())
+                    },
+                    StructField {
+                        field_type: FieldType::Option(Box::new(FieldType::Struct(vec![
+                            ("city".to_string(), FieldType::String),
+                            ("since".to_string(), FieldType::DateTime),
+                        ]))),
+                        ..StructField::unit("address".to_string())
+                    },
+                    StructField {
+                        field_type: FieldType::Other("Profile".to_string()),
+                        ..StructField::unit("profile".to_string())
+                    },
+                ],
+            ),
+            relation: None,
+            permissions: None,
+            mock_generation_config: None,
+        };
+
+        // Nested values are cast with the type of the last segment
+        let (path, field_type) = field_path(&table, "address.since").unwrap();
+        assert_eq!(path, "`address`.`since`");
+        assert_eq!(field_type, Some(&FieldType::DateTime));
+        let clause = Filter::new()
+            .gt("address.since", "2024-01-01T00:00:00Z")
+            .where_clause(&table, &mut Map::new())
+            .unwrap();
+        assert_eq!(
+            clause.as_deref(),
+            Some("`address`.`since` > <datetime> $filter_0")
+        );
+
+        // Fields of named object types are not known, only escaped
+        let (path, field_type) = field_path(&table, "profile.bio").unwrap();
+        assert_eq!(path, "`profile`.`bio`");
+        assert_eq!(field_type, None);
+
+        for path in [
+            "name.x OR true OR name",
+            "name`",
+            "",
+            "address.",
+            "address.city OR true",
+        ] {
+            assert!(
+                matches!(field_path(&table, path), Err(EvenframeError::Validation(_))),
+                "{path}"
+            );
+        }
+        for path in ["email", "name.first", "address.zip"] {
+            assert!(
+                matches!(
+                    field_path(&table, path),
+                    Err(EvenframeError::FieldNotFound { .. })
+                ),
+                "{path}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_numeric_record_id_round_trip() {
+        let db = Surreal::new::<Mem>(()).await.unwrap();
+        db.use_ns("test").use_db("test").await.unwrap();
+        db.query("CREATE user:1 SET name = 'numeric'; CREATE user:⟨2⟩ SET name = 'string';")
+            .await
+            .unwrap();
+
+        let name = async |id: &str| -> Option<String> {
+            let mut response = db
+                .query("SELECT VALUE name FROM ONLY $id")
+                .bind(("id", record_id("user", id).unwrap()))
+                .await
+                .unwrap();
+            response.take(0).unwrap()
+        };
+        assert_eq!(name("1").await.as_deref(), Some("numeric"));
+        assert_eq!(name("user:1").await.as_deref(), Some("numeric"));
+        assert_eq!(name("user:⟨2⟩").await.as_deref(), Some("string"));
+        assert_eq!(name("2").await, None);
+
+        assert_eq!(record_id("user", "user:1").unwrap().to_string(), "user:1");
+        assert!(record_id("user", "post:1").is_err());
+    }
+}
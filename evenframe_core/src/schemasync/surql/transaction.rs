@@ -0,0 +1,262 @@
+//! Atomic migrations and backup restores.
+//!
+//! A migration is sent as one `BEGIN TRANSACTION ... COMMIT TRANSACTION` batch, so a
+//! failing statement cancels all of them. When changes were applied outside of a
+//! transaction, the database can be put back from the export taken before the
+//! migration started.
+
+use crate::error::{EvenframeError, Result};
+use serde_json::Value;
+use std::collections::HashSet;
+use surrealdb::{Connection, Surreal};
+use tracing::{debug, error, info, trace};
+
+/// Wrap statement batches in a single transaction
+pub fn wrap_in_transaction(statements: &[String]) -> String {
+    let mut query = String::from("BEGIN TRANSACTION;\n");
+    for statement in statements {
+        let statement = statement.trim();
+        if statement.is_empty() {
+            continue;
+        }
+        query.push_str(statement);
+        if !statement.ends_with(';') {
+            query.push(';');
+        }
+        query.push('\n');
+    }
+    query.push_str("COMMIT TRANSACTION;\n");
+    query
+}
+
+/// Execute statement batches atomically. On error nothing has been applied.
+pub async fn execute_in_transaction<C: Connection>(
+    db: &Surreal<C>,
+    statements: &[String],
+) -> Result<()> {
+    let query = wrap_in_transaction(statements);
+    info!(
+        batch_count = statements.len(),
+        query_length = query.len(),
+        "Executing migration transaction"
+    );
+    crate::evenframe_log!(&query, "migration.surql");
+
+    let mut response = db
+        .query(query)
+        .await
+        .map_err(|e| EvenframeError::database(format!("Migration transaction failed: {e}")))?;
+
+    let mut errors: Vec<(usize, String)> = response
+        .take_errors()
+        .into_iter()
+        .map(|(index, e)| (index, e.to_string()))
+        .collect();
+    if errors.is_empty() {
+        debug!("Migration transaction committed");
+        return Ok(());
+    }
+    errors.sort_by_key(|(index, _)| *index);
+
+    // Every statement of a cancelled transaction reports an error, the cause is the one
+    // that is not about the transaction itself
+    let (index, cause) = errors
+        .iter()
+        .find(|(_, message)| !message.contains("failed transaction"))
+        .unwrap_or(&errors[0]);
+    error!(
+        statement_index = index,
+        error = %cause,
+        "Migration transaction cancelled"
+    );
+    Err(EvenframeError::database(format!(
+        "Migration transaction was cancelled at statement {index}: {cause}"
+    )))
+}
+
+/// Put the database back to the state captured in `backup` (an export of it, as
+/// written by [`write_backup`](crate::schemasync::backup::write_backup)). Only the
+/// tables whose records the backup holds are replaced: before importing, they are
+/// removed along with the accesses the backup recreates, and the `managed_tables` and
+/// `managed_accesses` that were created after it was taken. Tables the backup defines
+/// without their records keep their current definitions and records, other tables and
+/// accesses are left alone.
+pub async fn restore_from_backup<C: Connection>(
+    db: &Surreal<C>,
+    backup: &str,
+    managed_tables: &[String],
+    managed_accesses: &[String],
+) -> Result<()> {
+    info!(
+        backup_length = backup.len(),
+        "Restoring database from backup"
+    );
+
+    let (tables, accesses) = database_definitions(db).await?;
+    let backup_tables = backup_definitions(backup, "DEFINE TABLE ");
+    let record_tables = backup_record_tables(backup);
+    let backup_accesses = backup_definitions(backup, "DEFINE ACCESS ");
+
+    let mut query = String::new();
+    let mut kept_tables = HashSet::new();
+    for table in &tables {
+        if record_tables.contains(table)
+            || (managed_tables.contains(table) && !backup_tables.contains(table))
+        {
+            query.push_str(&format!("REMOVE TABLE IF EXISTS `{}`;\n", table));
+        } else if backup_tables.contains(table) {
+            kept_tables.insert(table.clone());
+        }
+    }
+    for access in accesses
+        .iter()
+        .filter(|access| backup_accesses.contains(*access) || managed_accesses.contains(*access))
+    {
+        query.push_str(&format!(
+            "REMOVE ACCESS IF EXISTS `{}` ON DATABASE;\n",
+            access
+        ));
+    }
+    trace!(remove_statements = %query, "Clearing database before restore");
+    debug!(kept_tables = ?kept_tables, "Keeping tables without records in the backup");
+    for line in backup.lines() {
+        if definition_table(line).is_some_and(|table| kept_tables.contains(&table)) {
+            continue;
+        }
+        query.push_str(line);
+        query.push('\n');
+    }
+
+    let mut response = db
+        .query(query)
+        .await
+        .map_err(|e| EvenframeError::database(format!("Failed to restore backup: {e}")))?;
+    let errors = response.take_errors();
+    if let Some(e) = errors.values().next() {
+        error!(error_count = errors.len(), error = %e, "Backup restore failed");
+        return Err(EvenframeError::database(format!(
+            "Failed to restore backup ({} statement errors): {e}",
+            errors.len()
+        )));
+    }
+
+    info!("Database restored from backup");
+    Ok(())
+}
+
+/// The names of the tables and of the accesses defined on the database
+pub async fn database_definitions<C: Connection>(
+    db: &Surreal<C>,
+) -> Result<(Vec<String>, Vec<String>)> {
+    let mut response = db
+        .query("INFO FOR DB")
+        .await
+        .map_err(|e| EvenframeError::database(format!("Failed to read database info: {e}")))?;
+    let info: surrealdb::Value = response
+        .take(0)
+        .map_err(|e| EvenframeError::database(format!("Failed to read database info: {e}")))?;
+    let info = serde_json::to_value(&info)?;
+
+    let names = |key: &str| -> Vec<String> {
+        info.get(key)
+            .and_then(Value::as_object)
+            .map(|definitions| definitions.keys().cloned().collect())
+            .unwrap_or_default()
+    };
+    Ok((names("tables"), names("accesses")))
+}
+
+/// Names of the definitions starting with `statement` (like `DEFINE TABLE `) in an export
+fn backup_definitions(backup: &str, statement: &str) -> HashSet<String> {
+    backup
+        .lines()
+        .filter_map(|line| definition_name(line, statement))
+        .collect()
+}
+
+/// Tables whose records an export holds, including tables that had none
+fn backup_record_tables(backup: &str) -> HashSet<String> {
+    backup
+        .lines()
+        .filter_map(|line| line.trim_start().strip_prefix("-- TABLE DATA: "))
+        .map(unescape_name)
+        .collect()
+}
+
+/// The name defined by a line starting with `statement`
+fn definition_name(line: &str, statement: &str) -> Option<String> {
+    let rest = line.trim_start().strip_prefix(statement)?.trim_start();
+    let rest = rest
+        .strip_prefix("OVERWRITE ")
+        .or_else(|| rest.strip_prefix("IF NOT EXISTS "))
+        .unwrap_or(rest);
+    rest.split_whitespace().next().map(unescape_name)
+}
+
+/// The table a `DEFINE TABLE`, `FIELD`, `INDEX` or `EVENT` line of an export belongs to
+fn definition_table(line: &str) -> Option<String> {
+    if let Some(table) = definition_name(line, "DEFINE TABLE ") {
+        return Some(table);
+    }
+    let line = line.trim_start();
+    ["DEFINE FIELD ", "DEFINE INDEX ", "DEFINE EVENT "]
+        .iter()
+        .find(|statement| line.starts_with(*statement))?;
+    let rest = line.split_once(" ON ")?.1.trim_start();
+    let rest = rest.strip_prefix("TABLE ").unwrap_or(rest);
+    rest.split_whitespace().next().map(unescape_name)
+}
+
+fn unescape_name(name: &str) -> String {
+    name.trim()
+        .trim_end_matches(';')
+        .trim_matches(|c| matches!(c, '`' | '⟨' | '⟩'))
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_definitions() {
+        let backup = "DEFINE TABLE user TYPE NORMAL SCHEMAFULL PERMISSIONS NONE;\n\
+            DEFINE FIELD name ON user TYPE string PERMISSIONS FULL;\n\
+            DEFINE TABLE OVERWRITE `audit-log` TYPE ANY SCHEMALESS PERMISSIONS NONE;\n\
+            DEFINE ACCESS user ON DATABASE TYPE RECORD DURATION FOR TOKEN 1h;\n";
+        assert_eq!(
+            backup_definitions(backup, "DEFINE TABLE "),
+            HashSet::from(["user".to_string(), "audit-log".to_string()])
+        );
+        assert_eq!(
+            backup_definitions(backup, "DEFINE ACCESS "),
+            HashSet::from(["user".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_backup_record_tables() {
+        let backup = "DEFINE TABLE user TYPE NORMAL SCHEMAFULL PERMISSIONS NONE;\n\
+            DEFINE TABLE session TYPE NORMAL SCHEMALESS PERMISSIONS NONE;\n\
+            DEFINE FIELD OVERWRITE name ON TABLE user TYPE string PERMISSIONS FULL;\n\
+            DEFINE INDEX token ON session FIELDS token;\n\
+            -- TABLE DATA: user\n\
+            INSERT [ { id: user:1, name: 'Ada' } ];\n";
+        assert_eq!(
+            backup_record_tables(backup),
+            HashSet::from(["user".to_string()])
+        );
+        let tables: Vec<_> = backup.lines().map(definition_table).collect();
+        assert_eq!(
+            tables,
+            vec![
+                Some("user".to_string()),
+                Some("session".to_string()),
+                Some("user".to_string()),
+                Some("session".to_string()),
+                None,
+                None,
+            ]
+        );
+    }
+}
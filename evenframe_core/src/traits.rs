@@ -1,4 +1,18 @@
-use crate::schemasync::TableConfig;
+use crate::{
+    error::Result,
+    schemasync::{
+        TableConfig,
+        surql::{
+            live::{self, LiveStream},
+            query::Query,
+            relation,
+            repository::{self, Filter, Page},
+        },
+    },
+};
+use serde::{Serialize, de::DeserializeOwned};
+use std::future::Future;
+use surrealdb::{Connection, Surreal};
 
 /// Trait for persistable structs (with ID field, representing database tables)
 pub trait EvenframePersistableStruct {
@@ -8,6 +22,113 @@ pub trait EvenframePersistableStruct {
     }
 }
 
+/// Typed CRUD operations for persistable structs, implemented by the derive macro.
+///
+/// All statements use bound parameters and decode results through the struct's
+/// `Deserialize` impl, so the same validators as `EvenframeDeserialize` apply to
+/// records read from and written to the database.
+pub trait EvenframeRepository: EvenframePersistableStruct + Sized {
+    /// Start a typed SELECT, filtered with the generated field constants
+    fn query() -> Query<Self> {
+        Query::new()
+    }
+
+    fn get<C: Connection>(
+        db: &Surreal<C>,
+        id: impl ToString,
+    ) -> impl Future<Output = Result<Option<Self>>> + Send
+    where
+        Self: DeserializeOwned + Send,
+    {
+        repository::get::<Self, C>(db, id.to_string())
+    }
+
+    fn list<C: Connection>(
+        db: &Surreal<C>,
+        filter: Filter,
+        page: Option<Page>,
+    ) -> impl Future<Output = Result<Vec<Self>>> + Send
+    where
+        Self: DeserializeOwned + Send,
+    {
+        repository::list::<Self, C>(db, filter, page)
+    }
+
+    /// Subscribe to created, updated and deleted records matching `filter`
+    fn live<C: Connection>(
+        db: &Surreal<C>,
+        filter: Filter,
+    ) -> impl Future<Output = Result<LiveStream<Self>>> + Send
+    where
+        Self: DeserializeOwned + Send + 'static,
+    {
+        live::live::<Self, C>(db, filter)
+    }
+
+    fn create<C: Connection>(&self, db: &Surreal<C>) -> impl Future<Output = Result<Self>> + Send
+    where
+        Self: Serialize + DeserializeOwned + Send + Sync,
+    {
+        repository::create::<Self, C>(db, self)
+    }
+
+    fn update<C: Connection>(&self, db: &Surreal<C>) -> impl Future<Output = Result<Self>> + Send
+    where
+        Self: Serialize + DeserializeOwned + Send + Sync,
+    {
+        repository::update::<Self, C>(db, self)
+    }
+
+    fn delete<C: Connection>(
+        db: &Surreal<C>,
+        id: impl ToString,
+    ) -> impl Future<Output = Result<Option<Self>>> + Send
+    where
+        Self: DeserializeOwned + Send,
+    {
+        repository::delete::<Self, C>(db, id.to_string())
+    }
+}
+
+/// Typed graph operations for relation tables, implemented by the derive macro for
+/// structs annotated with `#[relation(...)]`
+pub trait EvenframeRelation: EvenframePersistableStruct + Sized {
+    /// Create an edge from `from` to `to` with `payload` as its content
+    fn relate<C: Connection>(
+        db: &Surreal<C>,
+        from: impl ToString,
+        to: impl ToString,
+        payload: &Self,
+    ) -> impl Future<Output = Result<Self>> + Send
+    where
+        Self: Serialize + DeserializeOwned + Send + Sync,
+    {
+        relation::relate::<Self, C>(db, from.to_string(), to.to_string(), payload)
+    }
+
+    /// Edges leaving the record `id`
+    fn outgoing<C: Connection>(
+        db: &Surreal<C>,
+        id: impl ToString,
+    ) -> impl Future<Output = Result<Vec<Self>>> + Send
+    where
+        Self: DeserializeOwned + Send,
+    {
+        relation::outgoing::<Self, C>(db, id.to_string())
+    }
+
+    /// Edges arriving at the record `id`
+    fn incoming<C: Connection>(
+        db: &Surreal<C>,
+        id: impl ToString,
+    ) -> impl Future<Output = Result<Vec<Self>>> + Send
+    where
+        Self: DeserializeOwned + Send,
+    {
+        relation::incoming::<Self, C>(db, id.to_string())
+    }
+}
+
 use serde::Deserializer;
 
 pub trait EvenframeDeserialize<'de>: Sized {
@@ -1,6 +1,7 @@
 use core::fmt;
 use quote::{ToTokens, quote};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use syn::Type as SynType;
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -29,6 +30,19 @@ pub enum FieldType {
     EvenframeDuration,
     Timezone,
     Decimal,
+    Uuid,
+    NaiveDate,
+    NaiveTime,
+    /// `std::time::Duration`, serialized by serde as `{ secs, nanos }`
+    Duration,
+    /// `Vec<u8>` and other byte buffers
+    Bytes,
+    /// `serde_json::Value`, arbitrary JSON
+    JsonValue,
+    IpAddr,
+    Url,
+    /// GeoJSON geometry backed by the wrappers in `wrappers.rs`
+    Geometry(GeometryKind),
     OrderedFloat(Box<FieldType>), // Wraps F32 or F64
     Tuple(Vec<FieldType>),
     Struct(Vec<(String, FieldType)>),
@@ -36,10 +50,87 @@ pub enum FieldType {
     Vec(Box<FieldType>),
     HashMap(Box<FieldType>, Box<FieldType>),
     BTreeMap(Box<FieldType>, Box<FieldType>),
+    HashSet(Box<FieldType>),
+    BTreeSet(Box<FieldType>),
     RecordLink(Box<FieldType>),
+    /// A type parameter of the enclosing generic definition, e.g. `T` in `Paginated<T>`
+    GenericParam(String),
+    /// A concrete instantiation of a generic struct or enum, e.g. `Paginated<User>`
+    Generic(String, Vec<FieldType>),
     Other(String),
 }
 
+/// The shape of a SurrealDB `geometry<...>` field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GeometryKind {
+    Point,
+    LineString,
+    Polygon,
+    MultiPoint,
+    MultiLineString,
+    MultiPolygon,
+    /// Any geometry (`EvenframeGeometry`)
+    Any,
+}
+
+impl GeometryKind {
+    /// Resolve the geometry wrapper type name, e.g. `EvenframePoint`
+    pub fn from_wrapper_name(name: &str) -> Option<Self> {
+        match name {
+            "EvenframePoint" => Some(GeometryKind::Point),
+            "EvenframeLineString" => Some(GeometryKind::LineString),
+            "EvenframePolygon" => Some(GeometryKind::Polygon),
+            "EvenframeMultiPoint" => Some(GeometryKind::MultiPoint),
+            "EvenframeMultiLineString" => Some(GeometryKind::MultiLineString),
+            "EvenframeMultiPolygon" => Some(GeometryKind::MultiPolygon),
+            "EvenframeGeometry" => Some(GeometryKind::Any),
+            _ => None,
+        }
+    }
+
+    /// The SurrealDB geometry subtype used in `geometry<...>`
+    pub fn surreal_name(&self) -> &'static str {
+        match self {
+            GeometryKind::Point => "point",
+            GeometryKind::LineString => "line",
+            GeometryKind::Polygon => "polygon",
+            GeometryKind::MultiPoint => "multipoint",
+            GeometryKind::MultiLineString => "multiline",
+            GeometryKind::MultiPolygon => "multipolygon",
+            GeometryKind::Any => "feature",
+        }
+    }
+
+    /// The GeoJSON `type` tag, or `None` when any geometry is accepted
+    pub fn geojson_type(&self) -> Option<&'static str> {
+        match self {
+            GeometryKind::Point => Some("Point"),
+            GeometryKind::LineString => Some("LineString"),
+            GeometryKind::Polygon => Some("Polygon"),
+            GeometryKind::MultiPoint => Some("MultiPoint"),
+            GeometryKind::MultiLineString => Some("MultiLineString"),
+            GeometryKind::MultiPolygon => Some("MultiPolygon"),
+            GeometryKind::Any => None,
+        }
+    }
+}
+
+impl ToTokens for GeometryKind {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        match self {
+            GeometryKind::Point => tokens.extend(quote! { GeometryKind::Point }),
+            GeometryKind::LineString => tokens.extend(quote! { GeometryKind::LineString }),
+            GeometryKind::Polygon => tokens.extend(quote! { GeometryKind::Polygon }),
+            GeometryKind::MultiPoint => tokens.extend(quote! { GeometryKind::MultiPoint }),
+            GeometryKind::MultiLineString => {
+                tokens.extend(quote! { GeometryKind::MultiLineString })
+            }
+            GeometryKind::MultiPolygon => tokens.extend(quote! { GeometryKind::MultiPolygon }),
+            GeometryKind::Any => tokens.extend(quote! { GeometryKind::Any }),
+        }
+    }
+}
+
 impl ToTokens for FieldType {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         match self {
@@ -65,6 +156,15 @@ impl ToTokens for FieldType {
             FieldType::EvenframeDuration => tokens.extend(quote! { FieldType::EvenframeDuration }),
             FieldType::Timezone => tokens.extend(quote! { FieldType::Timezone }),
             FieldType::Decimal => tokens.extend(quote! { FieldType::Decimal }),
+            FieldType::Uuid => tokens.extend(quote! { FieldType::Uuid }),
+            FieldType::NaiveDate => tokens.extend(quote! { FieldType::NaiveDate }),
+            FieldType::NaiveTime => tokens.extend(quote! { FieldType::NaiveTime }),
+            FieldType::Duration => tokens.extend(quote! { FieldType::Duration }),
+            FieldType::Bytes => tokens.extend(quote! { FieldType::Bytes }),
+            FieldType::JsonValue => tokens.extend(quote! { FieldType::JsonValue }),
+            FieldType::IpAddr => tokens.extend(quote! { FieldType::IpAddr }),
+            FieldType::Url => tokens.extend(quote! { FieldType::Url }),
+            FieldType::Geometry(kind) => tokens.extend(quote! { FieldType::Geometry(#kind) }),
             FieldType::Unit => tokens.extend(quote! { FieldType::Unit }),
             FieldType::OrderedFloat(inner) => {
                 tokens.extend(quote! {
@@ -104,8 +204,22 @@ impl ToTokens for FieldType {
             FieldType::HashMap(Box::new(#key),Box::new(#value) ) }),
             FieldType::BTreeMap(key, value) => tokens.extend(quote! {
             FieldType::BTreeMap(Box::new(#key),Box::new(#value) ) }),
+            FieldType::HashSet(inner) => tokens.extend(quote! {
+            FieldType::HashSet(Box::new(#inner)) }),
+            FieldType::BTreeSet(inner) => tokens.extend(quote! {
+            FieldType::BTreeSet(Box::new(#inner)) }),
             FieldType::RecordLink(inner) => tokens.extend(quote! {
             FieldType::RecordLink(Box::new(#inner)) }),
+            FieldType::GenericParam(name) => {
+                let lit = syn::LitStr::new(name, proc_macro2::Span::call_site());
+                tokens.extend(quote! { FieldType::GenericParam(#lit.to_string()) });
+            }
+            FieldType::Generic(name, args) => {
+                let lit = syn::LitStr::new(name, proc_macro2::Span::call_site());
+                tokens.extend(quote! {
+                    FieldType::Generic(#lit.to_string(), vec![#(#args),*])
+                });
+            }
         }
     }
 }
@@ -236,7 +350,21 @@ impl FieldType {
                     return FieldType::Option(Box::new(Self::parse_syn_ty(type_args[0])));
                 }
                 "Vec" if type_args.len() == 1 => {
-                    return FieldType::Vec(Box::new(Self::parse_syn_ty(type_args[0])));
+                    return match Self::parse_syn_ty(type_args[0]) {
+                        // Vec<u8> is a byte buffer
+                        FieldType::U8 => FieldType::Bytes,
+                        inner => FieldType::Vec(Box::new(inner)),
+                    };
+                }
+                "HashSet" if type_args.len() == 1 => {
+                    return FieldType::HashSet(Box::new(Self::parse_syn_ty(type_args[0])));
+                }
+                "BTreeSet" if type_args.len() == 1 => {
+                    return FieldType::BTreeSet(Box::new(Self::parse_syn_ty(type_args[0])));
+                }
+                "Box" | "Arc" | "Rc" if type_args.len() == 1 => {
+                    // Smart pointers are transparent
+                    return Self::parse_syn_ty(type_args[0]);
                 }
                 "HashMap" if type_args.len() == 2 => {
                     return FieldType::HashMap(
@@ -261,6 +389,13 @@ impl FieldType {
                     // DateTime<Utc>, DateTime<Local>, etc. all become DateTime
                     return FieldType::DateTime;
                 }
+                _ if !type_args.is_empty() => {
+                    // Instantiation of a user-defined generic type, e.g. Paginated<User>
+                    return FieldType::Generic(
+                        ident.clone(),
+                        type_args.into_iter().map(Self::parse_syn_ty).collect(),
+                    );
+                }
                 _ => {
                     // Unknown generic type, fall through to check if it's a known non-generic
                 }
@@ -291,7 +426,19 @@ impl FieldType {
             "EvenframeDuration" => FieldType::EvenframeDuration,
             "Tz" | "Timezone" => FieldType::Timezone,
             "Decimal" => FieldType::Decimal,
-            _ => {
+            "Uuid" => FieldType::Uuid,
+            "NaiveDate" => FieldType::NaiveDate,
+            "NaiveTime" => FieldType::NaiveTime,
+            // chrono::Duration is a TimeDelta with a different representation
+            "Duration" if !Self::path_has_segment(tp, "chrono") => FieldType::Duration,
+            "Bytes" | "ByteBuf" => FieldType::Bytes,
+            "Value" if Self::path_has_segment(tp, "serde_json") => FieldType::JsonValue,
+            "IpAddr" | "Ipv4Addr" | "Ipv6Addr" => FieldType::IpAddr,
+            "Url" => FieldType::Url,
+            name => {
+                if let Some(kind) = GeometryKind::from_wrapper_name(name) {
+                    return FieldType::Geometry(kind);
+                }
                 // Unknown type - store as Other
                 let type_str = tp.to_token_stream().to_string();
                 tracing::trace!("Unknown type '{}', storing as Other", type_str);
@@ -300,6 +447,10 @@ impl FieldType {
         }
     }
 
+    fn path_has_segment(tp: &syn::TypePath, name: &str) -> bool {
+        tp.path.segments.iter().any(|segment| segment.ident == name)
+    }
+
     pub fn parse_type_str(type_str: &str) -> FieldType {
         // Remove whitespace for consistent parsing
         let clean_str = type_str
@@ -331,8 +482,19 @@ impl FieldType {
             "EvenframeDuration" => FieldType::EvenframeDuration,
             "Tz" | "Timezone" => FieldType::Timezone,
             "Decimal" => FieldType::Decimal,
+            "Uuid" | "uuid::Uuid" => FieldType::Uuid,
+            "NaiveDate" | "chrono::NaiveDate" => FieldType::NaiveDate,
+            "NaiveTime" | "chrono::NaiveTime" => FieldType::NaiveTime,
+            "Duration" | "std::time::Duration" => FieldType::Duration,
+            "Bytes" | "bytes::Bytes" => FieldType::Bytes,
+            "serde_json::Value" => FieldType::JsonValue,
+            "IpAddr" | "std::net::IpAddr" | "Ipv4Addr" | "Ipv6Addr" => FieldType::IpAddr,
+            "Url" | "url::Url" => FieldType::Url,
             "()" => FieldType::Unit,
-            _ => {
+            name => {
+                if let Some(kind) = GeometryKind::from_wrapper_name(name) {
+                    return FieldType::Geometry(kind);
+                }
                 // Check for generic types like Option<T> or Vec<T>
                 if let Some(start) = clean_str.find('<') {
                     if let Some(end) = clean_str.rfind('>') {
@@ -344,13 +506,24 @@ impl FieldType {
                                 let inner_type = Self::parse_type_str(inner);
                                 FieldType::Option(Box::new(inner_type))
                             }
-                            "Vec" => {
-                                let inner_type = Self::parse_type_str(inner);
-                                FieldType::Vec(Box::new(inner_type))
+                            "Vec" => match Self::parse_type_str(inner) {
+                                FieldType::U8 => FieldType::Bytes,
+                                inner_type => FieldType::Vec(Box::new(inner_type)),
+                            },
+                            "HashSet" => FieldType::HashSet(Box::new(Self::parse_type_str(inner))),
+                            "BTreeSet" => {
+                                FieldType::BTreeSet(Box::new(Self::parse_type_str(inner)))
                             }
+                            "Box" | "Arc" | "Rc" => Self::parse_type_str(inner),
                             "DateTime" => FieldType::DateTime,
                             "EvenframeDuration" => FieldType::EvenframeDuration,
-                            _ => FieldType::Other(clean_str),
+                            _ => FieldType::Generic(
+                                outer.to_string(),
+                                Self::split_type_args(inner)
+                                    .iter()
+                                    .map(|arg| Self::parse_type_str(arg))
+                                    .collect(),
+                            ),
                         }
                     } else {
                         // Malformed generic type (missing closing '>')
@@ -361,35 +534,10 @@ impl FieldType {
                     // Strip the outer parentheses
                     let inner = &clean_str[1..clean_str.len() - 1];
 
-                    // Split by commas, but handle nested generics carefully
-                    let mut elements = Vec::new();
-                    let mut current = String::new();
-                    let mut depth = 0;
-
-                    for c in inner.chars() {
-                        match c {
-                            '<' => {
-                                depth += 1;
-                                current.push(c);
-                            }
-                            '>' => {
-                                depth -= 1;
-                                current.push(c);
-                            }
-                            ',' if depth == 0 => {
-                                if !current.is_empty() {
-                                    elements.push(Self::parse_type_str(&current));
-                                    current.clear();
-                                }
-                            }
-                            _ => current.push(c),
-                        }
-                    }
-
-                    // Don't forget the last element
-                    if !current.is_empty() {
-                        elements.push(Self::parse_type_str(&current));
-                    }
+                    let elements = Self::split_type_args(inner)
+                        .iter()
+                        .map(|element| Self::parse_type_str(element))
+                        .collect();
 
                     FieldType::Tuple(elements)
                 } else {
@@ -401,6 +549,146 @@ impl FieldType {
     }
 }
 
+impl FieldType {
+    /// Split a comma separated list of types at the top level, leaving commas
+    /// nested inside generic arguments or tuples untouched.
+    fn split_type_args(inner: &str) -> Vec<String> {
+        let mut elements = Vec::new();
+        let mut current = String::new();
+        let mut depth = 0;
+
+        for c in inner.chars() {
+            match c {
+                '<' | '(' => {
+                    depth += 1;
+                    current.push(c);
+                }
+                '>' | ')' => {
+                    depth -= 1;
+                    current.push(c);
+                }
+                ',' if depth == 0 => {
+                    if !current.is_empty() {
+                        elements.push(std::mem::take(&mut current));
+                    }
+                }
+                _ => current.push(c),
+            }
+        }
+
+        // Don't forget the last element
+        if !current.is_empty() {
+            elements.push(current);
+        }
+
+        elements
+    }
+
+    /// Inclusive value range of integer types that fit in a JavaScript `number`
+    pub fn integer_bounds(&self) -> Option<(i64, i64)> {
+        match self {
+            FieldType::I8 => Some((i8::MIN.into(), i8::MAX.into())),
+            FieldType::I16 => Some((i16::MIN.into(), i16::MAX.into())),
+            FieldType::I32 => Some((i32::MIN.into(), i32::MAX.into())),
+            FieldType::U8 => Some((0, u8::MAX.into())),
+            FieldType::U16 => Some((0, u16::MAX.into())),
+            FieldType::U32 => Some((0, u32::MAX.into())),
+            _ => None,
+        }
+    }
+
+    /// Integer types that can exceed `Number.MAX_SAFE_INTEGER` (2^53 - 1)
+    pub fn is_wide_integer(&self) -> bool {
+        matches!(
+            self,
+            FieldType::I64
+                | FieldType::I128
+                | FieldType::Isize
+                | FieldType::U64
+                | FieldType::U128
+                | FieldType::Usize
+        )
+    }
+
+    /// Turn references to the given type parameters (parsed as `Other("T")`)
+    /// into `GenericParam("T")`.
+    pub fn resolve_generic_params(self, params: &[String]) -> FieldType {
+        if params.is_empty() {
+            return self;
+        }
+        let resolve = |ft: FieldType| ft.resolve_generic_params(params);
+        match self {
+            FieldType::Other(name) if params.contains(&name) => FieldType::GenericParam(name),
+            FieldType::OrderedFloat(inner) => FieldType::OrderedFloat(Box::new(resolve(*inner))),
+            FieldType::Option(inner) => FieldType::Option(Box::new(resolve(*inner))),
+            FieldType::Vec(inner) => FieldType::Vec(Box::new(resolve(*inner))),
+            FieldType::HashSet(inner) => FieldType::HashSet(Box::new(resolve(*inner))),
+            FieldType::BTreeSet(inner) => FieldType::BTreeSet(Box::new(resolve(*inner))),
+            FieldType::RecordLink(inner) => FieldType::RecordLink(Box::new(resolve(*inner))),
+            FieldType::HashMap(key, value) => {
+                FieldType::HashMap(Box::new(resolve(*key)), Box::new(resolve(*value)))
+            }
+            FieldType::BTreeMap(key, value) => {
+                FieldType::BTreeMap(Box::new(resolve(*key)), Box::new(resolve(*value)))
+            }
+            FieldType::Tuple(types) => FieldType::Tuple(types.into_iter().map(resolve).collect()),
+            FieldType::Struct(fields) => FieldType::Struct(
+                fields
+                    .into_iter()
+                    .map(|(name, ft)| (name, resolve(ft)))
+                    .collect(),
+            ),
+            FieldType::Generic(name, args) => {
+                FieldType::Generic(name, args.into_iter().map(resolve).collect())
+            }
+            other => other,
+        }
+    }
+
+    /// Replace every `GenericParam` with its bound type, producing the
+    /// monomorphised field type of a generic instantiation.
+    pub fn substitute_generics(&self, bindings: &HashMap<String, FieldType>) -> FieldType {
+        let substitute = |ft: &FieldType| Box::new(ft.substitute_generics(bindings));
+        match self {
+            FieldType::GenericParam(name) => bindings.get(name).cloned().unwrap_or_else(|| {
+                tracing::warn!("Unbound generic parameter '{}'", name);
+                self.clone()
+            }),
+            FieldType::OrderedFloat(inner) => FieldType::OrderedFloat(substitute(inner)),
+            FieldType::Option(inner) => FieldType::Option(substitute(inner)),
+            FieldType::Vec(inner) => FieldType::Vec(substitute(inner)),
+            FieldType::HashSet(inner) => FieldType::HashSet(substitute(inner)),
+            FieldType::BTreeSet(inner) => FieldType::BTreeSet(substitute(inner)),
+            FieldType::RecordLink(inner) => FieldType::RecordLink(substitute(inner)),
+            FieldType::HashMap(key, value) => {
+                FieldType::HashMap(substitute(key), substitute(value))
+            }
+            FieldType::BTreeMap(key, value) => {
+                FieldType::BTreeMap(substitute(key), substitute(value))
+            }
+            FieldType::Tuple(types) => FieldType::Tuple(
+                types
+                    .iter()
+                    .map(|t| t.substitute_generics(bindings))
+                    .collect(),
+            ),
+            FieldType::Struct(fields) => FieldType::Struct(
+                fields
+                    .iter()
+                    .map(|(name, ft)| (name.clone(), ft.substitute_generics(bindings)))
+                    .collect(),
+            ),
+            FieldType::Generic(name, args) => FieldType::Generic(
+                name.clone(),
+                args.iter()
+                    .map(|a| a.substitute_generics(bindings))
+                    .collect(),
+            ),
+            other => other.clone(),
+        }
+    }
+}
+
 impl fmt::Display for FieldType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -427,6 +715,15 @@ impl fmt::Display for FieldType {
             FieldType::EvenframeDuration => write!(f, "EvenframeDuration"),
             FieldType::Timezone => write!(f, "Timezone"),
             FieldType::Decimal => write!(f, "Decimal"),
+            FieldType::Uuid => write!(f, "Uuid"),
+            FieldType::NaiveDate => write!(f, "NaiveDate"),
+            FieldType::NaiveTime => write!(f, "NaiveTime"),
+            FieldType::Duration => write!(f, "Duration"),
+            FieldType::Bytes => write!(f, "Bytes"),
+            FieldType::JsonValue => write!(f, "JsonValue"),
+            FieldType::IpAddr => write!(f, "IpAddr"),
+            FieldType::Url => write!(f, "Url"),
+            FieldType::Geometry(kind) => write!(f, "Geometry({})", kind.surreal_name()),
             FieldType::OrderedFloat(inner) => write!(f, "OrderedFloat<{}>", inner),
             FieldType::Tuple(types) => {
                 write!(f, "Tuple(")?;
@@ -456,8 +753,74 @@ impl fmt::Display for FieldType {
             FieldType::Vec(inner) => write!(f, "Vec({})", inner),
             FieldType::HashMap(key, value) => write!(f, "HashMap({}, {})", key, value),
             FieldType::BTreeMap(key, value) => write!(f, "BTreeMap({}, {})", key, value),
+            FieldType::HashSet(inner) => write!(f, "HashSet({})", inner),
+            FieldType::BTreeSet(inner) => write!(f, "BTreeSet({})", inner),
             FieldType::RecordLink(inner) => write!(f, "RecordLink({})", inner),
+            FieldType::GenericParam(name) => write!(f, "{}", name),
+            FieldType::Generic(name, args) => {
+                write!(f, "{}<", name)?;
+                let mut first = true;
+                for arg in args {
+                    if !first {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", arg)?;
+                    first = false;
+                }
+                write!(f, ">")
+            }
             FieldType::Other(name) => write!(f, "{}", name),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_substitute_generics() {
+        let param = |name: &str| FieldType::GenericParam(name.to_string());
+        let user = FieldType::Other("User".to_string());
+        let bindings = HashMap::from([
+            ("T".to_string(), user.clone()),
+            ("E".to_string(), FieldType::String),
+        ]);
+
+        assert_eq!(param("T").substitute_generics(&bindings), user);
+        assert_eq!(
+            FieldType::Option(Box::new(FieldType::Vec(Box::new(param("T")))))
+                .substitute_generics(&bindings),
+            FieldType::Option(Box::new(FieldType::Vec(Box::new(user.clone()))))
+        );
+        assert_eq!(
+            FieldType::HashMap(Box::new(param("E")), Box::new(param("T")))
+                .substitute_generics(&bindings),
+            FieldType::HashMap(Box::new(FieldType::String), Box::new(user.clone()))
+        );
+        assert_eq!(
+            FieldType::Struct(vec![(
+                "pair".to_string(),
+                FieldType::Tuple(vec![param("T"), FieldType::U32])
+            )])
+            .substitute_generics(&bindings),
+            FieldType::Struct(vec![(
+                "pair".to_string(),
+                FieldType::Tuple(vec![user.clone(), FieldType::U32])
+            )])
+        );
+        // Arguments of nested instances are substituted, unbound parameters are kept
+        assert_eq!(
+            FieldType::Generic(
+                "Paginated".to_string(),
+                vec![FieldType::Generic("Audited".to_string(), vec![param("T")])]
+            )
+            .substitute_generics(&bindings),
+            FieldType::Generic(
+                "Paginated".to_string(),
+                vec![FieldType::Generic("Audited".to_string(), vec![user])]
+            )
+        );
+        assert_eq!(param("U").substitute_generics(&bindings), param("U"));
+    }
+}
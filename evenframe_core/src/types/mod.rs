@@ -1,6 +1,6 @@
 mod field_type;
 
-pub use crate::types::field_type::FieldType;
+pub use crate::types::field_type::{FieldType, GeometryKind};
 use crate::{
     EvenframeError, Result, evenframe_log,
     format::Format,
@@ -17,6 +17,9 @@ use std::collections::{HashMap, HashSet};
 pub struct TaggedUnion {
     pub enum_name: String,
     pub variants: Vec<Variant>,
+    /// Names of the enum's type parameters, empty for non-generic enums
+    #[serde(default)]
+    pub generics: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
@@ -109,8 +112,8 @@ impl StructField {
     }
     pub fn generate_define_statement(
         &self,
-        enums: HashMap<String, TaggedUnion>,
-        app_structs: HashMap<String, StructConfig>,
+        mut enums: HashMap<String, TaggedUnion>,
+        mut app_structs: HashMap<String, StructConfig>,
         persistable_structs: HashMap<String, TableConfig>,
         table_name: &String,
     ) -> Result<String> {
@@ -124,6 +127,10 @@ impl StructField {
             "define_generation.log"
         );
 
+        // Concrete instantiations such as `Paginated<User>` are resolved like any other
+        // named type, so register a monomorphised copy of each before converting
+        register_generic_instances(&self.field_type, &mut app_structs, &mut enums);
+
         /* --- Start of Iterative Type Conversion Logic --- */
 
         #[derive(Debug)]
@@ -132,6 +139,7 @@ impl StructField {
             PushString(String),
             AssembleOption,
             AssembleVec,
+            AssembleSet,
             AssembleMap,
             AssembleTuple { count: usize },
             AssembleStruct { count: usize, names: Vec<String> },
@@ -165,6 +173,27 @@ impl StructField {
                             FieldType::Decimal => {
                                 value_stack.push(("decimal".to_string(), false, None))
                             }
+                            FieldType::Uuid => value_stack.push(("uuid".to_string(), false, None)),
+                            FieldType::NaiveDate => {
+                                value_stack.push(("datetime".to_string(), false, None))
+                            }
+                            FieldType::Duration => {
+                                value_stack.push(("duration".to_string(), false, None))
+                            }
+                            FieldType::Bytes => {
+                                value_stack.push(("bytes".to_string(), false, None))
+                            }
+                            FieldType::JsonValue => {
+                                value_stack.push(("any".to_string(), false, None))
+                            }
+                            FieldType::NaiveTime | FieldType::IpAddr | FieldType::Url => {
+                                value_stack.push(("string".to_string(), false, None))
+                            }
+                            FieldType::Geometry(kind) => value_stack.push((
+                                format!("geometry<{}>", kind.surreal_name()),
+                                false,
+                                None,
+                            )),
                             FieldType::F32 | FieldType::F64 | FieldType::OrderedFloat(_) => {
                                 value_stack.push(("float".to_string(), false, None))
                             }
@@ -199,6 +228,10 @@ impl StructField {
                                 work_stack.push(WorkItem::AssembleVec);
                                 work_stack.push(WorkItem::Process(inner));
                             }
+                            FieldType::HashSet(inner) | FieldType::BTreeSet(inner) => {
+                                work_stack.push(WorkItem::AssembleSet);
+                                work_stack.push(WorkItem::Process(inner));
+                            }
                             FieldType::HashMap(_, value) | FieldType::BTreeMap(_, value) => {
                                 work_stack.push(WorkItem::AssembleMap);
                                 work_stack.push(WorkItem::Process(value));
@@ -228,7 +261,11 @@ impl StructField {
                                     work_stack.push(WorkItem::Process(ftype));
                                 }
                             }
-                            FieldType::Other(name) => {
+                            FieldType::GenericParam(_) => {
+                                value_stack.push(("any".to_string(), false, None))
+                            }
+                            FieldType::Other(_) | FieldType::Generic(_, _) => {
+                                let name = &field_type.to_string();
                                 if let Some(enum_def) = enums.get(name) {
                                     let total_variants = enum_def.variants.len();
                                     work_stack.push(WorkItem::AssembleEnum {
@@ -280,17 +317,38 @@ impl StructField {
                                     }
                                     work_stack
                                         .push(WorkItem::LeaveStructScope { name: name.clone() });
-                                    let names = app_struct
-                                        .fields
-                                        .iter()
-                                        .map(|f| f.field_name.clone())
-                                        .collect();
-                                    work_stack.push(WorkItem::AssembleStruct {
-                                        count: app_struct.fields.len(),
-                                        names,
-                                    });
-                                    for field in app_struct.fields.iter().rev() {
-                                        work_stack.push(WorkItem::Process(&field.field_type));
+                                    match app_struct.kind {
+                                        // Newtypes are transparent in the database
+                                        StructKind::Newtype => {
+                                            for field in &app_struct.fields {
+                                                work_stack
+                                                    .push(WorkItem::Process(&field.field_type));
+                                            }
+                                        }
+                                        StructKind::Tuple => {
+                                            work_stack.push(WorkItem::AssembleTuple {
+                                                count: app_struct.fields.len(),
+                                            });
+                                            for field in app_struct.fields.iter().rev() {
+                                                work_stack
+                                                    .push(WorkItem::Process(&field.field_type));
+                                            }
+                                        }
+                                        StructKind::Named => {
+                                            let names = app_struct
+                                                .fields
+                                                .iter()
+                                                .map(|f| f.field_name.clone())
+                                                .collect();
+                                            work_stack.push(WorkItem::AssembleStruct {
+                                                count: app_struct.fields.len(),
+                                                names,
+                                            });
+                                            for field in app_struct.fields.iter().rev() {
+                                                work_stack
+                                                    .push(WorkItem::Process(&field.field_type));
+                                            }
+                                        }
                                     }
                                     work_stack
                                         .push(WorkItem::EnterStructScope { name: name.clone() });
@@ -338,6 +396,18 @@ impl StructField {
                             })?;
                             value_stack.push((format!("array<{}>", inner_type), false, None));
                         }
+                        WorkItem::AssembleSet => {
+                            let (inner_type, _, _) = value_stack.pop().ok_or_else(|| {
+                                EvenframeError::FieldDefinition {
+                                    message: "Stack underflow in AssembleSet".to_string(),
+                                    work_stack: format!("{:#?}", work_stack),
+                                    value_stack: format!("{:#?}", value_stack),
+                                    item: "AssembleSet".to_string(),
+                                    visited_types: format!("{:#?}", visited_types),
+                                }
+                            })?;
+                            value_stack.push((format!("set<{}>", inner_type), false, None));
+                        }
                         WorkItem::AssembleMap => {
                             let (value_type, _, _) = value_stack.pop().ok_or_else(|| {
                                 EvenframeError::FieldDefinition {
@@ -520,9 +590,354 @@ impl StructField {
     }
 }
 
+/// How a struct declares its fields
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StructKind {
+    /// `struct User { name: String }`
+    #[default]
+    Named,
+    /// `struct Email(String)`, transparent over its single field named `0`
+    Newtype,
+    /// `struct Point(f64, f64)`, fields are named by position (`0`, `1`, ...)
+    Tuple,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct StructConfig {
     pub struct_name: String,
     pub fields: Vec<StructField>,
     pub validators: Vec<Validator>,
+    /// Names of the struct's type parameters, empty for non-generic structs
+    #[serde(default)]
+    pub generics: Vec<String>,
+    #[serde(default)]
+    pub kind: StructKind,
+}
+
+impl StructConfig {
+    /// The wrapped field of a newtype struct, `None` for named and tuple structs
+    pub fn newtype_field(&self) -> Option<&StructField> {
+        match self.kind {
+            StructKind::Newtype => self.fields.first(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+impl StructConfig {
+    /// A named, non-generic struct without struct-level validators
+    pub fn for_testing(struct_name: &str, fields: Vec<StructField>) -> Self {
+        Self {
+            struct_name: struct_name.to_string(),
+            fields,
+            validators: Vec::new(),
+            generics: Vec::new(),
+            kind: StructKind::Named,
+        }
+    }
+}
+
+/// Bind each type parameter name to the matching concrete argument
+fn generic_bindings(params: &[String], args: &[FieldType]) -> HashMap<String, FieldType> {
+    if params.len() != args.len() {
+        tracing::warn!(
+            "Generic arity mismatch: expected {} type arguments, found {}",
+            params.len(),
+            args.len()
+        );
+    }
+    params.iter().cloned().zip(args.iter().cloned()).collect()
+}
+
+fn instance_name(name: &str, args: &[FieldType]) -> String {
+    FieldType::Generic(name.to_string(), args.to_vec()).to_string()
+}
+
+impl StructConfig {
+    /// Produce the concrete struct for a generic instantiation such as `Paginated<User>`.
+    /// The returned config is named after the instantiation and has no type parameters.
+    pub fn monomorphise(&self, args: &[FieldType]) -> StructConfig {
+        let bindings = generic_bindings(&self.generics, args);
+        StructConfig {
+            struct_name: instance_name(&self.struct_name, args),
+            fields: self
+                .fields
+                .iter()
+                .map(|field| StructField {
+                    field_type: field.field_type.substitute_generics(&bindings),
+                    ..field.clone()
+                })
+                .collect(),
+            validators: self.validators.clone(),
+            generics: Vec::new(),
+            kind: self.kind,
+        }
+    }
+}
+
+impl TaggedUnion {
+    /// Produce the concrete enum for a generic instantiation such as `ApiResult<User>`.
+    /// Inline struct variants are renamed after the instantiation as well.
+    pub fn monomorphise(&self, args: &[FieldType]) -> TaggedUnion {
+        let bindings = generic_bindings(&self.generics, args);
+        TaggedUnion {
+            enum_name: instance_name(&self.enum_name, args),
+            variants: self
+                .variants
+                .iter()
+                .map(|variant| Variant {
+                    name: variant.name.clone(),
+                    data: variant.data.as_ref().map(|data| match data {
+                        VariantData::InlineStruct(s) => {
+                            let inline = StructConfig {
+                                generics: self.generics.clone(),
+                                ..s.clone()
+                            }
+                            .monomorphise(args);
+                            VariantData::InlineStruct(inline)
+                        }
+                        VariantData::DataStructureRef(ft) => {
+                            VariantData::DataStructureRef(ft.substitute_generics(&bindings))
+                        }
+                    }),
+                })
+                .collect(),
+            generics: Vec::new(),
+        }
+    }
+}
+
+/// Walk `field_type` and register a monomorphised copy of every generic struct or enum
+/// it instantiates, keyed by the instantiation's display name (e.g. `Paginated<User>`).
+/// Types reachable through named structs and enums are visited as well.
+pub fn register_generic_instances(
+    field_type: &FieldType,
+    structs: &mut HashMap<String, StructConfig>,
+    enums: &mut HashMap<String, TaggedUnion>,
+) {
+    let mut stack = vec![field_type.clone()];
+    let mut seen = HashSet::new();
+
+    while let Some(ft) = stack.pop() {
+        match ft {
+            FieldType::Option(inner)
+            | FieldType::Vec(inner)
+            | FieldType::HashSet(inner)
+            | FieldType::BTreeSet(inner)
+            | FieldType::OrderedFloat(inner)
+            | FieldType::RecordLink(inner) => stack.push(*inner),
+            FieldType::HashMap(key, value) | FieldType::BTreeMap(key, value) => {
+                stack.push(*key);
+                stack.push(*value);
+            }
+            FieldType::Tuple(types) => stack.extend(types),
+            FieldType::Struct(fields) => stack.extend(fields.into_iter().map(|(_, ft)| ft)),
+            FieldType::Generic(ref name, ref args) => {
+                let key = ft.to_string();
+                if !seen.insert(key.clone()) {
+                    continue;
+                }
+                stack.extend(args.iter().cloned());
+                if let Some(generic_struct) = structs.get(name) {
+                    let instance = generic_struct.monomorphise(args);
+                    stack.extend(instance.fields.iter().map(|f| f.field_type.clone()));
+                    structs.insert(key, instance);
+                } else if let Some(generic_enum) = enums.get(name) {
+                    let instance = generic_enum.monomorphise(args);
+                    for variant in &instance.variants {
+                        match &variant.data {
+                            Some(VariantData::InlineStruct(s)) => {
+                                stack.extend(s.fields.iter().map(|f| f.field_type.clone()));
+                                structs.insert(s.struct_name.clone(), s.clone());
+                            }
+                            Some(VariantData::DataStructureRef(ft)) => stack.push(ft.clone()),
+                            None => {}
+                        }
+                    }
+                    enums.insert(key, instance);
+                }
+            }
+            FieldType::Other(name) => {
+                if !seen.insert(name.clone()) {
+                    continue;
+                }
+                if let Some(s) = structs.get(&name) {
+                    stack.extend(s.fields.iter().map(|f| f.field_type.clone()));
+                } else if let Some(e) = enums.get(&name) {
+                    for variant in &e.variants {
+                        match &variant.data {
+                            Some(VariantData::InlineStruct(s)) => {
+                                stack.extend(s.fields.iter().map(|f| f.field_type.clone()))
+                            }
+                            Some(VariantData::DataStructureRef(ft)) => stack.push(ft.clone()),
+                            None => {}
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generic_struct(
+        name: &str,
+        generics: &[&str],
+        fields: Vec<(&str, FieldType)>,
+    ) -> StructConfig {
+        let fields = fields
+            .into_iter()
+            .map(|(field_name, field_type)| StructField {
+                field_type,
+                ..StructField::unit(field_name.to_string())
+            })
+            .collect();
+        StructConfig {
+            generics: generics.iter().map(|name| name.to_string()).collect(),
+            ..StructConfig::for_testing(name, fields)
+        }
+    }
+
+    fn param() -> FieldType {
+        FieldType::GenericParam("T".to_string())
+    }
+
+    fn user() -> FieldType {
+        FieldType::Other("User".to_string())
+    }
+
+    fn generic(name: &str, args: Vec<FieldType>) -> FieldType {
+        FieldType::Generic(name.to_string(), args)
+    }
+
+    fn api_result() -> TaggedUnion {
+        TaggedUnion {
+            enum_name: "ApiResult".to_string(),
+            variants: vec![
+                Variant {
+                    name: "Success".to_string(),
+                    data: Some(VariantData::DataStructureRef(param())),
+                },
+                Variant {
+                    name: "Failure".to_string(),
+                    data: Some(VariantData::InlineStruct(generic_struct(
+                        "ApiResultFailure",
+                        &[],
+                        vec![
+                            ("reason", FieldType::String),
+                            ("partial", FieldType::Option(Box::new(param()))),
+                        ],
+                    ))),
+                },
+                Variant {
+                    name: "Pending".to_string(),
+                    data: None,
+                },
+            ],
+            generics: vec!["T".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_monomorphise_struct() {
+        let paginated = generic_struct(
+            "Paginated",
+            &["T"],
+            vec![
+                ("items", FieldType::Vec(Box::new(param()))),
+                ("total", FieldType::U64),
+            ],
+        );
+        let instance = paginated.monomorphise(&[user()]);
+        assert_eq!(instance.struct_name, "Paginated<User>");
+        assert!(instance.generics.is_empty());
+        assert_eq!(
+            instance.fields[0].field_type,
+            FieldType::Vec(Box::new(user()))
+        );
+        assert_eq!(instance.fields[1].field_type, FieldType::U64);
+    }
+
+    #[test]
+    fn test_monomorphise_enum() {
+        let instance = api_result().monomorphise(&[user()]);
+        assert_eq!(instance.enum_name, "ApiResult<User>");
+        assert!(instance.generics.is_empty());
+        assert_eq!(
+            instance.variants[0].data,
+            Some(VariantData::DataStructureRef(user()))
+        );
+        let Some(VariantData::InlineStruct(failure)) = &instance.variants[1].data else {
+            panic!("Failure should stay an inline struct");
+        };
+        assert_eq!(failure.struct_name, "ApiResultFailure<User>");
+        assert!(failure.generics.is_empty());
+        assert_eq!(
+            failure.fields[1].field_type,
+            FieldType::Option(Box::new(user()))
+        );
+        assert_eq!(instance.variants[2].data, None);
+    }
+
+    #[test]
+    fn test_register_generic_instances() {
+        let mut structs = HashMap::from([
+            (
+                "Paginated".to_string(),
+                generic_struct(
+                    "Paginated",
+                    &["T"],
+                    vec![("items", FieldType::Vec(Box::new(param())))],
+                ),
+            ),
+            (
+                "Audited".to_string(),
+                generic_struct(
+                    "Audited",
+                    &["T"],
+                    vec![("value", param()), ("by", FieldType::String)],
+                ),
+            ),
+            (
+                "Page".to_string(),
+                generic_struct(
+                    "Page",
+                    &[],
+                    vec![("results", generic("ApiResult", vec![user()]))],
+                ),
+            ),
+        ]);
+        let mut enums = HashMap::from([("ApiResult".to_string(), api_result())]);
+
+        // Instances nested in other instances are registered too
+        let audited = generic("Audited", vec![user()]);
+        register_generic_instances(
+            &FieldType::Option(Box::new(generic("Paginated", vec![audited.clone()]))),
+            &mut structs,
+            &mut enums,
+        );
+        assert_eq!(
+            structs["Paginated<Audited<User>>"].fields[0].field_type,
+            FieldType::Vec(Box::new(audited))
+        );
+        assert_eq!(structs["Audited<User>"].fields[0].field_type, user());
+
+        // As are the instances used by named structs, and the inline structs of enums
+        register_generic_instances(
+            &FieldType::Other("Page".to_string()),
+            &mut structs,
+            &mut enums,
+        );
+        assert_eq!(enums["ApiResult<User>"].enum_name, "ApiResult<User>");
+        assert_eq!(
+            structs["ApiResultFailure<User>"].fields[1].field_type,
+            FieldType::Option(Box::new(user()))
+        );
+        assert!(!structs.contains_key("Paginated<User>"));
+    }
 }
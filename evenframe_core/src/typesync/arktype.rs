@@ -1,6 +1,7 @@
 use crate::default::field_type_to_default_value;
 use crate::types::StructConfig;
-use crate::types::{FieldType, TaggedUnion, VariantData};
+use crate::types::{FieldType, GeometryKind, StructKind, TaggedUnion, VariantData};
+use crate::typesync::config::BigNumberMode;
 use convert_case::{Case, Casing};
 use std::collections::HashMap;
 use tracing;
@@ -9,6 +10,7 @@ pub fn field_type_to_arktype(
     field_type: &FieldType,
     structs: &HashMap<String, StructConfig>,
     enums: &HashMap<String, TaggedUnion>,
+    big_numbers: BigNumberMode,
 ) -> String {
     tracing::trace!(field_type = ?field_type, "Converting field type to Arktype");
     match field_type {
@@ -16,21 +18,42 @@ pub fn field_type_to_arktype(
         FieldType::Char => "'string'".to_string(),
         FieldType::Bool => "'boolean'".to_string(),
         FieldType::Unit => "'null'".to_string(),
-        FieldType::Decimal => "'number'".to_string(),
+        FieldType::Decimal => match big_numbers {
+            BigNumberMode::Number => "'number'".to_string(),
+            // Decimals serialize as strings, which keeps their full precision
+            BigNumberMode::BigInt | BigNumberMode::String => "'string.numeric'".to_string(),
+        },
+        FieldType::Uuid => "'string.uuid'".to_string(),
+        FieldType::NaiveDate | FieldType::NaiveTime => "'string'".to_string(),
+        FieldType::Duration => "{ secs: 'number', nanos: 'number' }".to_string(), // serde's std Duration layout
+        FieldType::Bytes => "'number[]'".to_string(),
+        FieldType::JsonValue => "'unknown'".to_string(),
+        FieldType::IpAddr => "'string.ip'".to_string(),
+        FieldType::Url => "'string.url'".to_string(),
+        FieldType::Geometry(kind) => geometry_to_arktype(*kind),
         FieldType::OrderedFloat(_inner) => "'number'".to_string(), // OrderedFloat is treated as number
         FieldType::F32 | FieldType::F64 => "'number'".to_string(),
         FieldType::I8
         | FieldType::I16
         | FieldType::I32
-        | FieldType::I64
-        | FieldType::I128
-        | FieldType::Isize => "'number'".to_string(),
-        FieldType::U8
+        | FieldType::U8
         | FieldType::U16
-        | FieldType::U32
+        | FieldType::U32 => {
+            let (min, max) = field_type.integer_bounds().unwrap_or_default();
+            format!("'{} <= number.integer <= {}'", min, max)
+        }
+        FieldType::I64
+        | FieldType::I128
+        | FieldType::Isize
         | FieldType::U64
         | FieldType::U128
-        | FieldType::Usize => "'number'".to_string(),
+        | FieldType::Usize => match big_numbers {
+            BigNumberMode::Number => "'number'".to_string(),
+            BigNumberMode::BigInt => {
+                "['string.integer', '=>', (s: string) => BigInt(s)]".to_string()
+            }
+            BigNumberMode::String => "'string.integer'".to_string(),
+        },
         FieldType::EvenframeRecordId => r#""string""#.to_string(),
         FieldType::DateTime => "'string'".to_string(),
         FieldType::EvenframeDuration => "'number'".to_string(), // nanoseconds
@@ -39,7 +62,7 @@ pub fn field_type_to_arktype(
         FieldType::Tuple(types) => {
             let types_str = types
                 .iter()
-                .map(|t| field_type_to_arktype(t, structs, enums))
+                .map(|t| field_type_to_arktype(t, structs, enums, big_numbers))
                 .collect::<Vec<String>>()
                 .join(", ");
             format!("[{}]", types_str)
@@ -52,7 +75,7 @@ pub fn field_type_to_arktype(
                     format!(
                         "{}: {}",
                         name,
-                        field_type_to_arktype(field_type, structs, enums)
+                        field_type_to_arktype(field_type, structs, enums, big_numbers)
                     )
                 })
                 .collect::<Vec<String>>()
@@ -63,34 +86,59 @@ pub fn field_type_to_arktype(
         FieldType::Option(inner) => {
             format!(
                 "[[{}, '|', 'undefined'], '|', 'null']",
-                field_type_to_arktype(inner, structs, enums)
+                field_type_to_arktype(inner, structs, enums, big_numbers)
             )
         }
 
         FieldType::Vec(inner) => {
-            format!("[{}, '[]']", field_type_to_arktype(inner, structs, enums))
+            format!(
+                "[{}, '[]']",
+                field_type_to_arktype(inner, structs, enums, big_numbers)
+            )
+        }
+
+        // Sets serialize as arrays
+        FieldType::HashSet(inner) | FieldType::BTreeSet(inner) => {
+            format!(
+                "[{}, '[]']",
+                field_type_to_arktype(inner, structs, enums, big_numbers)
+            )
         }
 
         FieldType::HashMap(key, value) => {
             format!(
                 "'Record<{}, {}>'",
-                field_type_to_arktype(key, structs, enums).replace('\'', ""),
-                field_type_to_arktype(value, structs, enums).replace('\'', "")
+                field_type_to_arktype(key, structs, enums, big_numbers).replace('\'', ""),
+                field_type_to_arktype(value, structs, enums, big_numbers).replace('\'', "")
             )
         }
         FieldType::BTreeMap(key, value) => {
             format!(
                 "'Record<{}, {}>'",
-                field_type_to_arktype(key, structs, enums).replace('\'', ""),
-                field_type_to_arktype(value, structs, enums).replace('\'', "")
+                field_type_to_arktype(key, structs, enums, big_numbers).replace('\'', ""),
+                field_type_to_arktype(value, structs, enums, big_numbers).replace('\'', "")
             )
         }
 
         FieldType::RecordLink(inner) => format!(
             r#"[{}, "|",  "string"]"#,
-            field_type_to_arktype(inner, structs, enums)
+            field_type_to_arktype(inner, structs, enums, big_numbers)
         ),
 
+        FieldType::GenericParam(param) => format!("'{}'", param),
+
+        FieldType::Generic(type_name, args) => {
+            // Generic scope aliases are instantiated with string syntax, e.g. 'Paginated<User>'
+            let args_str = args
+                .iter()
+                .map(|arg| {
+                    field_type_to_arktype(arg, structs, enums, big_numbers).replace('\'', "")
+                })
+                .collect::<Vec<String>>()
+                .join(", ");
+            format!("'{}<{}>'", type_name.to_case(Case::Pascal), args_str)
+        }
+
         FieldType::Other(type_name) => {
             // Try to find a matching struct
             for struct_config in structs.values() {
@@ -118,7 +166,12 @@ pub fn field_type_to_arktype(
                                 }
                                 VariantData::DataStructureRef(field_type) => field_type,
                             };
-                            field_type_to_arktype(variant_data_field_type, structs, enums)
+                            field_type_to_arktype(
+                                variant_data_field_type,
+                                structs,
+                                enums,
+                                big_numbers,
+                            )
                         } else {
                             format!("'{}'", variant.name.to_case(Case::Pascal))
                         }
@@ -133,15 +186,61 @@ pub fn field_type_to_arktype(
     }
 }
 
+/// GeoJSON definition for a geometry, e.g. `{ type: "'Point'", coordinates: ['number', 'number'] }`
+fn geometry_to_arktype(kind: GeometryKind) -> String {
+    let depth = match kind {
+        GeometryKind::Point => 0,
+        GeometryKind::LineString | GeometryKind::MultiPoint => 1,
+        GeometryKind::Polygon | GeometryKind::MultiLineString => 2,
+        GeometryKind::MultiPolygon => 3,
+        GeometryKind::Any => {
+            return [
+                GeometryKind::Point,
+                GeometryKind::LineString,
+                GeometryKind::Polygon,
+                GeometryKind::MultiPoint,
+                GeometryKind::MultiLineString,
+                GeometryKind::MultiPolygon,
+            ]
+            .into_iter()
+            .map(geometry_to_arktype)
+            .reduce(|union, item| format!("[{}, '|', {}]", union, item))
+            .unwrap_or_default();
+        }
+    };
+    let mut coordinates = "['number', 'number']".to_string();
+    for _ in 0..depth {
+        coordinates = format!("[{}, '[]']", coordinates);
+    }
+    format!(
+        "{{ type: \"'{}'\", coordinates: {} }}",
+        kind.geojson_type().unwrap_or("Point"),
+        coordinates
+    )
+}
+
+/// Scope key for a type, declaring its parameters for generic definitions (e.g. `"Paginated<T>"`)
+fn scope_key(type_name: &str, generics: &[String]) -> String {
+    if generics.is_empty() {
+        type_name.to_string()
+    } else {
+        format!("\"{}<{}>\"", type_name, generics.join(", "))
+    }
+}
+
 pub fn generate_arktype_type_string(
     structs: &HashMap<String, StructConfig>,
     enums: &HashMap<String, TaggedUnion>,
     print_types: bool,
+    brand_newtypes: bool,
+    big_numbers: BigNumberMode,
 ) -> String {
     tracing::info!(
         struct_count = structs.len(),
         enum_count = enums.len(),
         print_types = print_types,
+        brand_newtypes = brand_newtypes,
+        big_numbers = ?big_numbers,
         "Generating Arktype type string"
     );
     let mut output = String::new();
@@ -156,7 +255,10 @@ pub fn generate_arktype_type_string(
         // Write the Arktype binding name
         scope_output.push_str(&format!(
             "{}: ",
-            schema_enum.enum_name.to_case(Case::Pascal)
+            scope_key(
+                &schema_enum.enum_name.to_case(Case::Pascal),
+                &schema_enum.generics
+            )
         ));
 
         // We'll accumulate the "nesting" into this string.
@@ -171,7 +273,7 @@ pub fn generate_arktype_type_string(
                     }
                     VariantData::DataStructureRef(field_type) => field_type,
                 };
-                field_type_to_arktype(variant_data_field_type, structs, enums)
+                field_type_to_arktype(variant_data_field_type, structs, enums, big_numbers)
             } else {
                 // For simple string variants, e.g. ["===", "Residential"]
                 format!("['===', '{}']", variant.name)
@@ -189,6 +291,11 @@ pub fn generate_arktype_type_string(
         // Now write out the final folded union string in your scope output
         scope_output.push_str(&format!("{},\n", union_ast));
 
+        // Generic aliases have no inferred type until instantiated
+        if !schema_enum.generics.is_empty() {
+            continue;
+        }
+
         // And write the corresponding TypeScript type
         types_output.push_str(&format!(
             "export type {} = typeof bindings.{}.infer;\n",
@@ -202,8 +309,49 @@ pub fn generate_arktype_type_string(
     for struct_config in structs.values() {
         tracing::trace!(struct_name = %struct_config.struct_name, "Processing struct");
         let type_name = struct_config.struct_name.to_case(Case::Pascal);
-        scope_output.push_str(&format!("{}: {{\n", type_name));
-        defaults_output.push_str(&format!(
+        let is_generic = !struct_config.generics.is_empty();
+
+        // Newtypes and tuple structs are aliases of their inner type(s)
+        if struct_config.kind != StructKind::Named {
+            let definition = if let Some(inner) = struct_config.newtype_field() {
+                let inner_def =
+                    field_type_to_arktype(&inner.field_type, structs, enums, big_numbers);
+                match inner_def.strip_suffix('\'') {
+                    // String definitions can carry a brand, e.g. 'string#Email'
+                    Some(unquoted) if brand_newtypes && inner_def.starts_with('\'') => {
+                        format!("{}#{}'", unquoted, type_name)
+                    }
+                    _ => inner_def,
+                }
+            } else {
+                let items = struct_config
+                    .fields
+                    .iter()
+                    .map(|f| field_type_to_arktype(&f.field_type, structs, enums, big_numbers))
+                    .collect::<Vec<String>>();
+                format!("[{}]", items.join(", "))
+            };
+            scope_output.push_str(&format!(
+                "{}: {},\n",
+                scope_key(&type_name, &struct_config.generics),
+                definition
+            ));
+            if !is_generic {
+                types_output.push_str(&format!(
+                    "export type {} = typeof bindings.{}.infer;\n",
+                    type_name, type_name
+                ));
+            }
+            continue;
+        }
+
+        scope_output.push_str(&format!(
+            "{}: {{\n",
+            scope_key(&type_name, &struct_config.generics)
+        ));
+        // Defaults and inferred types only exist for concrete structs
+        let mut struct_defaults = String::new();
+        struct_defaults.push_str(&format!(
             "export const default{}: {} = {{\n",
             &type_name, &type_name
         ));
@@ -214,24 +362,28 @@ pub fn generate_arktype_type_string(
             scope_output.push_str(&format!(
                 "  {}: {}",
                 field_name,
-                field_type_to_arktype(&field.field_type, structs, enums)
+                field_type_to_arktype(&field.field_type, structs, enums, big_numbers)
             ));
-            defaults_output.push_str(&format!(
+            struct_defaults.push_str(&format!(
                 "{}: {}",
                 field_name,
-                field_type_to_default_value(&field.field_type, structs, enums)
+                field_type_to_default_value(&field.field_type, structs, enums, big_numbers)
             ));
             // Add a comma if it's not the last field
             if Some(field) != struct_config.fields.last() {
                 scope_output.push_str(",\n");
-                defaults_output.push_str(",\n");
+                struct_defaults.push_str(",\n");
             } else {
                 scope_output.push('\n');
             }
         }
 
         scope_output.push_str("},\n");
-        defaults_output.push_str("\n};\n");
+        struct_defaults.push_str("\n};\n");
+        if is_generic {
+            continue;
+        }
+        defaults_output.push_str(&struct_defaults);
         types_output.push_str(&format!(
             "export type {} = typeof bindings.{}.infer;\n",
             type_name, type_name
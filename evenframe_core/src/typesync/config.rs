@@ -11,4 +11,28 @@ pub struct TypesyncConfig {
     pub should_generate_surrealdb_schemas: bool,
     /// Output path for generated type files
     pub output_path: String,
+    /// Whether newtypes (e.g. `struct Email(String)`) are emitted as branded
+    /// types instead of plain aliases of their inner type
+    #[serde(default)]
+    pub should_brand_newtypes: bool,
+    /// How 64/128-bit integers and `Decimal` are represented in TypeScript
+    #[serde(default)]
+    pub big_number_mode: BigNumberMode,
+    /// Whether to emit helpers that decode live query notifications of each table
+    /// with its generated schema
+    #[serde(default)]
+    pub should_generate_live_query_helpers: bool,
+}
+
+/// TypeScript representation of values that do not fit in a JavaScript `number`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BigNumberMode {
+    /// Plain `number`, losing precision above 2^53. Keeps the output of earlier versions
+    #[default]
+    Number,
+    /// `bigint` for wide integers and `BigDecimal` for decimals, both string-encoded
+    BigInt,
+    /// Decimal strings, left for the application to parse
+    String,
 }
\ No newline at end of file
@@ -1,5 +1,6 @@
 use crate::dependency::{RecursionInfo, analyse_recursion, deps_of};
-use crate::types::{FieldType, StructConfig, TaggedUnion, VariantData};
+use crate::types::{FieldType, GeometryKind, StructConfig, StructKind, TaggedUnion, VariantData};
+use crate::typesync::config::BigNumberMode;
 use crate::validator::{
     ArrayValidator, BigDecimalValidator, BigIntValidator, DateValidator, DurationValidator,
     NumberValidator, StringValidator, Validator,
@@ -13,11 +14,15 @@ pub fn generate_effect_schema_string(
     structs: &HashMap<String, StructConfig>,
     enums: &HashMap<String, TaggedUnion>,
     print_types: bool,
+    brand_newtypes: bool,
+    big_numbers: BigNumberMode,
 ) -> String {
     tracing::info!(
         struct_count = structs.len(),
         enum_count = enums.len(),
         print_types = print_types,
+        brand_newtypes = brand_newtypes,
+        big_numbers = ?big_numbers,
         "Generating Effect Schema string"
     );
 
@@ -59,7 +64,7 @@ pub fn generate_effect_schema_string(
 
     // Helper closure for field conversion that has access to `rec`.
     let to_schema = |ft: &FieldType, cur: &str, proc: &HashSet<String>| -> String {
-        field_type_to_effect_schema(ft, structs, cur, &rec, proc)
+        field_type_to_effect_schema(ft, structs, cur, &rec, proc, big_numbers)
     };
 
     for comp_id in ordered_comps {
@@ -77,8 +82,13 @@ pub fn generate_effect_schema_string(
                 .find(|e| e.enum_name.to_case(Case::Pascal) == name)
             {
                 // ---- ENUM ---------------------------------------------------
-                // Generate the schema class for the enum.
-                out_classes.push_str(&format!("export const {} = Schema.Union(", name));
+                // Generate the schema class for the enum. Generic enums become
+                // factories taking one schema per type parameter.
+                out_classes.push_str(&format!(
+                    "export const {} = {}Schema.Union(",
+                    name,
+                    generic_factory_prefix(&e.generics)
+                ));
                 let variants = e
                     .variants
                     .iter()
@@ -99,23 +109,72 @@ pub fn generate_effect_schema_string(
                 out_classes.push_str(&format!(").annotations({{ identifier: `{}` }});\n", name));
 
                 // Generate the `.Type` alias.
-                out_types.push_str(&format!(
-                    "export type {}Type = typeof {}.Type;\n",
-                    name, name
-                ));
+                out_types.push_str(&type_alias(&name, &e.generics));
 
                 // Generate the `...Encoded` type alias for the enum.
-                out_encoded.push_str(&encoded_alias_for_enum(e));
+                out_encoded.push_str(&encoded_alias_for_enum(e, big_numbers));
             } else if let Some(struct_config) = structs
                 .values()
                 .find(|sc| sc.struct_name.to_case(Case::Pascal) == name)
             {
+                if struct_config.kind != StructKind::Named {
+                    // ---- NEWTYPE / TUPLE STRUCT ---------------------------------
+                    // These are schemas of their inner type(s) rather than classes.
+                    let schema = if let Some(inner) = struct_config.newtype_field() {
+                        let schema = to_schema(&inner.field_type, &name, &processed);
+                        let mut validators = inner.validators.clone();
+                        validators.extend(struct_config.validators.iter().cloned());
+                        let schema = apply_validators_to_schema(schema, &validators);
+                        if brand_newtypes {
+                            format!("{}.pipe(Schema.brand(\"{}\"))", schema, name)
+                        } else {
+                            schema
+                        }
+                    } else {
+                        let items = struct_config
+                            .fields
+                            .iter()
+                            .map(|f| {
+                                apply_validators_to_schema(
+                                    to_schema(&f.field_type, &name, &processed),
+                                    &f.validators,
+                                )
+                            })
+                            .collect::<Vec<_>>();
+                        format!("Schema.Tuple({})", items.join(", "))
+                    };
+                    out_classes.push_str(&format!(
+                        "export const {} = {}{}.annotations({{ identifier: `{}` }});\n\n",
+                        name,
+                        generic_factory_prefix(&struct_config.generics),
+                        schema,
+                        name
+                    ));
+                    out_types.push_str(&type_alias(&name, &struct_config.generics));
+                    out_encoded.push_str(&encoded_alias_for_positional_struct(
+                        struct_config,
+                        big_numbers,
+                    ));
+                    processed.insert(name);
+                    continue;
+                }
+
                 // ---- STRUCT -------------------------------------------------
-                // Generate the schema class for the struct.
-                out_classes.push_str(&format!(
-                    "export class {} extends Schema.Class<{}>(\"{}\")( {{\n",
-                    name, name, name
-                ));
+                // Generate the schema class for the struct. Generic structs can't be
+                // classes, so they become factories returning a `Schema.Struct`.
+                let is_generic = !struct_config.generics.is_empty();
+                if is_generic {
+                    out_classes.push_str(&format!(
+                        "export const {} = {}Schema.Struct({{\n",
+                        name,
+                        generic_factory_prefix(&struct_config.generics)
+                    ));
+                } else {
+                    out_classes.push_str(&format!(
+                        "export class {} extends Schema.Class<{}>(\"{}\")( {{\n",
+                        name, name, name
+                    ));
+                }
                 for (idx, f) in struct_config.fields.iter().enumerate() {
                     let schema = to_schema(&f.field_type, &name, &processed);
                     let schema_with_validators = apply_validators_to_schema(schema, &f.validators);
@@ -131,16 +190,20 @@ pub fn generate_effect_schema_string(
                     ));
                     out_classes.push('\n');
                 }
-                out_classes.push_str("}) {[key: string]: unknown}\n\n");
+                if is_generic {
+                    out_classes.push_str(&format!(
+                        "}}).annotations({{ identifier: `{}` }});\n\n",
+                        name
+                    ));
+                } else {
+                    out_classes.push_str("}) {[key: string]: unknown}\n\n");
+                }
 
                 // Generate the `.Type` alias.
-                out_types.push_str(&format!(
-                    "export type {}Type = typeof {}.Type;\n",
-                    name, name
-                ));
+                out_types.push_str(&type_alias(&name, &struct_config.generics));
 
                 // Generate the `...Encoded` interface for the struct.
-                out_encoded.push_str(&encoded_interface_for_struct(struct_config));
+                out_encoded.push_str(&encoded_interface_for_struct(struct_config, big_numbers));
             }
             processed.insert(name);
         }
@@ -159,10 +222,61 @@ pub fn generate_effect_schema_string(
     result
 }
 
+// ----- Generic Definition Helpers ------------------------------------------
+
+/// `<T extends Schema.Schema.Any>(T: T) => ` for generic definitions, empty otherwise.
+fn generic_factory_prefix(generics: &[String]) -> String {
+    if generics.is_empty() {
+        return String::new();
+    }
+    let params = generics
+        .iter()
+        .map(|g| format!("{} extends Schema.Schema.Any", g))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let args = generics
+        .iter()
+        .map(|g| format!("{}: {}", g, g))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("<{}>({}) => ", params, args)
+}
+
+/// The `...Type` alias, instantiating the factory for generic definitions.
+fn type_alias(name: &str, generics: &[String]) -> String {
+    if generics.is_empty() {
+        return format!("export type {}Type = typeof {}.Type;\n", name, name);
+    }
+    let params = generics
+        .iter()
+        .map(|g| format!("{} extends Schema.Schema.Any", g))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "export type {}Type<{}> = Schema.Schema.Type<ReturnType<typeof {}<{}>>>;\n",
+        name,
+        params,
+        name,
+        generics.join(", ")
+    )
+}
+
+/// `<T, E>` for generic definitions, empty otherwise.
+fn encoded_params(generics: &[String]) -> String {
+    if generics.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", generics.join(", "))
+    }
+}
+
 // ----- Encoded Type Generation Helpers -------------------------------------
 
 /// Generates an `...Encoded` TypeScript interface for a given struct.
-fn encoded_interface_for_struct(struct_config: &StructConfig) -> String {
+fn encoded_interface_for_struct(
+    struct_config: &StructConfig,
+    big_numbers: BigNumberMode,
+) -> String {
     let name = struct_config.struct_name.to_case(Case::Pascal);
     let body = struct_config
         .fields
@@ -171,17 +285,48 @@ fn encoded_interface_for_struct(struct_config: &StructConfig) -> String {
             format!(
                 "  readonly {}: {};",
                 f.field_name.to_case(Case::Camel),
-                field_type_to_ts_encoded(&f.field_type)
+                field_type_to_ts_encoded(&f.field_type, big_numbers)
             )
         })
         .collect::<Vec<_>>()
         .join("\n");
 
-    format!("export interface {}Encoded {{\n{}\n}}\n\n", name, body)
+    format!(
+        "export interface {}Encoded{} {{\n{}\n}}\n\n",
+        name,
+        encoded_params(&struct_config.generics),
+        body
+    )
+}
+
+/// Generates an `...Encoded` TypeScript type alias for a newtype or tuple struct.
+fn encoded_alias_for_positional_struct(
+    struct_config: &StructConfig,
+    big_numbers: BigNumberMode,
+) -> String {
+    let name = struct_config.struct_name.to_case(Case::Pascal);
+    let encoded = match struct_config.newtype_field() {
+        Some(inner) => field_type_to_ts_encoded(&inner.field_type, big_numbers),
+        None => {
+            let items = struct_config
+                .fields
+                .iter()
+                .map(|f| field_type_to_ts_encoded(&f.field_type, big_numbers))
+                .collect::<Vec<_>>();
+            format!("readonly [{}]", items.join(", "))
+        }
+    };
+
+    format!(
+        "export type {}Encoded{} = {};\n\n",
+        name,
+        encoded_params(&struct_config.generics),
+        encoded
+    )
 }
 
 /// Generates an `...Encoded` TypeScript type alias for a given enum/union.
-fn encoded_alias_for_enum(en: &TaggedUnion) -> String {
+fn encoded_alias_for_enum(en: &TaggedUnion, big_numbers: BigNumberMode) -> String {
     tracing::trace!(enum_name = %en.enum_name, "Creating encoded alias for enum");
     let name = en.enum_name.to_case(Case::Pascal);
     let union = en
@@ -193,14 +338,21 @@ fn encoded_alias_for_enum(en: &TaggedUnion) -> String {
                     // For inline structs, use the variant name + "Encoded"
                     format!("{}Encoded", v.name.to_case(Case::Pascal))
                 }
-                VariantData::DataStructureRef(field_type) => field_type_to_ts_encoded(field_type),
+                VariantData::DataStructureRef(field_type) => {
+                    field_type_to_ts_encoded(field_type, big_numbers)
+                }
             },
             None => format!("\"{}\"", v.name),
         })
         .collect::<Vec<_>>()
         .join(" | ");
 
-    format!("export type {}Encoded = {};\n\n", name, union)
+    format!(
+        "export type {}Encoded{} = {};\n\n",
+        name,
+        encoded_params(&en.generics),
+        union
+    )
 }
 
 // ----- Schema and Type Conversion Logic ------------------------------------
@@ -212,37 +364,70 @@ fn field_type_to_effect_schema(
     current: &str,
     rec: &RecursionInfo,
     processed: &HashSet<String>,
+    big_numbers: BigNumberMode,
 ) -> String {
     // Helper to recurse with the same context.
     let field = |inner: &FieldType| -> String {
-        field_type_to_effect_schema(inner, structs, current, rec, processed)
+        field_type_to_effect_schema(inner, structs, current, rec, processed, big_numbers)
     };
     match field_type {
         FieldType::String => "Schema.String".to_string(),
         FieldType::Char => "Schema.String".to_string(),
         FieldType::Bool => "Schema.Boolean".to_string(),
         FieldType::Unit => "Schema.Null".to_string(),
-        FieldType::Decimal => "Schema.Number".to_string(),
+        FieldType::Decimal => match big_numbers {
+            BigNumberMode::Number => "Schema.Number".to_string(),
+            BigNumberMode::BigInt => "Schema.BigDecimal".to_string(),
+            BigNumberMode::String => {
+                r"Schema.String.pipe(Schema.pattern(/^-?\d+(\.\d+)?$/))".to_string()
+            }
+        },
         FieldType::OrderedFloat(_) => "Schema.Number".to_string(),
         FieldType::F32 | FieldType::F64 => "Schema.Number".to_string(),
         FieldType::I8
         | FieldType::I16
         | FieldType::I32
-        | FieldType::I64
-        | FieldType::I128
-        | FieldType::Isize => "Schema.Number".to_string(),
-        FieldType::U8
+        | FieldType::U8
         | FieldType::U16
-        | FieldType::U32
+        | FieldType::U32 => {
+            let (min, max) = field_type.integer_bounds().unwrap_or_default();
+            format!("Schema.Int.pipe(Schema.between({}, {}))", min, max)
+        }
+        FieldType::I64
+        | FieldType::I128
+        | FieldType::Isize
         | FieldType::U64
         | FieldType::U128
-        | FieldType::Usize => "Schema.Number".to_string(),
+        | FieldType::Usize => match big_numbers {
+            BigNumberMode::Number => "Schema.Number".to_string(),
+            // Decodes the string-encoded integer into a `bigint`
+            BigNumberMode::BigInt => "Schema.BigInt".to_string(),
+            BigNumberMode::String => r"Schema.String.pipe(Schema.pattern(/^-?\d+$/))".to_string(),
+        },
         FieldType::EvenframeRecordId => "Schema.String".to_string(),
         FieldType::DateTime => "Schema.DateTimeUtc".to_string(),
         FieldType::EvenframeDuration => "Schema.Duration".to_string(),
         FieldType::Timezone => "Schema.TimeZoneNamed".to_string(),
+        FieldType::Uuid => "Schema.UUID".to_string(),
+        FieldType::NaiveDate => {
+            r"Schema.String.pipe(Schema.pattern(/^\d{4}-\d{2}-\d{2}$/))".to_string()
+        }
+        FieldType::NaiveTime => {
+            r"Schema.String.pipe(Schema.pattern(/^\d{2}:\d{2}:\d{2}(\.\d+)?$/))".to_string()
+        }
+        FieldType::Duration => {
+            "Schema.Struct({ secs: Schema.Number, nanos: Schema.Number })".to_string()
+        }
+        FieldType::Bytes => "Schema.Array(Schema.Number)".to_string(),
+        FieldType::JsonValue => "Schema.Unknown".to_string(),
+        FieldType::IpAddr => "Schema.String".to_string(),
+        FieldType::Url => "Schema.URL".to_string(),
+        FieldType::Geometry(kind) => geometry_to_effect_schema(*kind),
         FieldType::Option(i) => format!("Schema.OptionFromNullishOr({}, null)", field(i)),
         FieldType::Vec(i) => format!("Schema.Array({})", field(i)),
+        FieldType::HashSet(i) | FieldType::BTreeSet(i) => {
+            format!("Schema.ReadonlySet({})", field(i))
+        }
         FieldType::Tuple(v) => format!(
             "Schema.Tuple({})",
             v.iter().map(field).collect::<Vec<_>>().join(", ")
@@ -263,16 +448,21 @@ fn field_type_to_effect_schema(
                 field(v)
             )
         }
+        FieldType::GenericParam(param) => param.clone(),
+        FieldType::Generic(name, args) => format!(
+            "{}({})",
+            name.to_case(Case::Pascal),
+            args.iter().map(field).collect::<Vec<_>>().join(", ")
+        ),
         FieldType::Other(name) => {
             let pascal = name.to_case(Case::Pascal);
             let wrap_id = format!("{}Ref", pascal);
             // Decide whether we need Schema.suspend for recursion.
             if rec.is_recursive_pair(current, &pascal) && !processed.contains(&pascal) {
                 // Forward edge *inside* a recursive SCC requires suspension.
-                if structs
-                    .values()
-                    .any(|sc| sc.struct_name.to_case(Case::Pascal) == pascal)
-                {
+                if structs.values().any(|sc| {
+                    sc.struct_name.to_case(Case::Pascal) == pascal && sc.kind == StructKind::Named
+                }) {
                     format!(
                         "Schema.suspend((): Schema.Schema<{}, {}Encoded> => {}).annotations({{ identifier: `{}` }})",
                         pascal, pascal, pascal, wrap_id
@@ -292,21 +482,33 @@ fn field_type_to_effect_schema(
 }
 
 /// Converts a `FieldType` into its corresponding raw TypeScript type for the `...Encoded` interface.
-fn field_type_to_ts_encoded(ft: &FieldType) -> String {
-    let enc = |f: &FieldType| field_type_to_ts_encoded(f);
+fn field_type_to_ts_encoded(ft: &FieldType, big_numbers: BigNumberMode) -> String {
+    let enc = |f: &FieldType| field_type_to_ts_encoded(f, big_numbers);
 
     match ft {
         // Primitives
         FieldType::String
         | FieldType::Char
         | FieldType::EvenframeRecordId
-        | FieldType::Timezone => "string".into(),
+        | FieldType::Timezone
+        | FieldType::Uuid
+        | FieldType::NaiveDate
+        | FieldType::NaiveTime
+        | FieldType::IpAddr
+        | FieldType::Url => "string".into(),
         FieldType::Bool => "boolean".into(),
         FieldType::DateTime => "string".into(), // ISO 8601 string
         FieldType::EvenframeDuration => {
             "| Schema.DurationEncoded |readonly [seconds: number, nanos: number]".into()
         }
         FieldType::Unit => "null".into(),
+        FieldType::Duration => "{ readonly secs: number; readonly nanos: number }".into(),
+        FieldType::Bytes => "ReadonlyArray<number>".into(),
+        FieldType::JsonValue => "unknown".into(),
+        FieldType::Geometry(kind) => geometry_to_ts_encoded(*kind),
+        // Values outside the safe `number` range travel as strings
+        FieldType::Decimal if big_numbers != BigNumberMode::Number => "string".into(),
+        ft if ft.is_wide_integer() && big_numbers != BigNumberMode::Number => "string".into(),
         FieldType::Decimal
         | FieldType::OrderedFloat(_)
         | FieldType::F32
@@ -326,7 +528,9 @@ fn field_type_to_ts_encoded(ft: &FieldType) -> String {
 
         // Containers
         FieldType::Option(inner) => format!("{} | null | undefined", enc(inner)),
-        FieldType::Vec(inner) => format!("ReadonlyArray<{}>", enc(inner)),
+        FieldType::Vec(inner) | FieldType::HashSet(inner) | FieldType::BTreeSet(inner) => {
+            format!("ReadonlyArray<{}>", enc(inner))
+        }
         FieldType::Tuple(items) => {
             let elems = items.iter().map(enc).collect::<Vec<_>>().join(", ");
             format!("[{}]", elems)
@@ -345,10 +549,70 @@ fn field_type_to_ts_encoded(ft: &FieldType) -> String {
         FieldType::RecordLink(inner) => format!("string | {}", enc(inner)),
 
         // User-defined types
+        FieldType::GenericParam(param) => param.clone(),
+        FieldType::Generic(name, args) => format!(
+            "{}Encoded<{}>",
+            name.to_case(Case::Pascal),
+            args.iter().map(enc).collect::<Vec<_>>().join(", ")
+        ),
         FieldType::Other(name) => format!("{}Encoded", name.to_case(Case::Pascal)),
     }
 }
 
+// ----- Geometry Logic -----------------------------------------------------
+
+const GEOMETRY_KINDS: [GeometryKind; 6] = [
+    GeometryKind::Point,
+    GeometryKind::LineString,
+    GeometryKind::Polygon,
+    GeometryKind::MultiPoint,
+    GeometryKind::MultiLineString,
+    GeometryKind::MultiPolygon,
+];
+
+/// Array nesting of a geometry's `coordinates` around a single position
+fn geometry_depth(kind: GeometryKind) -> usize {
+    match kind {
+        GeometryKind::Point | GeometryKind::Any => 0,
+        GeometryKind::LineString | GeometryKind::MultiPoint => 1,
+        GeometryKind::Polygon | GeometryKind::MultiLineString => 2,
+        GeometryKind::MultiPolygon => 3,
+    }
+}
+
+/// Converts a geometry into a GeoJSON `Schema.Struct`.
+fn geometry_to_effect_schema(kind: GeometryKind) -> String {
+    if kind == GeometryKind::Any {
+        let members = GEOMETRY_KINDS.map(geometry_to_effect_schema);
+        return format!("Schema.Union({})", members.join(", "));
+    }
+    let mut coordinates = "Schema.Tuple(Schema.Number, Schema.Number)".to_string();
+    for _ in 0..geometry_depth(kind) {
+        coordinates = format!("Schema.Array({})", coordinates);
+    }
+    format!(
+        "Schema.Struct({{ type: Schema.Literal(\"{}\"), coordinates: {} }})",
+        kind.geojson_type().unwrap_or("Point"),
+        coordinates
+    )
+}
+
+/// Converts a geometry into its GeoJSON TypeScript type.
+fn geometry_to_ts_encoded(kind: GeometryKind) -> String {
+    if kind == GeometryKind::Any {
+        return GEOMETRY_KINDS.map(geometry_to_ts_encoded).join(" | ");
+    }
+    let mut coordinates = "readonly [number, number]".to_string();
+    for _ in 0..geometry_depth(kind) {
+        coordinates = format!("ReadonlyArray<{}>", coordinates);
+    }
+    format!(
+        "{{ readonly type: \"{}\"; readonly coordinates: {} }}",
+        kind.geojson_type().unwrap_or("Point"),
+        coordinates
+    )
+}
+
 // ----- Validator Application Logic -----------------------------------------
 
 /// Applies a series of validators to a schema string by chaining `.pipe()` calls.
@@ -0,0 +1,117 @@
+use crate::schemasync::TableConfig;
+use convert_case::{Case, Casing};
+use std::collections::HashMap;
+
+/// Live notification actions as sent by SurrealDB
+const LIVE_ACTIONS: [&str; 3] = ["CREATE", "UPDATE", "DELETE"];
+
+/// Tables that get live notification helpers, sorted for deterministic output.
+/// Generic tables are skipped since their schemas need type arguments.
+fn live_table_names(tables: &HashMap<String, TableConfig>) -> Vec<String> {
+    let mut names: Vec<String> = tables
+        .values()
+        .filter(|table| table.struct_config.generics.is_empty())
+        .map(|table| table.struct_config.struct_name.to_case(Case::Pascal))
+        .collect();
+    names.sort();
+    names
+}
+
+/// Effect helpers decoding `{ action, result }` live notifications with the generated
+/// table schemas. Appended to the Effect bindings, after the schemas they reference.
+pub fn generate_effect_live_helpers(tables: &HashMap<String, TableConfig>) -> String {
+    tracing::info!(
+        table_count = tables.len(),
+        "Generating Effect live query helpers"
+    );
+    let actions = LIVE_ACTIONS
+        .iter()
+        .map(|action| format!("\"{}\"", action))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let mut output = format!("export const LiveAction = Schema.Literal({});\n\n", actions);
+    for name in live_table_names(tables) {
+        output.push_str(&format!(
+            "export const {name}LiveNotification = Schema.Struct({{ action: LiveAction, result: {name} }});\n\
+             export const decode{name}LiveNotification = Schema.decodeUnknownSync({name}LiveNotification);\n\n"
+        ));
+    }
+    output
+}
+
+/// Arktype helpers decoding `{ action, result }` live notifications with the types in
+/// the exported `validator` scope. Requires `type` to be imported from 'arktype'.
+pub fn generate_arktype_live_helpers(tables: &HashMap<String, TableConfig>) -> String {
+    tracing::info!(
+        table_count = tables.len(),
+        "Generating Arktype live query helpers"
+    );
+    let actions = LIVE_ACTIONS
+        .iter()
+        .map(|action| format!("'{}'", action))
+        .collect::<Vec<_>>()
+        .join(" | ");
+
+    let mut output = format!("export const liveAction = type(\"{}\");\n\n", actions);
+    for name in live_table_names(tables) {
+        output.push_str(&format!(
+            "export const {name}LiveNotification = type({{ action: liveAction, result: validator.{name} }});\n\
+             export const decode{name}LiveNotification = (notification: unknown) => {{\n\
+             \x20 const decoded = {name}LiveNotification(notification);\n\
+             \x20 if (decoded instanceof type.errors) throw new Error(decoded.summary);\n\
+             \x20 return decoded;\n\
+             }};\n\n"
+        ));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schemasync::StructConfig;
+
+    fn table(struct_name: &str, generics: Vec<String>) -> TableConfig {
+        TableConfig {
+            table_name: struct_name.to_case(Case::Snake),
+            struct_config: StructConfig {
+                generics,
+                ..StructConfig::for_testing(struct_name, Vec::new())
+            },
+            relation: None,
+            permissions: None,
+            mock_generation_config: None,
+        }
+    }
+
+    #[test]
+    fn test_live_helpers() {
+        let tables = HashMap::from([
+            ("chat_message".to_string(), table("ChatMessage", Vec::new())),
+            ("page".to_string(), table("Page", vec!["T".to_string()])),
+        ]);
+
+        let effect = generate_effect_live_helpers(&tables);
+        assert!(effect.starts_with(
+            "export const LiveAction = Schema.Literal(\"CREATE\", \"UPDATE\", \"DELETE\");"
+        ));
+        assert!(effect.contains(
+            "export const ChatMessageLiveNotification = Schema.Struct({ action: LiveAction, result: ChatMessage });"
+        ));
+        assert!(effect.contains("export const decodeChatMessageLiveNotification"));
+
+        let arktype = generate_arktype_live_helpers(&tables);
+        assert!(
+            arktype
+                .starts_with("export const liveAction = type(\"'CREATE' | 'UPDATE' | 'DELETE'\");")
+        );
+        assert!(arktype.contains(
+            "export const ChatMessageLiveNotification = type({ action: liveAction, result: validator.ChatMessage });"
+        ));
+
+        // Generic tables need type arguments, they get no helpers
+        assert!(!effect.contains("PageLiveNotification"));
+        assert!(!arktype.contains("PageLiveNotification"));
+    }
+}
@@ -1,3 +1,4 @@
 pub mod arktype;
 pub mod config;
 pub mod effect;
+pub mod live;
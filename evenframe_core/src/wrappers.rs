@@ -259,3 +259,77 @@ impl<'de> Deserialize<'de> for EvenframeDuration {
         deserializer.deserialize_any(DurationVisitor)
     }
 }
+
+/// A `[longitude, latitude]` position, in GeoJSON order
+pub type Position = [f64; 2];
+
+/// A GeoJSON point, stored as `geometry<point>`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "Point")]
+pub struct EvenframePoint {
+    pub coordinates: Position,
+}
+
+impl EvenframePoint {
+    pub fn new(longitude: f64, latitude: f64) -> Self {
+        EvenframePoint {
+            coordinates: [longitude, latitude],
+        }
+    }
+
+    pub fn longitude(&self) -> f64 {
+        self.coordinates[0]
+    }
+
+    pub fn latitude(&self) -> f64 {
+        self.coordinates[1]
+    }
+}
+
+/// A GeoJSON line string, stored as `geometry<line>`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "LineString")]
+pub struct EvenframeLineString {
+    pub coordinates: Vec<Position>,
+}
+
+/// A GeoJSON polygon, stored as `geometry<polygon>`. The first ring is the exterior,
+/// any further rings are holes; each ring is closed (first and last position are equal).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "Polygon")]
+pub struct EvenframePolygon {
+    pub coordinates: Vec<Vec<Position>>,
+}
+
+/// A GeoJSON multi point, stored as `geometry<multipoint>`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "MultiPoint")]
+pub struct EvenframeMultiPoint {
+    pub coordinates: Vec<Position>,
+}
+
+/// A GeoJSON multi line string, stored as `geometry<multiline>`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "MultiLineString")]
+pub struct EvenframeMultiLineString {
+    pub coordinates: Vec<Vec<Position>>,
+}
+
+/// A GeoJSON multi polygon, stored as `geometry<multipolygon>`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "MultiPolygon")]
+pub struct EvenframeMultiPolygon {
+    pub coordinates: Vec<Vec<Vec<Position>>>,
+}
+
+/// Any GeoJSON geometry, stored as `geometry<feature>`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "coordinates")]
+pub enum EvenframeGeometry {
+    Point(Position),
+    LineString(Vec<Position>),
+    Polygon(Vec<Vec<Position>>),
+    MultiPoint(Vec<Position>),
+    MultiLineString(Vec<Vec<Position>>),
+    MultiPolygon(Vec<Vec<Vec<Position>>>),
+}